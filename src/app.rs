@@ -1,5 +1,10 @@
-use log::debug;
-use serde_derive::{Deserialize, Serialize};
+use std::future::Future;
+
+use gloo_timers::callback::Timeout;
+use gloo_worker::{Spawnable, WorkerBridge};
+use log::{debug, error};
+use wasm_bindgen::JsValue;
+use wasm_bindgen_futures::spawn_local;
 
 use yew::prelude::*;
 use yew::NodeRef;
@@ -7,158 +12,686 @@ use yew::NodeRef;
 use gloo_storage::LocalStorage;
 use gloo_storage::Storage;
 
-use crate::utils::{highlighter, images::build_webp_url};
-use web_sys::HtmlInputElement;
+use crate::preview::Preview;
+use crate::share::{self, Shared};
+use crate::snippets::{parse_tags, Library, Snippet};
+use crate::state::State;
+use crate::utils::{clipboard, download, images::build_webp_url, upload};
+use planetb_syntax_highligher::export::html::{self, StyleMode};
+use planetb_syntax_highligher::export::{docx, image, latex, pdf, rtf};
+use planetb_syntax_highligher::highlighter::{
+    tokenize, Language, Registry, SyntaxError, Token, TokenChunks, UpdateStats,
+};
+use planetb_syntax_highligher::theme::{import, Color, Theme};
+use planetb_syntax_highligher::worker::{
+    BuiltinLanguages, HighlightOptions, HighlightRequest, HighlightResponse, HighlightWorker,
+    Languages, WORKER_PATH,
+};
+use web_sys::{HtmlInputElement, HtmlSelectElement};
 
-const STATE_KEY: &str = "codestyle.state";
+const THEMES_KEY: &str = "codestyle.themes";
+/// Number of detected languages offered, the preselected one included.
+const DETECTED_LANGUAGES_SHOWN: usize = 3;
+/// Pause in typing after which the code is highlighted again.
+const HIGHLIGHT_DELAY_MS: u32 = 150;
+/// Tab width of the preview while no language is selected.
+const DEFAULT_TAB_WIDTH: usize = 4;
 
-pub struct App {
-    state: State,
-    textarea_ref: NodeRef,
-    webp_support: Option<bool>,
+/// Milliseconds since the page loaded, for timing the highlighting.
+fn now() -> f64 {
+    web_sys::window()
+        .and_then(|window| window.performance())
+        .map_or(0.0, |performance| performance.now())
 }
 
-#[derive(Deserialize, Serialize, Clone, Copy)]
-pub struct ProgrammingLanguage {
-    pub name: &'static str,
-    pub css_class: &'static str,
-    pub image_file: &'static str,
-    pub image_file_extension: &'static str,
+/// Extension, MIME type and content of an exported file.
+type ExportedFile = (&'static str, &'static str, Vec<u8>);
+
+/// What the last highlighting took, shown in the debug overlay.
+#[derive(Clone, Copy, Debug, Default)]
+struct HighlightStats {
+    update: UpdateStats,
+    tokens: usize,
+    detect_ms: f64,
+    tokenize_ms: f64,
 }
 
-impl ProgrammingLanguage {
-    fn to_img_url(self, webp_support: bool) -> String {
-        build_webp_url(self.image_file, self.image_file_extension, webp_support)
-    }
+/// The app, highlighting the languages of `L` in the worker as well as for the exports.
+pub struct App<L: Languages = BuiltinLanguages> {
+    state: State,
+    /// Whether the welcome message is shown, starting out as the user prefers.
+    show_info: bool,
+    registry: Registry,
+    themes: Vec<Theme>,
+    /// Themes the user imported, kept in local storage.
+    custom_themes: Vec<Theme>,
+    /// Why the last theme import failed.
+    theme_error: Option<String>,
+    /// Ids and confidence of the languages detected for the current code, most likely first.
+    detected_languages: Vec<(String, f32)>,
+    /// Outcome of the last copy to the clipboard, until the code or language changes.
+    copied: Option<bool>,
+    /// Outcome of the last link copied to the clipboard, until the code or options change.
+    link_copied: Option<bool>,
+    /// Why the shared link the app was opened with, or the last one copied, didn't work.
+    share_error: Option<String>,
+    /// Why the last export failed.
+    export_error: Option<String>,
+    textarea_ref: NodeRef,
+    webp_support: Option<bool>,
+    /// Tokenizes off the main thread.
+    worker: WorkerBridge<HighlightWorker<L>>,
+    /// Id of the latest request to the worker, responses to older ones are stale.
+    request_id: u64,
+    /// Code of the latest request, the preview shows it once its tokens arrive.
+    requested_code: AttrValue,
+    /// When the latest request was sent.
+    requested_at: f64,
+    /// The tokens the worker has cached, kept in sync by applying each chunk it sends.
+    worker_tokens: TokenChunks,
+    /// Code as of the last highlighting, which the preview shows until the next one.
+    highlighted_code: AttrValue,
+    /// Id of the language `tokens` are for.
+    highlighted_language: Option<String>,
+    /// Tokens of the chunks received so far, all of them once `stats.update.done`.
+    tokens: TokenChunks,
+    /// Whether the highlighted code parses, `None` when its language isn't checked.
+    validation: Option<Result<(), SyntaxError>>,
+    /// Highlights the code once typing pauses.
+    highlight_timeout: Option<Timeout>,
+    /// Whether the debug overlay shows, enabled by a `debug` query parameter.
+    debug: bool,
+    stats: HighlightStats,
+    /// The snippet library, once IndexedDB is open.
+    library: Option<Library>,
+    snippets: Vec<Snippet>,
+    show_library: bool,
+    show_image_options: bool,
+    snippet_query: String,
+    /// Id of the snippet the code was opened from or last saved as.
+    open_snippet: Option<String>,
+    snippet_title: String,
+    /// Comma separated tags of the snippet to save.
+    snippet_tags: String,
 }
 
-const PROGRAMMING_LANGUAGES: [ProgrammingLanguage; 12] = [
-    ProgrammingLanguage {
-        name: "C / C++",
-        css_class: "cpp",
-        image_file: "images/cpp",
-        image_file_extension: "png",
-    },
-    ProgrammingLanguage {
-        name: "C#",
-        css_class: "csharp",
-        image_file: "images/csharp",
-        image_file_extension: "png",
-    },
-    ProgrammingLanguage {
-        name: "Python",
-        css_class: "python",
-        image_file: "images/python",
-        image_file_extension: "png",
-    },
-    ProgrammingLanguage {
-        name: "CSS",
-        css_class: "css",
-        image_file: "images/css",
-        image_file_extension: "png",
-    },
-    ProgrammingLanguage {
-        name: "Delphi",
-        css_class: "delphi",
-        image_file: "images/delphi",
-        image_file_extension: "png",
-    },
-    ProgrammingLanguage {
-        name: "VisualBasic",
-        css_class: "vb",
-        image_file: "images/viauslbasic",
-        image_file_extension: "svg",
-    },
-    ProgrammingLanguage {
-        name: "Java",
-        css_class: "java",
-        image_file: "images/java",
-        image_file_extension: "png",
-    },
-    ProgrammingLanguage {
-        name: "JavaScript",
-        css_class: "js",
-        image_file: "images/javascript",
-        image_file_extension: "png",
-    },
-    ProgrammingLanguage {
-        name: "Ruby",
-        css_class: "ruby",
-        image_file: "images/ruby",
-        image_file_extension: "png",
-    },
-    ProgrammingLanguage {
-        name: "SQL",
-        css_class: "sql",
-        image_file: "images/sql",
-        image_file_extension: "png",
-    },
-    ProgrammingLanguage {
-        name: "HTML / XML",
-        css_class: "xml",
-        image_file: "images/html",
-        image_file_extension: "png",
-    },
-    ProgrammingLanguage {
-        name: "PHP",
-        css_class: "php",
-        image_file: "images/php",
-        image_file_extension: "png",
-    },
-];
-
-#[derive(Serialize, Deserialize)]
-#[serde(bound(deserialize = "'de: 'static"))]
-pub struct State {
-    pub show_info: bool,
-    pub code: String,
-    pub programming_language: Option<ProgrammingLanguage>,
+fn language_img_url(language: &Language, webp_support: bool) -> Option<String> {
+    language
+        .icon
+        .as_ref()
+        .map(|icon| build_webp_url(&icon.path, &icon.extension, webp_support))
 }
 
-#[derive(Serialize, Deserialize)]
-pub struct StoredState {
-    pub show_info: Option<bool>,
+/// An edit of the image export options.
+pub enum ImageChange {
+    Padding(String),
+    Background(String),
+    Transparent(bool),
+    Shadow(bool),
+    Window(bool),
+    Title(String),
+    Scale(String),
 }
 
 pub enum Msg {
     HideInitMessage(bool),
-    ChooseLanguage(&'static ProgrammingLanguage),
+    ChooseLanguage(String),
     InputCode,
     WebPSupport(bool),
+    Highlight,
+    Highlighted(HighlightResponse),
+    Copied(bool),
+    CopyLink,
+    LinkCopied(bool),
+    ToggleLineNumbers,
+    ChooseTheme(String),
+    ImportTheme(Event),
+    ThemeImported(Result<Theme, String>),
+    RemoveTheme,
+    ExportHtml(StyleMode),
+    ExportDocx,
+    ExportPdf,
+    ExportLatex(latex::Flavor),
+    ToggleImageOptions,
+    ChangeImage(ImageChange),
+    /// Exports an image, a PNG when set or else an SVG.
+    ExportImage(bool),
+    LibraryLoaded(Result<(Library, Vec<Snippet>), String>),
+    ToggleLibrary,
+    SearchSnippets(String),
+    SnippetTitle(String),
+    SnippetTags(String),
+    NewSnippet,
+    SaveSnippet,
+    OpenSnippet(String),
+    DuplicateSnippet(String),
+    DeleteSnippet(String),
 }
 
-impl App {
-    fn format_code(&mut self) {
-        let window = web_sys::window().expect("no global `window` exists");
-        let document = window.document().expect("should have a document on window");
-        let elements = document.query_selector("div.dp-highlighter").unwrap();
+impl<L: Languages> App<L> {
+    fn programming_language(&self) -> Option<&Language> {
+        self.state
+            .programming_language
+            .as_deref()
+            .and_then(|id| self.registry.get(id))
+    }
+
+    /// The language the preview's tokens are for, which lags behind the selected one until the
+    /// worker answers.
+    fn highlighted_language(&self) -> Option<&Language> {
+        self.highlighted_language
+            .as_deref()
+            .and_then(|id| self.registry.get(id))
+    }
+
+    fn theme(&self) -> &Theme {
+        self.themes
+            .iter()
+            .chain(&self.custom_themes)
+            .find(|theme| theme.id == self.state.theme)
+            .unwrap_or(&self.themes[0])
+    }
+
+    fn is_custom_theme(&self) -> bool {
+        self.custom_themes
+            .iter()
+            .any(|theme| theme.id == self.state.theme)
+    }
 
-        if let Some(element) = elements {
-            element.remove();
+    fn store_custom_themes(&self) {
+        if let Err(error) = LocalStorage::set(THEMES_KEY, &self.custom_themes) {
+            error!("Storing the themes failed: {:?}", error);
         }
+    }
+
+    /// Runs `change` against the library in the background, logging failures.
+    fn change_library<F>(&self, change: impl FnOnce(Library) -> F)
+    where
+        F: Future<Output = Result<(), JsValue>> + 'static,
+    {
+        let Some(library) = self.library.clone() else {
+            return;
+        };
+        let change = change(library);
+        spawn_local(async move {
+            if let Err(error) = change.await {
+                error!("Updating the snippet library failed: {:?}", error);
+            }
+        });
+    }
 
-        highlighter::highlight();
+    /// Takes over the code and options of a shared link the app was opened with.
+    fn restore_shared(&mut self) {
+        let location = web_sys::window()
+            .expect("no global `window` exists")
+            .location();
+        let fragment = location.hash().unwrap_or_default();
+        if fragment.trim_start_matches('#').is_empty() {
+            return;
+        }
+
+        match share::decode(&fragment) {
+            Ok(shared) => {
+                self.state.language_chosen = shared.programming_language.is_some();
+                self.state.code = shared.code;
+                self.state.programming_language = shared.programming_language;
+                self.state.line_numbers = shared.line_numbers;
+                if self
+                    .themes
+                    .iter()
+                    .chain(&self.custom_themes)
+                    .any(|t| t.id == shared.theme)
+                {
+                    self.state.theme = shared.theme;
+                }
+                self.show_info = false;
+                self.state.store();
+            }
+            Err(error) => {
+                error!("Opening the shared link failed: {}", error);
+                self.share_error = Some(format!("Opening the link failed, {error}"));
+            }
+        }
+
+        // the restored code is edited from here on, a reload shouldn't reset it
+        let url = format!(
+            "{}{}",
+            location.pathname().unwrap_or_default(),
+            location.search().unwrap_or_default()
+        );
+        let history = web_sys::window().and_then(|window| window.history().ok());
+        if let Some(Err(error)) =
+            history.map(|history| history.replace_state_with_url(&JsValue::NULL, "", Some(&url)))
+        {
+            error!("Removing the shared link failed: {:?}", error);
+        }
+    }
+
+    /// Asks the worker to bring the preview's tokens up to date with the code, detecting its
+    /// language first when `detect` is set.
+    fn highlight(&mut self, detect: bool) {
+        if self.state.code.trim().is_empty() {
+            self.detected_languages.clear();
+            self.state.language_chosen = false;
+            self.highlighted_code = AttrValue::from(self.state.code.clone());
+            self.highlighted_language = None;
+            self.tokens = TokenChunks::new();
+            self.validation = None;
+            self.stats = HighlightStats::default();
+            return;
+        }
+
+        self.request_id += 1;
+        self.requested_code = AttrValue::from(self.state.code.clone());
+        self.requested_at = now();
+        self.worker.send(HighlightRequest {
+            id: self.request_id,
+            code: self.state.code.clone(),
+            language: match self.state.language_chosen {
+                true => self.state.programming_language.clone(),
+                false => None,
+            },
+            options: HighlightOptions {
+                detect,
+                ..HighlightOptions::default()
+            },
+        });
+    }
+
+    /// Applies what the worker sent, returning whether the preview changed.
+    fn highlighted(&mut self, response: HighlightResponse) -> bool {
+        match response {
+            HighlightResponse::Detected {
+                id,
+                guesses,
+                elapsed,
+            } => {
+                if id != self.request_id {
+                    return false;
+                }
+                self.detected_languages =
+                    guesses.into_iter().take(DETECTED_LANGUAGES_SHOWN).collect();
+                self.stats.detect_ms = elapsed;
+
+                if !self.state.language_chosen {
+                    if let Some((id, _)) = self.detected_languages.first() {
+                        self.state.programming_language = Some(id.clone());
+                        self.state.store();
+                    }
+                }
+                true
+            }
+            HighlightResponse::Tokens {
+                id,
+                language,
+                from_offset,
+                tokens,
+                stats,
+            } => {
+                self.worker_tokens.replace_from(from_offset, tokens);
+
+                if id != self.request_id {
+                    return false;
+                }
+                // the preview shows every chunk, the lines after it plain until the next one
+                self.stats.update = stats;
+                self.highlighted_code = self.requested_code.clone();
+                self.highlighted_language = language;
+                self.tokens = self.worker_tokens.clone();
+                self.stats.tokens = self.tokens.len();
+                self.stats.tokenize_ms = now() - self.requested_at;
+                true
+            }
+            HighlightResponse::Checked { id, result } => {
+                if id != self.request_id {
+                    return false;
+                }
+                self.validation = result;
+                true
+            }
+        }
+    }
+
+    fn view_debug_overlay(&self) -> Html {
+        let stats = &self.stats;
+        html! {
+            <div class="card" style="position:fixed;bottom:16px;right:16px;z-index:1050;font-size:12px;">
+                <div class="card-body p-2">
+                    <div>{format!("Lines: {}, tokenized: {}", stats.update.lines, stats.update.tokenized)}</div>
+                    <div>{format!("Tokens: {}", stats.tokens)}</div>
+                    <div>{format!("Detection: {:.1} ms", stats.detect_ms)}</div>
+                    <div>{format!("Highlighting: {:.1} ms", stats.tokenize_ms)}</div>
+                    <div>{format!("Highlight delay: {HIGHLIGHT_DELAY_MS} ms")}</div>
+                </div>
+            </div>
+        }
+    }
+
+    fn view_detected_languages(&self, ctx: &yew::Context<Self>) -> Html {
+        let mut detected = self
+            .detected_languages
+            .iter()
+            .filter_map(|(id, confidence)| Some((self.registry.get(id)?, confidence)));
+
+        let Some((best, confidence)) = detected.next() else {
+            return html! {};
+        };
+
+        html! {
+            <small class="text-muted">
+                {format!("Detected {name} ({percent:.0}%)", name=best.name, percent=confidence * 100.0)}
+                {
+                    detected.map(|(language, confidence)| {
+                        let id = language.id.clone();
+                        html! {
+                            <>
+                                {" · "}
+                                <a href="#" onclick={ctx.link().callback(move |_| Msg::ChooseLanguage(id.clone()))}>
+                                    {format!("{name} ({percent:.0}%)", name=language.name, percent=confidence * 100.0)}
+                                </a>
+                            </>
+                        }
+                    }).collect::<Html>()
+                }
+            </small>
+        }
+    }
+
+    /// Title of images exported without one, the snippet's title or the language.
+    fn default_image_title(&self) -> String {
+        match self.snippet_title.trim() {
+            "" => self
+                .programming_language()
+                .map_or_else(String::new, |language| language.name.clone()),
+            title => title.to_string(),
+        }
+    }
+
+    fn view_image_options(&self, ctx: &yew::Context<Self>) -> Html {
+        let options = &self.state.image;
+        let change = |change: fn(String) -> ImageChange| {
+            ctx.link().callback(move |e: Event| {
+                Msg::ChangeImage(change(
+                    e.target_unchecked_into::<HtmlInputElement>().value(),
+                ))
+            })
+        };
+        let toggle = |change: fn(bool) -> ImageChange| {
+            ctx.link().callback(move |e: Event| {
+                Msg::ChangeImage(change(
+                    e.target_unchecked_into::<HtmlInputElement>().checked(),
+                ))
+            })
+        };
+        let disabled = self.programming_language().is_none() || self.state.code.trim().is_empty();
+
+        html! {
+            <div class="card" style="position:fixed;top:0;right:0;bottom:0;width:320px;z-index:1050;overflow-y:auto;border-radius:0;">
+                <div class="card-body">
+                    <div class="d-flex justify-content-between align-items-center mb-3">
+                        <h5 class="mb-0">{"Export image"}</h5>
+                        <button type="button" class="btn-close" aria-label="Close" onclick={ctx.link().callback(|_| Msg::ToggleImageOptions)}></button>
+                    </div>
+                    <label class="form-label" for="image-padding">{"Padding"}</label>
+                    <div class="input-group input-group-outline mb-2">
+                        <input
+                            type="number"
+                            class="form-control"
+                            id="image-padding"
+                            min="0"
+                            value={options.padding.to_string()}
+                            onchange={change(ImageChange::Padding)}
+                        />
+                    </div>
+                    <label class="form-label" for="image-background">{"Background"}</label>
+                    <div class="d-flex align-items-center mb-2">
+                        <input
+                            type="color"
+                            class="form-control form-control-color me-3"
+                            id="image-background"
+                            disabled={options.background.is_none()}
+                            value={options.background.unwrap_or(self.theme().background).to_hex()}
+                            onchange={change(ImageChange::Background)}
+                        />
+                        <div class="form-check form-switch mb-0">
+                            <input class="form-check-input" type="checkbox" id="image-transparent" checked={options.background.is_none()} onchange={toggle(ImageChange::Transparent)} />
+                            <label class="form-check-label" for="image-transparent">{"Transparent"}</label>
+                        </div>
+                    </div>
+                    <div class="form-check form-switch mb-2">
+                        <input class="form-check-input" type="checkbox" id="image-shadow" checked={options.shadow} onchange={toggle(ImageChange::Shadow)} />
+                        <label class="form-check-label" for="image-shadow">{"Drop shadow"}</label>
+                    </div>
+                    <div class="form-check form-switch mb-2">
+                        <input class="form-check-input" type="checkbox" id="image-window" checked={options.window} onchange={toggle(ImageChange::Window)} />
+                        <label class="form-check-label" for="image-window">{"Window frame"}</label>
+                    </div>
+                    if options.window {
+                        <div class="input-group input-group-outline mb-2">
+                            <input
+                                type="text"
+                                class="form-control"
+                                placeholder={self.default_image_title()}
+                                value={options.title.clone()}
+                                onchange={change(ImageChange::Title)}
+                            />
+                        </div>
+                    }
+                    <label class="form-label" for="image-scale">{"Scale"}</label>
+                    <div class="input-group input-group-outline mb-3">
+                        <select class="form-control" id="image-scale" onchange={ctx.link().callback(|e: Event| Msg::ChangeImage(ImageChange::Scale(e.target_unchecked_into::<HtmlSelectElement>().value())))}>
+                            {
+                                (1..=4).map(|scale| html! {
+                                    <option value={scale.to_string()} selected={options.scale == scale}>{format!("{scale}x")}</option>
+                                }).collect::<Html>()
+                            }
+                        </select>
+                    </div>
+                    <div class="d-flex">
+                        <button type="button" class="btn btn-sm bg-gradient-dark mb-0 me-2" {disabled} onclick={ctx.link().callback(|_| Msg::ExportImage(true))}>
+                            {"Download PNG"}
+                        </button>
+                        <button type="button" class="btn btn-sm btn-outline-dark mb-0" {disabled} onclick={ctx.link().callback(|_| Msg::ExportImage(false))}>
+                            {"Download SVG"}
+                        </button>
+                    </div>
+                    if let Some(error) = &self.export_error {
+                        <small class="text-danger d-block mt-2">{error}</small>
+                    }
+                </div>
+            </div>
+        }
+    }
+
+    fn view_library(&self, ctx: &yew::Context<Self>) -> Html {
+        let snippets = self
+            .snippets
+            .iter()
+            .filter(|snippet| snippet.matches(&self.snippet_query))
+            .map(|snippet| {
+                let open = snippet.id.clone();
+                let duplicate = snippet.id.clone();
+                let delete = snippet.id.clone();
+                let language = snippet
+                    .language
+                    .as_deref()
+                    .and_then(|id| self.registry.get(id))
+                    .map_or("Plain text", |language| language.name.as_str());
+                let updated = js_sys::Date::new(&JsValue::from_f64(snippet.updated))
+                    .to_locale_date_string("default", &JsValue::UNDEFINED);
+                let active = self.open_snippet.as_ref() == Some(&snippet.id);
+
+                html! {
+                    <li class={classes!("list-group-item", active.then_some("active"))}>
+                        <a href="#" class="d-block text-truncate" onclick={ctx.link().callback(move |e: MouseEvent| { e.prevent_default(); Msg::OpenSnippet(open.clone()) })}>
+                            <strong>{&snippet.title}</strong>
+                        </a>
+                        <small class="text-muted">{format!("{language} · {}", String::from(updated))}</small>
+                        <div>
+                            {
+                                snippet.tags.iter().map(|tag| html! {
+                                    <span class="badge bg-secondary me-1">{tag}</span>
+                                }).collect::<Html>()
+                            }
+                        </div>
+                        <div class="text-end">
+                            <button type="button" class="btn btn-link btn-sm mb-0 p-1" title="Duplicate" onclick={ctx.link().callback(move |_| Msg::DuplicateSnippet(duplicate.clone()))}>
+                                <i class="fas fa-clone"></i>
+                            </button>
+                            <button type="button" class="btn btn-link btn-sm text-danger mb-0 p-1" title="Delete" onclick={ctx.link().callback(move |_| Msg::DeleteSnippet(delete.clone()))}>
+                                <i class="fas fa-trash"></i>
+                            </button>
+                        </div>
+                    </li>
+                }
+            })
+            .collect::<Html>();
+
+        html! {
+            <div class="card" style="position:fixed;top:0;left:0;bottom:0;width:320px;z-index:1050;overflow-y:auto;border-radius:0;">
+                <div class="card-body">
+                    <div class="d-flex justify-content-between align-items-center mb-3">
+                        <h5 class="mb-0">{"Snippets"}</h5>
+                        <button type="button" class="btn-close" aria-label="Close" onclick={ctx.link().callback(|_| Msg::ToggleLibrary)}></button>
+                    </div>
+                    if self.library.is_none() {
+                        <p class="text-danger">{"The snippet library isn't available in this browser."}</p>
+                    }
+                    <div class="input-group input-group-outline mb-2">
+                        <input
+                            type="text"
+                            class="form-control"
+                            placeholder="Title"
+                            value={self.snippet_title.clone()}
+                            oninput={ctx.link().callback(|e: InputEvent| Msg::SnippetTitle(e.target_unchecked_into::<HtmlInputElement>().value()))}
+                        />
+                    </div>
+                    <div class="input-group input-group-outline mb-2">
+                        <input
+                            type="text"
+                            class="form-control"
+                            placeholder="Tags, comma separated"
+                            value={self.snippet_tags.clone()}
+                            oninput={ctx.link().callback(|e: InputEvent| Msg::SnippetTags(e.target_unchecked_into::<HtmlInputElement>().value()))}
+                        />
+                    </div>
+                    <div class="d-flex mb-3">
+                        <button
+                            type="button"
+                            class="btn btn-sm bg-gradient-dark mb-0 me-2"
+                            disabled={self.library.is_none() || self.state.code.trim().is_empty()}
+                            onclick={ctx.link().callback(|_| Msg::SaveSnippet)}
+                        >
+                            <i class="fas fa-save"></i>
+                            { if self.open_snippet.is_some() { "  Save" } else { "  Save as snippet" } }
+                        </button>
+                        <button type="button" class="btn btn-sm btn-outline-secondary mb-0" onclick={ctx.link().callback(|_| Msg::NewSnippet)}>
+                            <i class="fas fa-file"></i>
+                            {"  New"}
+                        </button>
+                    </div>
+                    <div class="input-group input-group-outline mb-2">
+                        <input
+                            type="search"
+                            class="form-control"
+                            placeholder="Search snippets..."
+                            value={self.snippet_query.clone()}
+                            oninput={ctx.link().callback(|e: InputEvent| Msg::SearchSnippets(e.target_unchecked_into::<HtmlInputElement>().value()))}
+                        />
+                    </div>
+                    <ul class="list-group">
+                        {snippets}
+                    </ul>
+                </div>
+            </div>
+        }
+    }
+
+    /// Whether the preview's tokens are all those of the code in `language`.
+    fn is_highlighted(&self, language: &Language) -> bool {
+        self.stats.update.done
+            && *self.highlighted_code == *self.state.code
+            && self.highlighted_language.as_deref() == Some(language.id.as_str())
+    }
+
+    /// Tokens of the code in `language`, the preview's unless they are behind the code.
+    fn tokens_of(&self, language: &Language) -> Vec<Token> {
+        match self.is_highlighted(language) {
+            true => self.tokens.to_vec(),
+            false => language.tokenize(&self.state.code),
+        }
+    }
+
+    /// Copies the code as HTML, RTF and plain text. The copy starts within the click, as the
+    /// fallback for browsers refusing RTF through the Clipboard API needs a user gesture.
+    fn copy_code(&self, ctx: &yew::Context<Self>) -> Callback<MouseEvent> {
+        let Some(language) = self.programming_language() else {
+            return Callback::noop();
+        };
+        let highlighted = self.is_highlighted(language);
+        let code = match highlighted {
+            true => self.highlighted_code.clone(),
+            false => AttrValue::from(self.state.code.clone()),
+        };
+        let tokens = highlighted.then(|| self.tokens.clone());
+        let brush = language.brush.clone();
+        let theme = self.theme().clone();
+        let link = ctx.link().clone();
+
+        Callback::from(move |_| {
+            let tokens = match &tokens {
+                Some(tokens) => tokens.to_vec(),
+                None => tokenize(&code, brush.as_ref()),
+            };
+            let html = html::render_inline(&code, &tokens, &theme);
+            let rtf = rtf::render(&code, &tokens, &theme);
+            let copied = clipboard::write(&[
+                ("text/html", html.as_str()),
+                ("text/rtf", rtf.as_str()),
+                ("text/plain", code.as_str()),
+            ]);
+            link.send_future(async move { Msg::Copied(copied.await.is_ok()) });
+        })
+    }
+
+    /// Offers the highlighted code as a file, doing nothing while no language is selected.
+    /// Returns why rendering the file failed.
+    fn export(
+        &self,
+        render: impl FnOnce(&Language, &[Token]) -> Result<ExportedFile, String>,
+    ) -> Result<(), String> {
+        let Some(language) = self.programming_language() else {
+            return Ok(());
+        };
+        let tokens = self.tokens_of(language);
+        let (extension, mime, content) = render(language, &tokens)?;
+
+        if let Err(error) = download::download(&format!("snippet.{extension}"), mime, &content) {
+            error!("Export failed: {:?}", error);
+        }
+        Ok(())
     }
 }
 
-impl Component for App {
+impl<L: Languages> Component for App<L> {
     type Message = Msg;
     type Properties = ();
 
     fn create(ctx: &yew::Context<Self>) -> Self {
-        let mut state = State {
-            show_info: true,
-            code: "".to_string(),
-            programming_language: None,
-        };
-
-        let local_storage: Result<StoredState, _> = LocalStorage::get(STATE_KEY);
+        let state = State::load();
 
-        if let Ok(stored_state) = local_storage {
-            if let Some(show_info) = stored_state.show_info {
-                state.show_info = show_info;
-            }
-        }
+        ctx.link().send_future(async {
+            let library = match Library::open().await {
+                Ok(library) => library,
+                Err(error) => return Msg::LibraryLoaded(Err(format!("{:?}", error))),
+            };
+            let snippets = library.snippets().await;
+            Msg::LibraryLoaded(
+                snippets
+                    .map(|snippets| (library, snippets))
+                    .map_err(|error| format!("{:?}", error)),
+            )
+        });
 
         ctx.link().send_future(async {
             match crate::utils::images::has_webp_support().await {
@@ -167,46 +700,72 @@ impl Component for App {
             }
         });
 
-        App {
+        let mut app = App {
+            show_info: state.show_info,
             state,
+            registry: L::registry(),
+            themes: Theme::builtin(),
+            custom_themes: LocalStorage::get(THEMES_KEY).unwrap_or_default(),
+            theme_error: None,
+            detected_languages: Vec::new(),
+            copied: None,
             textarea_ref: NodeRef::default(),
             webp_support: None,
-        }
-    }
-
-    fn rendered(&mut self, _ctx: &yew::Context<Self>, _first_render: bool) {
-        debug!("Rendering code change");
-        if !self.state.code.trim().is_empty() && self.state.programming_language.is_some() {
-            self.format_code();
-        }
+            worker: {
+                let link = ctx.link().clone();
+                HighlightWorker::<L>::spawner()
+                    .callback(move |response| link.send_message(Msg::Highlighted(response)))
+                    .spawn(WORKER_PATH)
+            },
+            request_id: 0,
+            requested_code: AttrValue::default(),
+            requested_at: 0.0,
+            worker_tokens: TokenChunks::new(),
+            highlighted_code: AttrValue::default(),
+            highlighted_language: None,
+            tokens: TokenChunks::new(),
+            validation: None,
+            highlight_timeout: None,
+            debug: web_sys::window()
+                .and_then(|window| window.location().search().ok())
+                .and_then(|search| web_sys::UrlSearchParams::new_with_str(&search).ok())
+                .is_some_and(|params| params.has("debug")),
+            stats: HighlightStats::default(),
+            library: None,
+            snippets: Vec::new(),
+            show_library: false,
+            show_image_options: false,
+            snippet_query: String::new(),
+            open_snippet: None,
+            snippet_title: String::new(),
+            snippet_tags: String::new(),
+            link_copied: None,
+            share_error: None,
+            export_error: None,
+        };
+        app.restore_shared();
+        app.highlight(true);
+        app
     }
 
-    fn update(&mut self, _ctx: &yew::Context<Self>, msg: Self::Message) -> bool {
+    fn update(&mut self, ctx: &yew::Context<Self>, msg: Self::Message) -> bool {
         match msg {
             Msg::HideInitMessage(dont_show_again) => {
-                self.state.show_info = false;
+                self.show_info = false;
+                self.state.show_info = !dont_show_again;
+                self.state.store();
 
-                let local_storage: Result<StoredState, _> = LocalStorage::get(STATE_KEY);
-
-                if let Ok(mut stored_state) = local_storage {
-                    stored_state.show_info = Some(!dont_show_again);
-
-                    LocalStorage::set(STATE_KEY, stored_state).unwrap();
-                } else {
-                    LocalStorage::set(
-                        STATE_KEY,
-                        StoredState {
-                            show_info: Some(!dont_show_again),
-                        },
-                    )
-                    .unwrap();
-                }
                 true
             }
-            Msg::ChooseLanguage(language) => {
-                debug!("Selected {}", language.name);
+            Msg::ChooseLanguage(id) => {
+                debug!("Selected {}", id);
 
-                self.state.programming_language = Some(*language);
+                self.state.programming_language = Some(id);
+                self.state.language_chosen = true;
+                self.state.store();
+                self.highlight(false);
+                self.copied = None;
+                self.link_copied = None;
                 true
             }
             Msg::InputCode => {
@@ -215,11 +774,367 @@ impl Component for App {
                     .cast::<HtmlInputElement>()
                     .unwrap()
                     .value();
+                self.copied = None;
+                self.link_copied = None;
+
+                let link = ctx.link().clone();
+                self.highlight_timeout = Some(Timeout::new(HIGHLIGHT_DELAY_MS, move || {
+                    link.send_message(Msg::Highlight)
+                }));
+                true
+            }
+            Msg::Highlight => {
+                self.highlight_timeout = None;
+                self.highlight(true);
+                self.state.store();
+
                 true
             }
+            Msg::Highlighted(response) => self.highlighted(response),
             Msg::WebPSupport(state) => {
                 self.webp_support = Some(state);
 
+                true
+            }
+            Msg::Copied(copied) => {
+                self.copied = Some(copied);
+
+                true
+            }
+            Msg::CopyLink => {
+                let fragment = match share::encode(&Shared::from(&self.state)) {
+                    Ok(fragment) => fragment,
+                    Err(error) => {
+                        self.share_error = Some(format!("Copying the link failed, {error}"));
+                        return true;
+                    }
+                };
+                let location = web_sys::window()
+                    .expect("no global `window` exists")
+                    .location();
+                let url = format!(
+                    "{}{}{}#{fragment}",
+                    location.origin().unwrap_or_default(),
+                    location.pathname().unwrap_or_default(),
+                    location.search().unwrap_or_default()
+                );
+                self.share_error = None;
+
+                let copied = clipboard::write(&[("text/plain", url.as_str())]);
+                ctx.link()
+                    .send_future(async move { Msg::LinkCopied(copied.await.is_ok()) });
+                true
+            }
+            Msg::LinkCopied(copied) => {
+                self.link_copied = Some(copied);
+
+                true
+            }
+            Msg::ToggleLineNumbers => {
+                self.state.line_numbers = !self.state.line_numbers;
+                self.link_copied = None;
+                self.state.store();
+
+                true
+            }
+            Msg::ChooseTheme(id) => {
+                debug!("Selected theme {}", id);
+                self.state.theme = id;
+                self.link_copied = None;
+                self.state.store();
+
+                true
+            }
+            Msg::ImportTheme(e) => {
+                let input = e.target_unchecked_into::<HtmlInputElement>();
+                let Some(file) = upload::picked_file(&input) else {
+                    return false;
+                };
+                // lets the same file be picked again after editing it
+                input.set_value("");
+
+                ctx.link().send_future(async move {
+                    let theme = match upload::read_text(&file).await {
+                        Ok(source) => import(&source, &file.name()).map_err(|e| e.to_string()),
+                        Err(error) => Err(format!("reading the file failed: {:?}", error)),
+                    };
+                    Msg::ThemeImported(theme)
+                });
+                false
+            }
+            Msg::ThemeImported(Ok(theme)) => {
+                debug!("Imported theme {}", theme.id);
+                self.state.theme = theme.id.clone();
+                match self.custom_themes.iter_mut().find(|t| t.id == theme.id) {
+                    Some(existing) => *existing = theme,
+                    None => self.custom_themes.push(theme),
+                }
+                self.store_custom_themes();
+                self.state.store();
+                self.theme_error = None;
+
+                true
+            }
+            Msg::ThemeImported(Err(error)) => {
+                error!("Importing the theme failed: {}", error);
+                self.theme_error = Some(error);
+
+                true
+            }
+            Msg::RemoveTheme => {
+                self.custom_themes
+                    .retain(|theme| theme.id != self.state.theme);
+                self.store_custom_themes();
+                self.state.theme = Theme::default().id;
+                self.state.store();
+
+                true
+            }
+            Msg::ExportHtml(mode) => {
+                let code = &self.state.code;
+                let line_numbers = self.state.line_numbers;
+                let theme = self.theme();
+                let exported = self.export(|language, tokens| {
+                    let options = html::Options {
+                        mode,
+                        line_numbers,
+                        title: language.name.clone(),
+                        ..html::Options::default()
+                    };
+                    let document = html::render_document(code, tokens, &options, theme);
+                    Ok(("html", "text/html", document.into_bytes()))
+                });
+                self.export_error = exported.err();
+
+                true
+            }
+            Msg::ExportDocx => {
+                let code = &self.state.code;
+                let line_numbers = self.state.line_numbers;
+                let theme = self.theme();
+                let exported = self.export(|_, tokens| {
+                    let document = docx::render(code, tokens, theme, line_numbers);
+                    Ok((
+                        "docx",
+                        "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+                        document,
+                    ))
+                });
+                self.export_error = exported.err();
+
+                true
+            }
+            Msg::ExportPdf => {
+                let code = &self.state.code;
+                let line_numbers = self.state.line_numbers;
+                let title = self.snippet_title.trim();
+                let theme = self.theme();
+                let exported = self.export(|language, tokens| {
+                    let options = pdf::Options {
+                        title: match title {
+                            "" => language.name.clone(),
+                            title => title.to_string(),
+                        },
+                        line_numbers,
+                        tab_width: language.tab_width,
+                    };
+                    let document = pdf::render(code, tokens, &options, theme);
+                    Ok(("pdf", "application/pdf", document))
+                });
+                self.export_error = exported.err();
+
+                true
+            }
+            Msg::ExportLatex(flavor) => {
+                let code = &self.state.code;
+                let line_numbers = self.state.line_numbers;
+                let theme = self.theme();
+                let exported = self.export(|language, tokens| {
+                    let options = latex::Options {
+                        flavor,
+                        line_numbers,
+                        tab_width: language.tab_width,
+                        language: language.id.clone(),
+                    };
+                    let latex = latex::render(code, tokens, &options, theme);
+                    Ok(("tex", "application/x-latex", latex.into_bytes()))
+                });
+                self.export_error = exported.err();
+
+                true
+            }
+            Msg::ToggleImageOptions => {
+                self.show_image_options = !self.show_image_options;
+
+                true
+            }
+            Msg::ChangeImage(change) => {
+                let options = &mut self.state.image;
+                match change {
+                    ImageChange::Padding(padding) => {
+                        options.padding = padding.parse().unwrap_or(options.padding)
+                    }
+                    ImageChange::Background(color) => {
+                        if let Ok(color) = Color::try_from(color) {
+                            options.background = Some(color);
+                        }
+                    }
+                    ImageChange::Transparent(transparent) => {
+                        options.background = match transparent {
+                            true => None,
+                            false => image::Options::default().background,
+                        }
+                    }
+                    ImageChange::Shadow(shadow) => options.shadow = shadow,
+                    ImageChange::Window(window) => options.window = window,
+                    ImageChange::Title(title) => options.title = title,
+                    ImageChange::Scale(scale) => {
+                        options.scale = scale.parse().unwrap_or(options.scale)
+                    }
+                }
+                self.state.store();
+
+                true
+            }
+            Msg::ExportImage(png) => {
+                let code = &self.state.code;
+                let theme = self.theme();
+                let mut options = image::Options {
+                    line_numbers: self.state.line_numbers,
+                    ..self.state.image.clone()
+                };
+                if options.title.trim().is_empty() {
+                    options.title = self.default_image_title();
+                }
+                let exported = self.export(|language, tokens| {
+                    options.tab_width = language.tab_width;
+                    match png {
+                        true => image::render_png(code, tokens, &options, theme)
+                            .map(|png| ("png", "image/png", png))
+                            .map_err(|error| format!("Exporting the PNG failed, {error}")),
+                        false => {
+                            let svg = image::render_svg(code, tokens, &options, theme);
+                            Ok(("svg", "image/svg+xml", svg.into_bytes()))
+                        }
+                    }
+                });
+                self.export_error = exported.err();
+
+                true
+            }
+            Msg::LibraryLoaded(Ok((library, snippets))) => {
+                self.library = Some(library);
+                self.snippets = snippets;
+
+                true
+            }
+            Msg::LibraryLoaded(Err(error)) => {
+                error!("Opening the snippet library failed: {}", error);
+
+                false
+            }
+            Msg::ToggleLibrary => {
+                self.show_library = !self.show_library;
+
+                true
+            }
+            Msg::SearchSnippets(query) => {
+                self.snippet_query = query;
+
+                true
+            }
+            Msg::SnippetTitle(title) => {
+                self.snippet_title = title;
+
+                false
+            }
+            Msg::SnippetTags(tags) => {
+                self.snippet_tags = tags;
+
+                false
+            }
+            Msg::NewSnippet => {
+                self.open_snippet = None;
+                self.snippet_title.clear();
+                self.snippet_tags.clear();
+                self.state.code.clear();
+                self.state.programming_language = None;
+                self.highlight(true);
+                self.state.store();
+                self.copied = None;
+                self.link_copied = None;
+
+                true
+            }
+            Msg::SaveSnippet => {
+                let existing = self
+                    .open_snippet
+                    .as_ref()
+                    .and_then(|id| self.snippets.iter().position(|s| &s.id == id));
+                let mut snippet = match existing {
+                    Some(index) => self.snippets.remove(index),
+                    None => Snippet::new(String::new(), String::new()),
+                };
+                snippet.title = match self.snippet_title.trim() {
+                    "" => "Untitled".to_string(),
+                    title => title.to_string(),
+                };
+                snippet.code = self.state.code.clone();
+                snippet.language = self.state.programming_language.clone();
+                snippet.tags = parse_tags(&self.snippet_tags);
+                snippet.line_numbers = self.state.line_numbers;
+                snippet.theme = self.state.theme.clone();
+                snippet.updated = js_sys::Date::now();
+
+                self.open_snippet = Some(snippet.id.clone());
+                self.snippets.insert(0, snippet.clone());
+                self.change_library(|library| async move { library.save(&snippet).await });
+
+                true
+            }
+            Msg::OpenSnippet(id) => {
+                let Some(snippet) = self.snippets.iter().find(|s| s.id == id) else {
+                    return false;
+                };
+                self.state.code = snippet.code.clone();
+                self.state.programming_language = snippet.language.clone();
+                self.state.language_chosen = snippet.language.is_some();
+                self.state.line_numbers = snippet.line_numbers;
+                if self
+                    .themes
+                    .iter()
+                    .chain(&self.custom_themes)
+                    .any(|t| t.id == snippet.theme)
+                {
+                    self.state.theme = snippet.theme.clone();
+                }
+                self.snippet_title = snippet.title.clone();
+                self.snippet_tags = snippet.tags.join(", ");
+                self.open_snippet = Some(id);
+                self.highlight(true);
+                self.state.store();
+                self.copied = None;
+                self.link_copied = None;
+
+                true
+            }
+            Msg::DuplicateSnippet(id) => {
+                let Some(snippet) = self.snippets.iter().find(|s| s.id == id) else {
+                    return false;
+                };
+                let duplicate = snippet.duplicate();
+                self.snippets.insert(0, duplicate.clone());
+                self.change_library(|library| async move { library.save(&duplicate).await });
+
+                true
+            }
+            Msg::DeleteSnippet(id) => {
+                self.snippets.retain(|snippet| snippet.id != id);
+                if self.open_snippet.as_ref() == Some(&id) {
+                    self.open_snippet = None;
+                }
+                self.change_library(|library| async move { library.delete(&id).await });
+
                 true
             }
         }
@@ -237,7 +1152,7 @@ impl Component for App {
                 >
                     {
                         html! {
-                            <div class="container-fluid" hidden={!self.state.show_info}>
+                            <div class="container-fluid" hidden={!self.show_info}>
                                 <div class="row">
                                     <div class="col-md-6 offset-lg-2">
                                         <div class="card" data-animation="true">
@@ -257,16 +1172,11 @@ impl Component for App {
                                                                     <div class="col-lg-12 my-auto">
                                                                         <h3>{"Do you need to highlight some code?"}</h3>
                                                                             <p class="pe-4">
-                                                                                {"This small webapp uses the"}
-                                                                                <a href="https://code.google.com/archive/p/syntaxhighlighter/">
-                                                                                    {"syntaxhighlighter"}
-                                                                                </a>
-
-                                                                                {"from Google Code Archive. Maybe you have seen it on"}
+                                                                                {"This small webapp highlights your code with its own engine, written in Rust and compiled to WebAssembly. It recognizes the language as you type, highlights in the background so long files stay responsive, and copies or exports the result with colors for Word, slides or papers. Maybe you know the look from"}
 
                                                                                 <a href="http://planetb.ca/syntax-highlight-word">{"planetb.ca"}</a>
 
-                                                                                {". Sadly the planetb one is most of the times just not available. So have a go and see what you get."}
+                                                                                {", which sadly is most of the times just not available. So have a go and see what you get."}
 
                                                                                 <br />
                                                                                 <br />
@@ -282,10 +1192,10 @@ impl Component for App {
                                                                                     {"It supports the following languages:"}
                                                                                     <div class="row">
                                                                                         {
-                                                                                            PROGRAMMING_LANGUAGES.iter().map(|language|{
+                                                                                            self.registry.languages().iter().map(|language|{
                                                                                                 html! {
                                                                                                     <div class="col-sm-6 col-md-6 col-lg-4 col-xl-3">
-                                                                                                    <li>{language.name}</li>
+                                                                                                    <li>{&language.name}</li>
                                                                                                 </div>
                                                                                                 }
                                                                                             }).collect::<Vec<_>>()
@@ -328,7 +1238,26 @@ impl Component for App {
                     }
                     {
                         html! {
-                            <div class="container-fluid" style="height: 100vh;overflow-y: scroll" hidden={self.state.show_info}>
+                            <div class="container-fluid" style="height: 100vh;overflow-y: scroll" hidden={self.show_info}>
+                                if self.debug {
+                                    {self.view_debug_overlay()}
+                                }
+                                if self.show_image_options {
+                                    {self.view_image_options(ctx)}
+                                }
+                                if self.show_library {
+                                    {self.view_library(ctx)}
+                                } else {
+                                    <button
+                                        type="button"
+                                        class="btn btn-sm bg-gradient-dark"
+                                        style="position:fixed;top:16px;left:16px;z-index:1050;"
+                                        onclick={ctx.link().callback(|_| Msg::ToggleLibrary)}
+                                    >
+                                        <i class="fas fa-book"></i>
+                                        {"  Snippets"}
+                                    </button>
+                                }
                                 <div class="row" style="min-height: 87.5%;margin-top:12.5vh;height: auto;">
                                     <div class="col-md-6">
                                         <div class="card" style="min-height: 75%;">
@@ -346,9 +1275,9 @@ impl Component for App {
                                                                                 id="navbarDropdownMenuLink2"
                                                                             >
                                                                                 {
-                                                                                    if let Some(programming_language) = self.state.programming_language {
-                                                                                        if let Some(webp_support) = self.webp_support {
-                                                                                            html!{<img src={programming_language.to_img_url(webp_support)}  height="24"/>}
+                                                                                    if let Some(programming_language) = self.programming_language() {
+                                                                                        if let Some(src) = self.webp_support.and_then(|webp_support| language_img_url(programming_language, webp_support)) {
+                                                                                            html!{<img {src}  height="24"/>}
                                                                                         } else {
                                                                                             html!{}
                                                                                         }
@@ -358,7 +1287,7 @@ impl Component for App {
                                                                                 }
 
                                                                                 {
-                                                                                    if let Some(programming_language) = self.state.programming_language {
+                                                                                    if let Some(programming_language) = self.programming_language() {
                                                                                         format!("  {name}", name=programming_language.name)
                                                                                     } else {
                                                                                         "Select a Programming language ...".to_string()
@@ -371,12 +1300,13 @@ impl Component for App {
                                                                             >
                                                                                 {
                                                                                     if let Some(webp_support) = self.webp_support {
-                                                                                        PROGRAMMING_LANGUAGES.iter().map(|language|{
+                                                                                        self.registry.languages().iter().map(|language|{
+                                                                                            let id = language.id.clone();
                                                                                             html! {
-                                                                                                <li onclick={ctx.link().callback(|_| Msg::ChooseLanguage(language))}>
+                                                                                                <li onclick={ctx.link().callback(move |_| Msg::ChooseLanguage(id.clone()))}>
                                                                                                     <a class="dropdown-item" href="#">
-                                                                                                        <img src={language.to_img_url(webp_support)} height="24" />
-                                                                                                        {"   "}{language.name}
+                                                                                                        <img src={language_img_url(language, webp_support)} height="24" />
+                                                                                                        {"   "}{&language.name}
                                                                                                     </a>
                                                                                                 </li>
                                                                                             }
@@ -390,6 +1320,7 @@ impl Component for App {
                                                                     </div>
                                                                     <div class="col-md-4 col-lg-5" style="padding-right:0;">
                                                                         <div class="text-right">
+                                                                            {self.view_detected_languages(ctx)}
                                                                         </div>
                                                                     </div>
                                                                 </div>
@@ -402,6 +1333,7 @@ impl Component for App {
                                                                                 class="form-control"
                                                                                 id="message"
                                                                                 style={ format!("min-height: calc(75vh - 204px);overflow-y: hidden;{height}", height=if let Some(element) = self.textarea_ref.cast::<web_sys::Element>(){format!("height: {px}px", px=element.scroll_height())} else {"".to_string()}) }
+                                                                                value={self.state.code.clone()}
                                                                                 oninput={ctx.link().callback(|_e| Msg::InputCode)}
                                                                                 placeholder="Just paste something and see what happens...."
                                                                             />
@@ -420,7 +1352,161 @@ impl Component for App {
                                             <div class="col-md-6">
                                                 <div class="card" style="min-height: 75%;">
                                                     <div class="card-body">
-                                                        <pre name="code" style="width:100%;height:100%" class={ if let Some(language) = self.state.programming_language { language.css_class } else {""}}>{if !self.state.code.trim().is_empty() {self.state.code.as_str()} else {"Nothing to show...yet"}}</pre>
+                                                        <div class="d-flex justify-content-between align-items-center">
+                                                            <div class="form-check form-switch mb-0">
+                                                                <input
+                                                                    class="form-check-input"
+                                                                    type="checkbox"
+                                                                    id="line-numbers"
+                                                                    checked={self.state.line_numbers}
+                                                                    onchange={ctx.link().callback(|_| Msg::ToggleLineNumbers)}
+                                                                />
+                                                                <label class="form-check-label" for="line-numbers">{"Line numbers"}</label>
+                                                            </div>
+                                                            <div class="input-group input-group-outline w-auto">
+                                                                <select
+                                                                    class="form-control form-control-sm"
+                                                                    aria-label="Theme"
+                                                                    onchange={ctx.link().callback(|e: Event| Msg::ChooseTheme(e.target_unchecked_into::<HtmlSelectElement>().value()))}
+                                                                >
+                                                                    {
+                                                                        self.themes.iter().chain(&self.custom_themes).map(|theme| {
+                                                                            html! {
+                                                                                <option value={theme.id.clone()} selected={theme.id == self.theme().id}>{&theme.name}</option>
+                                                                            }
+                                                                        }).collect::<Html>()
+                                                                    }
+                                                                </select>
+                                                                if self.is_custom_theme() {
+                                                                    <button
+                                                                        type="button"
+                                                                        class="btn btn-sm btn-outline-secondary mb-0"
+                                                                        title="Remove theme"
+                                                                        onclick={ctx.link().callback(|_| Msg::RemoveTheme)}
+                                                                    >
+                                                                        <i class="fas fa-trash"></i>
+                                                                    </button>
+                                                                }
+                                                                <label class="btn btn-sm btn-outline-secondary mb-0" title="Import a VS Code, TextMate or Sublime theme">
+                                                                    <i class="fas fa-file-import"></i>
+                                                                    <input
+                                                                        type="file"
+                                                                        accept=".json,.jsonc,.tmTheme,.sublime-color-scheme"
+                                                                        hidden=true
+                                                                        onchange={ctx.link().callback(Msg::ImportTheme)}
+                                                                    />
+                                                                </label>
+                                                            </div>
+                                                            <div>
+                                                                <button
+                                                                    type="button"
+                                                                    class="btn btn-sm bg-gradient-dark mb-0"
+                                                                    style="margin-right: 8px;"
+                                                                    disabled={self.state.code.trim().is_empty() || self.programming_language().is_none()}
+                                                                    onclick={self.copy_code(ctx)}
+                                                                >
+                                                                    <i class="fas fa-copy"></i>
+                                                                    {
+                                                                        match self.copied {
+                                                                            Some(true) => "  Copied",
+                                                                            Some(false) => "  Copy failed",
+                                                                            None => "  Copy",
+                                                                        }
+                                                                    }
+                                                                </button>
+                                                                <button
+                                                                    type="button"
+                                                                    class="btn btn-sm bg-gradient-dark mb-0"
+                                                                    style="margin-right: 8px;"
+                                                                    disabled={self.state.code.trim().is_empty()}
+                                                                    onclick={ctx.link().callback(|_| Msg::CopyLink)}
+                                                                >
+                                                                    <i class="fas fa-link"></i>
+                                                                    {
+                                                                        match self.link_copied {
+                                                                            Some(true) => "  Link copied",
+                                                                            Some(false) => "  Copy failed",
+                                                                            None => "  Copy link",
+                                                                        }
+                                                                    }
+                                                                </button>
+                                                                <div class="dropdown d-inline-block">
+                                                                    <a
+                                                                        class="btn btn-sm bg-gradient-dark dropdown-toggle mb-0"
+                                                                        data-bs-toggle="dropdown"
+                                                                        id="exportDropdown"
+                                                                    >
+                                                                        <i class="fas fa-download"></i>
+                                                                        {"  Export"}
+                                                                    </a>
+                                                                    <ul class="dropdown-menu" aria-labelledby="exportDropdown">
+                                                                        <li>
+                                                                            <a class="dropdown-item" href="#" onclick={ctx.link().callback(|e: MouseEvent| { e.prevent_default(); Msg::ExportHtml(StyleMode::Inline) })}>
+                                                                                {"HTML, inline styles"}
+                                                                            </a>
+                                                                        </li>
+                                                                        <li>
+                                                                            <a class="dropdown-item" href="#" onclick={ctx.link().callback(|e: MouseEvent| { e.prevent_default(); Msg::ExportHtml(StyleMode::Stylesheet) })}>
+                                                                                {"HTML, with stylesheet"}
+                                                                            </a>
+                                                                        </li>
+                                                                        <li>
+                                                                            <a class="dropdown-item" href="#" onclick={ctx.link().callback(|e: MouseEvent| { e.prevent_default(); Msg::ExportDocx })}>
+                                                                                {"Word document (.docx)"}
+                                                                            </a>
+                                                                        </li>
+                                                                        <li>
+                                                                            <a class="dropdown-item" href="#" onclick={ctx.link().callback(|e: MouseEvent| { e.prevent_default(); Msg::ExportPdf })}>
+                                                                                {"PDF"}
+                                                                            </a>
+                                                                        </li>
+                                                                        <li>
+                                                                            <a class="dropdown-item" href="#" onclick={ctx.link().callback(|e: MouseEvent| { e.prevent_default(); Msg::ExportLatex(latex::Flavor::Verbatim) })}>
+                                                                                {"LaTeX, Verbatim"}
+                                                                            </a>
+                                                                        </li>
+                                                                        <li>
+                                                                            <a class="dropdown-item" href="#" onclick={ctx.link().callback(|e: MouseEvent| { e.prevent_default(); Msg::ExportLatex(latex::Flavor::Listings) })}>
+                                                                                {"LaTeX, listings"}
+                                                                            </a>
+                                                                        </li>
+                                                                        <li>
+                                                                            <a class="dropdown-item" href="#" onclick={ctx.link().callback(|e: MouseEvent| { e.prevent_default(); Msg::ToggleImageOptions })}>
+                                                                                {"Image (PNG, SVG)…"}
+                                                                            </a>
+                                                                        </li>
+                                                                    </ul>
+                                                                </div>
+                                                            </div>
+                                                        </div>
+                                                        if let Some(error) = &self.theme_error {
+                                                            <small class="text-danger">{format!("Theme import failed, {error}")}</small>
+                                                        }
+                                                        if let Some(error) = &self.share_error {
+                                                            <small class="text-danger">{error}</small>
+                                                        }
+                                                        if let Some(error) = &self.export_error {
+                                                            <small class="text-danger">{error}</small>
+                                                        }
+                                                        <style>{html::stylesheet(self.theme())}</style>
+                                                        {
+                                                            if self.highlighted_code.trim().is_empty() {
+                                                                html! {
+                                                                    <pre style="width:100%;height:100%;">{"Nothing to show...yet"}</pre>
+                                                                }
+                                                            } else {
+                                                                // without a language the code shows plain, but still virtualized
+                                                                html! {
+                                                                    <Preview
+                                                                        code={self.highlighted_code.clone()}
+                                                                        tokens={self.tokens.clone()}
+                                                                        line_numbers={self.state.line_numbers}
+                                                                        tab_width={self.highlighted_language().map_or(DEFAULT_TAB_WIDTH, |language| language.tab_width)}
+                                                                        validation={self.validation.clone()}
+                                                                    />
+                                                                }
+                                                            }
+                                                        }
                                                     </div>
                                                 </div>
                                             </div>
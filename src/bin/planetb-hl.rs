@@ -0,0 +1,115 @@
+//! Highlights code from the command line, with the same languages and themes as the web app.
+
+use std::io::{self, Read, Write};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use clap::Parser;
+
+use planetb_syntax_highligher::export::Format;
+use planetb_syntax_highligher::highlighter::{detect, Language, Registry};
+use planetb_syntax_highligher::theme::{self, Theme};
+
+/// Highlights source code the way the PlanetB SyntaxHighlighter does.
+#[derive(Debug, Parser)]
+#[command(name = "planetb-hl", version, about)]
+struct Args {
+    /// File to highlight, stdin when missing or `-`.
+    file: Option<PathBuf>,
+    /// Language id or alias, detected from the file extension or the code when missing.
+    #[arg(short, long)]
+    lang: Option<String>,
+    /// Id of a built-in theme, or a VS Code, TextMate or Sublime theme file.
+    #[arg(short, long, default_value = "light")]
+    theme: String,
+    /// One of html, rtf, ansi, json, docx, pdf or latex.
+    #[arg(short, long, default_value_t = Format::Html)]
+    format: Format,
+    /// Shows line numbers, in every format but RTF and JSON.
+    #[arg(short = 'n', long)]
+    line_numbers: bool,
+}
+
+fn read_code(file: Option<&PathBuf>) -> Result<String, String> {
+    match file.filter(|file| file.as_os_str() != "-") {
+        Some(file) => std::fs::read_to_string(file)
+            .map_err(|error| format!("reading {} failed: {error}", file.display())),
+        None => {
+            let mut code = String::new();
+            io::stdin()
+                .read_to_string(&mut code)
+                .map_err(|error| format!("reading stdin failed: {error}"))?;
+            Ok(code)
+        }
+    }
+}
+
+fn language<'a>(registry: &'a Registry, args: &Args, code: &str) -> Result<&'a Language, String> {
+    if let Some(lang) = &args.lang {
+        return registry.find(lang).ok_or_else(|| {
+            let ids = registry
+                .languages()
+                .iter()
+                .map(|language| language.id.as_str())
+                .collect::<Vec<_>>();
+            format!("unknown language `{lang}`, pick one of {}", ids.join(", "))
+        });
+    }
+
+    let by_extension = args
+        .file
+        .as_ref()
+        .and_then(|file| file.extension())
+        .and_then(|extension| registry.find_by_extension(&extension.to_string_lossy()));
+    by_extension
+        .or_else(|| detect(registry, code).first().map(|guess| guess.language))
+        .ok_or_else(|| "couldn't detect the language, pass it with --lang".to_string())
+}
+
+fn theme(name: &str) -> Result<Theme, String> {
+    if let Some(theme) = Theme::find(name) {
+        return Ok(theme);
+    }
+
+    let path = PathBuf::from(name);
+    if !path.is_file() {
+        let ids = Theme::builtin()
+            .into_iter()
+            .map(|theme| theme.id)
+            .collect::<Vec<_>>();
+        return Err(format!(
+            "unknown theme `{name}`, pick one of {} or pass a theme file",
+            ids.join(", ")
+        ));
+    }
+    let source = std::fs::read_to_string(&path)
+        .map_err(|error| format!("reading {name} failed: {error}"))?;
+    let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+    theme::import(&source, &file_name).map_err(|error| format!("importing {name} failed: {error}"))
+}
+
+fn run(args: Args) -> Result<(), String> {
+    let code = read_code(args.file.as_ref())?;
+    let registry = Registry::builtin();
+    let language = language(&registry, &args, &code)?;
+    let theme = theme(&args.theme)?;
+    let tokens = language.tokenize(&code);
+
+    let output = args
+        .format
+        .render(&code, &tokens, language, &theme, args.line_numbers);
+
+    io::stdout()
+        .write_all(&output)
+        .map_err(|error| format!("writing the output failed: {error}"))
+}
+
+fn main() -> ExitCode {
+    match run(Args::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("planetb-hl: {error}");
+            ExitCode::FAILURE
+        }
+    }
+}
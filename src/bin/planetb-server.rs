@@ -0,0 +1,256 @@
+//! Serves the web app and highlights code over HTTP, for tools on the network without a browser.
+
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use axum::extract::rejection::JsonRejection;
+use axum::extract::{DefaultBodyLimit, State};
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use clap::Parser;
+use serde_derive::Deserialize;
+use serde_json::json;
+use tower_http::services::{ServeDir, ServeFile};
+
+use planetb_syntax_highligher::export::Format;
+use planetb_syntax_highligher::highlighter::{detect, Registry};
+use planetb_syntax_highligher::theme::Theme;
+
+/// Serves the PlanetB SyntaxHighlighter and its highlighting API.
+#[derive(Debug, Parser)]
+#[command(name = "planetb-server", version, about)]
+struct Args {
+    /// Address to listen on.
+    #[arg(long, env = "PLANETB_ADDRESS", default_value = "0.0.0.0:8080")]
+    address: SocketAddr,
+    /// Directory holding the frontend as `trunk build` leaves it.
+    #[arg(long, env = "PLANETB_DIST", default_value = "dist")]
+    dist: PathBuf,
+    /// Largest request body accepted, in bytes.
+    #[arg(long, env = "PLANETB_MAX_REQUEST_BYTES", default_value_t = 1024 * 1024)]
+    max_request_bytes: usize,
+}
+
+/// Body of `POST /api/highlight`.
+#[derive(Debug, Deserialize)]
+struct HighlightRequest {
+    code: String,
+    /// Language id or alias, detected from the code when missing.
+    language: Option<String>,
+    /// Id of a built-in theme, the default one when missing.
+    theme: Option<String>,
+    /// `html`, `rtf`, `ansi`, `json`, `docx`, `pdf` or `latex`, HTML when missing.
+    format: Option<String>,
+    #[serde(default)]
+    line_numbers: bool,
+}
+
+fn error(status: StatusCode, message: impl Into<String>) -> Response {
+    (status, Json(json!({ "error": message.into() }))).into_response()
+}
+
+async fn health() -> Json<serde_json::Value> {
+    Json(json!({
+        "status": "ok",
+        "version": env!("CARGO_PKG_VERSION"),
+    }))
+}
+
+/// Detects the language if need be, then tokenizes and renders the code, all of which takes a
+/// while on large code.
+fn render(
+    registry: &Registry,
+    request: HighlightRequest,
+    theme: Theme,
+    format: Format,
+) -> Response {
+    let language = match &request.language {
+        Some(alias) => registry.find(alias),
+        None => detect(registry, &request.code)
+            .first()
+            .map(|guess| guess.language),
+    };
+    let Some(language) = language else {
+        return error(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            match &request.language {
+                Some(alias) => format!("unknown language `{alias}`"),
+                None => "couldn't detect the language, pass it as `language`".to_string(),
+            },
+        );
+    };
+
+    let tokens = language.tokenize(&request.code);
+    let output = format.render(
+        &request.code,
+        &tokens,
+        language,
+        &theme,
+        request.line_numbers,
+    );
+    ([(header::CONTENT_TYPE, format.mime_type())], output).into_response()
+}
+
+async fn highlight(
+    State(registry): State<Arc<Registry>>,
+    request: Result<Json<HighlightRequest>, JsonRejection>,
+) -> Response {
+    let request = match request {
+        Ok(Json(request)) => request,
+        Err(rejection) => return error(rejection.status(), rejection.body_text()),
+    };
+
+    let theme = match &request.theme {
+        Some(id) => match Theme::find(id) {
+            Some(theme) => theme,
+            None => {
+                return error(
+                    StatusCode::UNPROCESSABLE_ENTITY,
+                    format!("unknown theme `{id}`"),
+                )
+            }
+        },
+        None => Theme::default(),
+    };
+    let format = match request.format.as_deref().map(str::parse::<Format>) {
+        Some(Ok(format)) => format,
+        Some(Err(message)) => return error(StatusCode::UNPROCESSABLE_ENTITY, message),
+        None => Format::Html,
+    };
+
+    // off the async workers, so other connections don't wait for it
+    tokio::task::spawn_blocking(move || render(&registry, request, theme, format))
+        .await
+        .unwrap_or_else(|failure| {
+            error(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("highlighting failed: {failure}"),
+            )
+        })
+}
+
+/// The API routes, falling back to the frontend in `dist`.
+fn router(registry: Registry, dist: &Path, max_request_bytes: usize) -> Router {
+    let frontend = ServeDir::new(dist).fallback(ServeFile::new(dist.join("index.html")));
+    Router::new()
+        .route("/api/health", get(health))
+        .route("/api/highlight", post(highlight))
+        .fallback_service(frontend)
+        .layer(DefaultBodyLimit::max(max_request_bytes))
+        .with_state(Arc::new(registry))
+}
+
+async fn shutdown() {
+    if let Err(error) = tokio::signal::ctrl_c().await {
+        eprintln!("planetb-server: listening for ctrl-c failed: {error}");
+    }
+}
+
+#[tokio::main]
+async fn main() -> std::io::Result<()> {
+    let args = Args::parse();
+
+    let app = router(Registry::builtin(), &args.dist, args.max_request_bytes);
+
+    let listener = tokio::net::TcpListener::bind(args.address).await?;
+    eprintln!("planetb-server: listening on http://{}", args.address);
+    axum::serve(listener, app)
+        .with_graceful_shutdown(shutdown())
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::body::{to_bytes, Body};
+    use axum::http::Request;
+    use tower::ServiceExt;
+
+    use super::*;
+
+    const MAX_REQUEST_BYTES: usize = 1024;
+
+    async fn send(request: Request<Body>) -> (StatusCode, String, String) {
+        let app = router(Registry::builtin(), Path::new("dist"), MAX_REQUEST_BYTES);
+        let response = app.oneshot(request).await.unwrap();
+        let status = response.status();
+        let content_type = response
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .map_or("", |value| value.to_str().unwrap())
+            .to_string();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (
+            status,
+            content_type,
+            String::from_utf8_lossy(&body).into_owned(),
+        )
+    }
+
+    async fn post(body: serde_json::Value) -> (StatusCode, String, String) {
+        let request = Request::post("/api/highlight")
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap();
+        send(request).await
+    }
+
+    #[tokio::test]
+    async fn health_reports_the_version() {
+        let (status, _, body) =
+            send(Request::get("/api/health").body(Body::empty()).unwrap()).await;
+        assert_eq!(status, StatusCode::OK);
+        let body = serde_json::from_str::<serde_json::Value>(&body).unwrap();
+        assert_eq!(body["status"], "ok");
+        assert_eq!(body["version"], env!("CARGO_PKG_VERSION"));
+    }
+
+    #[tokio::test]
+    async fn highlights_as_html_by_default() {
+        let (status, content_type, body) =
+            post(json!({ "code": "fn main() {}", "language": "rs" })).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(content_type.starts_with("text/html"), "{content_type}");
+        assert!(body.contains("<span class=\"keyword\">fn</span>"), "{body}");
+    }
+
+    #[tokio::test]
+    async fn detects_the_language_when_missing() {
+        let (status, _, body) = post(json!({
+            "code": "#!/usr/bin/env python3\nprint('hi')",
+            "format": "json",
+        }))
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        let body = serde_json::from_str::<serde_json::Value>(&body).unwrap();
+        assert_eq!(body["language"], "python");
+    }
+
+    #[tokio::test]
+    async fn unknown_languages_and_formats_are_unprocessable() {
+        let (status, _, body) = post(json!({ "code": "x", "language": "cobol" })).await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert!(body.contains("unknown language `cobol`"), "{body}");
+
+        let (status, _, body) =
+            post(json!({ "code": "x", "language": "rust", "format": "gif" })).await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert!(body.contains("gif"), "{body}");
+
+        let (status, _, _) =
+            post(json!({ "code": "x", "language": "rust", "theme": "neon" })).await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn bodies_over_the_limit_are_rejected() {
+        let code = "x".repeat(MAX_REQUEST_BYTES);
+        let (status, _, _) = post(json!({ "code": code, "language": "rust" })).await;
+        assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+
+        let (status, _, _) = post(json!({ "language": "rust" })).await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    }
+}
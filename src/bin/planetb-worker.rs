@@ -0,0 +1,9 @@
+//! The Web Worker the app highlights in, see [`planetb_syntax_highligher::worker`].
+
+use gloo_worker::Registrable;
+
+use planetb_syntax_highligher::worker::{BuiltinLanguages, HighlightWorker};
+
+fn main() {
+    HighlightWorker::<BuiltinLanguages>::registrar().register();
+}
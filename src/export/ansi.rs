@@ -0,0 +1,103 @@
+use crate::highlighter::{lines, Token};
+use crate::theme::{Color, Theme};
+
+const RESET: &str = "\x1b[0m";
+
+/// SGR parameters selecting `color` as the 24 bit foreground color.
+fn foreground(color: Color) -> String {
+    format!("38;2;{};{};{}", color.r, color.g, color.b)
+}
+
+/// Renders the code for terminals supporting 24 bit colors. Plain text keeps the terminal's own
+/// colors, so the output stays readable on any background.
+pub fn render(code: &str, tokens: &[Token], theme: &Theme, line_numbers: bool) -> String {
+    let mut lines = lines(code, tokens);
+    // the line break ending the last line is written anyway
+    if code.ends_with('\n') {
+        lines.pop();
+    }
+    let width = lines.len().to_string().len();
+    let mut ansi = String::new();
+
+    for (index, line) in lines.into_iter().enumerate() {
+        if index > 0 {
+            ansi.push('\n');
+        }
+        if line_numbers {
+            ansi.push_str(&format!(
+                "\x1b[{}m{:>width$} {RESET}",
+                foreground(theme.gutter),
+                index + 1
+            ));
+        }
+        for (kind, text) in line {
+            let style = kind.map(|kind| theme.style(kind)).unwrap_or_default();
+            let mut parameters = Vec::new();
+            if let Some(color) = style.color {
+                parameters.push(foreground(color));
+            }
+            if style.bold {
+                parameters.push("1".to_string());
+            }
+            if style.italic {
+                parameters.push("3".to_string());
+            }
+
+            if parameters.is_empty() {
+                ansi.push_str(text);
+            } else {
+                ansi.push_str(&format!("\x1b[{}m{text}{RESET}", parameters.join(";")));
+            }
+        }
+    }
+
+    ansi.push('\n');
+    ansi
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::highlighter::TokenKind;
+    use crate::theme::Style;
+
+    #[test]
+    fn tokens_get_their_colors_and_plain_text_none() {
+        let style = Style {
+            color: Some(Color::rgb(10, 20, 30)),
+            bold: true,
+            italic: true,
+            ..Style::default()
+        };
+        let mut theme = Theme::default();
+        theme.styles.insert(TokenKind::Keyword, style);
+
+        let ansi = render(
+            "fn main",
+            &[Token::new(TokenKind::Keyword, 0..2)],
+            &theme,
+            false,
+        );
+        assert_eq!(ansi, "\x1b[38;2;10;20;30;1;3mfn\x1b[0m main\n");
+    }
+
+    #[test]
+    fn line_numbers_are_right_aligned() {
+        let theme = Theme::default();
+        let gutter = foreground(theme.gutter);
+        let code = ["x"; 10].join("\n");
+        let ansi = render(&code, &[], &theme, true);
+        let lines = ansi.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 10);
+        assert_eq!(lines[0], format!("\x1b[{gutter}m 1 {RESET}x"));
+        assert_eq!(lines[9], format!("\x1b[{gutter}m10 {RESET}x"));
+    }
+
+    #[test]
+    fn output_ends_with_one_line_break() {
+        let theme = Theme::default();
+        assert_eq!(render("a\nb", &[], &theme, false), "a\nb\n");
+        assert_eq!(render("a\nb\n", &[], &theme, false), "a\nb\n");
+        assert_eq!(render("a\n\n", &[], &theme, false), "a\n\n");
+    }
+}
@@ -0,0 +1,259 @@
+//! Word documents, written as Office Open XML without any help from the browser.
+//!
+//! The document holds nothing but the code, one paragraph per line. With line numbers the lines
+//! become the rows of a two column table instead, so a number stays next to its line even when
+//! Word wraps the line.
+
+use std::io::{Cursor, Write};
+
+use zip::write::SimpleFileOptions;
+use zip::ZipWriter;
+
+use crate::highlighter::{lines, Token};
+use crate::theme::{Color, Style, Theme, FONT_SIZE_PT};
+
+/// Font of the runs, the first of [`crate::theme::FONT_FAMILY`].
+const FONT: &str = "Consolas";
+/// Width of the text area on a letter page with one inch margins, in twentieths of a point.
+const TEXT_WIDTH: usize = 9360;
+/// Approximate width of a digit at [`FONT_SIZE_PT`], in twentieths of a point.
+const DIGIT_WIDTH: usize = 110;
+
+const CONTENT_TYPES: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types"><Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/><Default Extension="xml" ContentType="application/xml"/><Override PartName="/word/document.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.document.main+xml"/></Types>"#;
+
+const RELATIONSHIPS: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="word/document.xml"/></Relationships>"#;
+
+/// Letter paper with one inch margins.
+const SECTION: &str = r#"<w:sectPr><w:pgSz w:w="12240" w:h="15840"/><w:pgMar w:top="1440" w:right="1440" w:bottom="1440" w:left="1440" w:header="720" w:footer="720" w:gutter="0"/></w:sectPr>"#;
+
+/// Lines follow each other without any spacing, like in the preview.
+const SPACING: &str = r#"<w:spacing w:before="0" w:after="0" w:line="240" w:lineRule="auto"/>"#;
+
+/// Line breaks other than `\n`, which [`lines`] leaves within a line.
+const BREAKS: [char; 5] = ['\r', '\u{b}', '\u{c}', '\u{2028}', '\u{2029}'];
+
+/// A color as WordprocessingML wants it, hex digits without the `#`.
+fn hex(color: Color) -> String {
+    format!("{:02X}{:02X}{:02X}", color.r, color.g, color.b)
+}
+
+fn shading(color: Color) -> String {
+    format!(
+        r#"<w:shd w:val="clear" w:color="auto" w:fill="{}"/>"#,
+        hex(color)
+    )
+}
+
+/// Escapes `text` for XML, dropping the control characters XML can't hold.
+fn escape(text: &str, xml: &mut String) {
+    for c in text.chars() {
+        match c {
+            '&' => xml.push_str("&amp;"),
+            '<' => xml.push_str("&lt;"),
+            '>' => xml.push_str("&gt;"),
+            '"' => xml.push_str("&quot;"),
+            c if c < ' ' => {}
+            c => xml.push(c),
+        }
+    }
+}
+
+fn text_element(text: &str, xml: &mut String) {
+    if !text.is_empty() {
+        xml.push_str(r#"<w:t xml:space="preserve">"#);
+        escape(text, xml);
+        xml.push_str("</w:t>");
+    }
+}
+
+/// Appends a run of `text` in `style`, or in `color` where the style has none. Tabs become
+/// `<w:tab/>` elements, the breaks left within a line, like a lone `\r`, `<w:br/>` elements.
+fn run(text: &str, style: Style, color: Color, xml: &mut String) {
+    xml.push_str(&format!(
+        r#"<w:r><w:rPr><w:rFonts w:ascii="{FONT}" w:hAnsi="{FONT}" w:cs="{FONT}"/>"#
+    ));
+    if style.bold {
+        xml.push_str("<w:b/>");
+    }
+    if style.italic {
+        xml.push_str("<w:i/>");
+    }
+    xml.push_str(&format!(
+        r#"<w:color w:val="{}"/>"#,
+        hex(style.color.unwrap_or(color))
+    ));
+    xml.push_str(&format!(
+        r#"<w:sz w:val="{0}"/><w:szCs w:val="{0}"/>"#,
+        (FONT_SIZE_PT * 2.0) as u32
+    ));
+    // the schema wants the properties in order, shading after the size
+    if let Some(background) = style.background {
+        xml.push_str(&shading(background));
+    }
+    xml.push_str("</w:rPr>");
+
+    let mut start = 0;
+    for (index, c) in text
+        .char_indices()
+        .filter(|(_, c)| *c == '\t' || BREAKS.contains(c))
+    {
+        text_element(&text[start..index], xml);
+        xml.push_str(if c == '\t' { "<w:tab/>" } else { "<w:br/>" });
+        start = index + c.len_utf8();
+    }
+    text_element(&text[start..], xml);
+    xml.push_str("</w:r>");
+}
+
+/// The `word/document.xml` part.
+fn document(code: &str, tokens: &[Token], theme: &Theme, line_numbers: bool) -> String {
+    let lines = lines(code, tokens);
+    let mut body = String::new();
+
+    let digits = lines.len().to_string().len();
+    let gutter_width = 200 + digits * DIGIT_WIDTH;
+    let code_width = TEXT_WIDTH - gutter_width;
+    if line_numbers {
+        body.push_str(&format!(
+            r#"<w:tbl><w:tblPr><w:tblW w:w="{TEXT_WIDTH}" w:type="dxa"/><w:tblLayout w:type="fixed"/><w:tblCellMar><w:left w:w="60" w:type="dxa"/><w:right w:w="60" w:type="dxa"/></w:tblCellMar></w:tblPr><w:tblGrid><w:gridCol w:w="{gutter_width}"/><w:gridCol w:w="{code_width}"/></w:tblGrid>"#
+        ));
+    }
+
+    for (index, line) in lines.into_iter().enumerate() {
+        if line_numbers {
+            body.push_str(&format!(
+                r#"<w:tr><w:trPr><w:cantSplit/></w:trPr><w:tc><w:tcPr><w:tcW w:w="{gutter_width}" w:type="dxa"/>{}</w:tcPr><w:p><w:pPr>{SPACING}<w:jc w:val="right"/></w:pPr>"#,
+                shading(theme.gutter_background)
+            ));
+            let number = (index + 1).to_string();
+            run(&number, Style::default(), theme.gutter, &mut body);
+            body.push_str(&format!(
+                r#"</w:p></w:tc><w:tc><w:tcPr><w:tcW w:w="{code_width}" w:type="dxa"/><w:tcBorders><w:left w:val="single" w:sz="18" w:space="0" w:color="{}"/></w:tcBorders>{}</w:tcPr><w:p><w:pPr>{SPACING}</w:pPr>"#,
+                hex(theme.border),
+                shading(theme.background)
+            ));
+        } else {
+            body.push_str(&format!(
+                "<w:p><w:pPr>{}{SPACING}</w:pPr>",
+                shading(theme.background)
+            ));
+        }
+
+        for (kind, text) in line {
+            let style = kind.map(|kind| theme.style(kind)).unwrap_or_default();
+            run(text, style, theme.text, &mut body);
+        }
+
+        body.push_str("</w:p>");
+        if line_numbers {
+            body.push_str("</w:tc></w:tr>");
+        }
+    }
+
+    if line_numbers {
+        // Word insists on a paragraph after a table
+        body.push_str("</w:tbl><w:p/>");
+    }
+
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:body>{body}{SECTION}</w:body></w:document>"#
+    )
+}
+
+/// Renders a `.docx` document in the monospace font and colors of the preview.
+pub fn render(code: &str, tokens: &[Token], theme: &Theme, line_numbers: bool) -> Vec<u8> {
+    let parts = [
+        ("[Content_Types].xml", CONTENT_TYPES.to_string()),
+        ("_rels/.rels", RELATIONSHIPS.to_string()),
+        (
+            "word/document.xml",
+            document(code, tokens, theme, line_numbers),
+        ),
+    ];
+
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    for (name, content) in parts {
+        zip.start_file(name, SimpleFileOptions::default())
+            .and_then(|()| Ok(zip.write_all(content.as_bytes())?))
+            .expect("zipping into memory doesn't fail");
+    }
+    zip.finish()
+        .expect("zipping into memory doesn't fail")
+        .into_inner()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use zip::ZipArchive;
+
+    use super::*;
+    use crate::highlighter::TokenKind;
+
+    /// The parts of a rendered document by name.
+    fn unzip(docx: Vec<u8>) -> Vec<(String, String)> {
+        let mut archive = ZipArchive::new(Cursor::new(docx)).unwrap();
+        (0..archive.len())
+            .map(|index| {
+                let mut file = archive.by_index(index).unwrap();
+                let mut content = String::new();
+                file.read_to_string(&mut content).unwrap();
+                (file.name().to_string(), content)
+            })
+            .collect()
+    }
+
+    fn document_xml(code: &str, tokens: &[Token], line_numbers: bool) -> String {
+        let parts = unzip(render(code, tokens, &Theme::default(), line_numbers));
+        let names = parts
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            ["[Content_Types].xml", "_rels/.rels", "word/document.xml"]
+        );
+        assert!(parts[0].1.contains(r#"PartName="/word/document.xml""#));
+        parts.into_iter().next_back().unwrap().1
+    }
+
+    #[test]
+    fn code_is_escaped_for_xml() {
+        let code = "if (a < b && c > \"d\") {}";
+        let xml = document_xml(code, &[Token::new(TokenKind::Keyword, 0..2)], false);
+        assert!(xml.contains(
+            r#"<w:t xml:space="preserve"> (a &lt; b &amp;&amp; c &gt; &quot;d&quot;) {}</w:t>"#
+        ));
+        assert!(xml.contains("<w:b/>"));
+        assert!(!xml.contains("\"d\""));
+    }
+
+    #[test]
+    fn tabs_and_breaks_become_elements() {
+        let xml = document_xml("\ta\tb\nc\rd\u{7}e", &[], false);
+        assert!(xml.contains(
+            r#"<w:tab/><w:t xml:space="preserve">a</w:t><w:tab/><w:t xml:space="preserve">b</w:t>"#
+        ));
+        // a paragraph per line, a lone `\r` breaking the line within it
+        assert_eq!(xml.matches("<w:p>").count(), 2);
+        assert!(xml.contains(
+            r#"<w:t xml:space="preserve">c</w:t><w:br/><w:t xml:space="preserve">de</w:t>"#
+        ));
+    }
+
+    #[test]
+    fn line_numbers_become_a_table() {
+        let code = (1..=12)
+            .map(|n| n.to_string())
+            .collect::<Vec<_>>()
+            .join("\n");
+        let xml = document_xml(&code, &[], true);
+        assert_eq!(xml.matches("<w:tr>").count(), 12);
+        assert!(xml.contains(r#"<w:gridCol w:w="420"/>"#));
+        assert!(xml.contains("</w:tbl><w:p/><w:sectPr>"));
+    }
+}
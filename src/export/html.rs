@@ -0,0 +1,346 @@
+use crate::highlighter::{lines, Token, TokenKind};
+use crate::theme::{Theme, FONT_FAMILY, FONT_SIZE_PT};
+
+/// Escapes the characters HTML treats specially.
+pub fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Renders a `<pre>` fragment styled through `style` attributes only, the form word processors
+/// keep the colors of when pasting.
+pub fn render_inline(code: &str, tokens: &[Token], theme: &Theme) -> String {
+    let mut html = format!(
+        "<pre style=\"font-family:{font};font-size:{size}pt;color:{color};background-color:{background};padding:4px 8px;\">",
+        font = FONT_FAMILY,
+        size = FONT_SIZE_PT,
+        color = theme.text.to_hex(),
+        background = theme.background.to_hex(),
+    );
+
+    for (index, line) in lines(code, tokens).into_iter().enumerate() {
+        if index > 0 {
+            html.push('\n');
+        }
+        for (kind, text) in line {
+            match kind {
+                Some(kind) => html.push_str(&format!(
+                    "<span style=\"{style}\">{text}</span>",
+                    style = theme.style(kind).to_css(),
+                    text = escape(text)
+                )),
+                None => html.push_str(&escape(text)),
+            }
+        }
+    }
+
+    html.push_str("</pre>");
+    html
+}
+
+/// How a standalone document carries its colors.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StyleMode {
+    /// Every element gets a `style` attribute.
+    Inline,
+    /// Elements get the shCore CSS classes, styled by an embedded [`stylesheet`].
+    Stylesheet,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Options {
+    pub mode: StyleMode,
+    /// Shows the gutter with line numbers, shCore's `nogutter` turned around.
+    pub line_numbers: bool,
+    /// Number of the first line, shCore's `firstline[value]`.
+    pub first_line: usize,
+    pub title: String,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            mode: StyleMode::Stylesheet,
+            line_numbers: true,
+            first_line: 1,
+            title: "Code".to_string(),
+        }
+    }
+}
+
+// layout of the `.dp-highlighter` markup, taken from `SyntaxHighlighter.css`
+const CONTAINER_CSS: &str = "font-size:12px;width:99%;overflow:auto;margin:18px 0;padding-top:1px;";
+const LIST_CSS: &str = "list-style:decimal;margin:0 0 1px 45px;padding:0;";
+const LIST_NOGUTTER_CSS: &str = "list-style:none;margin-left:0;";
+const LINE_CSS: &str = "list-style:decimal-leading-zero;list-style-position:outside;padding:0 3px 0 10px;margin:0;line-height:14px;white-space:pre;";
+const LINE_NOGUTTER_CSS: &str = "list-style:none;border:0;";
+
+fn container_css(theme: &Theme) -> String {
+    format!(
+        "font-family:{FONT_FAMILY};{CONTAINER_CSS}background-color:{};",
+        theme.gutter_background.to_hex()
+    )
+}
+
+fn list_css(theme: &Theme) -> String {
+    format!(
+        "{LIST_CSS}background-color:{};color:{};",
+        theme.alt_background.to_hex(),
+        theme.gutter.to_hex()
+    )
+}
+
+fn line_css(theme: &Theme, alt: bool) -> String {
+    let background = if alt {
+        theme.alt_background
+    } else {
+        theme.background
+    };
+    format!(
+        "{LINE_CSS}border-left:3px solid {};color:{};background-color:{};",
+        theme.border.to_hex(),
+        theme.gutter.to_hex(),
+        background.to_hex()
+    )
+}
+
+fn code_css(theme: &Theme) -> String {
+    format!("color:{};", theme.text.to_hex())
+}
+
+/// The rules [`StyleMode::Stylesheet`] documents embed, also styling the preview.
+pub fn stylesheet(theme: &Theme) -> String {
+    let mut css = format!(
+        ".dp-highlighter {{ {container} }}\n\
+         .dp-highlighter ol {{ {list} }}\n\
+         .dp-highlighter ol li {{ {line} }}\n\
+         .dp-highlighter ol li.alt {{ background-color:{alt_background}; }}\n\
+         .dp-highlighter ol li span {{ {code} }}\n\
+         .dp-highlighter.nogutter ol {{ {LIST_NOGUTTER_CSS} }}\n\
+         .dp-highlighter.nogutter ol li {{ {LINE_NOGUTTER_CSS} }}\n",
+        container = container_css(theme),
+        list = list_css(theme),
+        line = line_css(theme, false),
+        alt_background = theme.alt_background.to_hex(),
+        code = code_css(theme),
+    );
+    for kind in TokenKind::ALL {
+        css.push_str(&format!(
+            ".dp-highlighter .{class} {{ {style} }}\n",
+            class = kind.css_class(),
+            style = theme.style(kind).to_css()
+        ));
+    }
+    css
+}
+
+/// Renders the `.dp-highlighter` markup shCore produced, one `<li>` per line.
+pub fn render_fragment(code: &str, tokens: &[Token], options: &Options, theme: &Theme) -> String {
+    let inline = options.mode == StyleMode::Inline;
+    let mut html = String::new();
+
+    if inline {
+        let mut list_css = list_css(theme);
+        if !options.line_numbers {
+            list_css.push_str(LIST_NOGUTTER_CSS);
+        }
+        html.push_str(&format!(
+            "<div style=\"{}\"><ol start=\"{}\" style=\"{}\">",
+            container_css(theme),
+            options.first_line,
+            list_css
+        ));
+    } else {
+        html.push_str(&format!(
+            "<div class=\"dp-highlighter{}\"><ol start=\"{}\">",
+            if options.line_numbers {
+                ""
+            } else {
+                " nogutter"
+            },
+            options.first_line
+        ));
+    }
+
+    for (index, line) in lines(code, tokens).into_iter().enumerate() {
+        // shCore starts with an `alt` line
+        let alt = index % 2 == 0;
+
+        if inline {
+            let mut css = line_css(theme, alt);
+            if !options.line_numbers {
+                css.push_str(LINE_NOGUTTER_CSS);
+            }
+            html.push_str(&format!(
+                "<li style=\"{css}\"><span style=\"{}\">",
+                code_css(theme)
+            ));
+        } else if alt {
+            html.push_str("<li class=\"alt\"><span>");
+        } else {
+            html.push_str("<li><span>");
+        }
+
+        if line.is_empty() {
+            html.push_str("&nbsp;");
+        }
+        for (kind, text) in line {
+            match (kind, inline) {
+                (Some(kind), true) => html.push_str(&format!(
+                    "<span style=\"{}\">{}</span>",
+                    theme.style(kind).to_css(),
+                    escape(text)
+                )),
+                (Some(kind), false) => html.push_str(&format!(
+                    "<span class=\"{}\">{}</span>",
+                    kind.css_class(),
+                    escape(text)
+                )),
+                (None, _) => html.push_str(&escape(text)),
+            }
+        }
+
+        html.push_str("</span></li>");
+    }
+
+    html.push_str("</ol></div>");
+    html
+}
+
+/// Renders a self-contained HTML document around [`render_fragment`].
+pub fn render_document(code: &str, tokens: &[Token], options: &Options, theme: &Theme) -> String {
+    let style = match options.mode {
+        StyleMode::Inline => String::new(),
+        StyleMode::Stylesheet => format!("<style>\n{}</style>\n", stylesheet(theme)),
+    };
+
+    format!(
+        "<!DOCTYPE html>\n\
+         <html lang=\"en\">\n\
+         <head>\n\
+         <meta charset=\"utf-8\">\n\
+         <title>{title}</title>\n\
+         {style}\
+         </head>\n\
+         <body>\n\
+         {fragment}\n\
+         </body>\n\
+         </html>\n",
+        title = escape(&options.title),
+        fragment = render_fragment(code, tokens, options, theme),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fragment(code: &str, tokens: &[Token], options: &Options) -> String {
+        render_fragment(code, tokens, options, &Theme::default())
+    }
+
+    #[test]
+    fn special_characters_are_escaped() {
+        assert_eq!(
+            escape(r#"<a href="x">&'</a>"#),
+            "&lt;a href=&quot;x&quot;&gt;&amp;&#39;&lt;/a&gt;"
+        );
+
+        let code = "if a < b && s == \"<b>\" {}";
+        let tokens = [
+            Token::new(TokenKind::Keyword, 0..2),
+            Token::new(TokenKind::String, 17..22),
+        ];
+        let html = fragment(code, &tokens, &Options::default());
+        assert!(html.contains(
+            "<span class=\"keyword\">if</span> a &lt; b &amp;&amp; s == \
+             <span class=\"string\">&quot;&lt;b&gt;&quot;</span> {}"
+        ));
+        let inline = render_inline(code, &tokens, &Theme::default());
+        assert!(inline.contains(" a &lt; b &amp;&amp; s == "));
+        assert!(inline.contains("&quot;&lt;b&gt;&quot;</span> {}</pre>"));
+
+        let options = Options {
+            title: "a <b> & 'c'".to_string(),
+            ..Options::default()
+        };
+        let document = render_document("", &[], &options, &Theme::default());
+        assert!(document.contains("<title>a &lt;b&gt; &amp; &#39;c&#39;</title>"));
+    }
+
+    #[test]
+    fn nogutter_hides_the_line_numbers() {
+        let options = Options {
+            line_numbers: false,
+            ..Options::default()
+        };
+        let html = fragment("a\nb", &[], &options);
+        assert!(html.starts_with("<div class=\"dp-highlighter nogutter\"><ol start=\"1\">"));
+        assert!(stylesheet(&Theme::default()).contains(&format!(
+            ".dp-highlighter.nogutter ol {{ {LIST_NOGUTTER_CSS} }}"
+        )));
+
+        let inline = Options {
+            mode: StyleMode::Inline,
+            ..options
+        };
+        let html = fragment("a\nb", &[], &inline);
+        assert!(!html.contains("class="));
+        assert!(html.contains(LIST_NOGUTTER_CSS));
+        assert_eq!(html.matches(LINE_NOGUTTER_CSS).count(), 2);
+
+        let gutter = fragment("a\nb", &[], &Options::default());
+        assert!(gutter.starts_with("<div class=\"dp-highlighter\"><ol start=\"1\">"));
+        assert!(!gutter.contains(LINE_NOGUTTER_CSS));
+    }
+
+    #[test]
+    fn lines_alternate_starting_with_alt() {
+        let options = Options {
+            first_line: 10,
+            ..Options::default()
+        };
+        let html = fragment("a\n\nc\nd", &[], &options);
+        assert!(html.contains("<ol start=\"10\">"));
+        assert_eq!(html.matches("<li").count(), 4);
+        assert!(html.contains(
+            "<li class=\"alt\"><span>a</span></li>\
+             <li><span>&nbsp;</span></li>\
+             <li class=\"alt\"><span>c</span></li>\
+             <li><span>d</span></li>"
+        ));
+
+        // the preview marks odd line numbers, counting from one
+        let alts = html
+            .split("<li")
+            .skip(1)
+            .map(|li| li.starts_with(" class=\"alt\""))
+            .collect::<Vec<_>>();
+        let preview = (1..=4).map(|number| number % 2 == 1).collect::<Vec<_>>();
+        assert_eq!(alts, preview);
+
+        // inline documents alternate the backgrounds the same way
+        let theme = Theme::default();
+        let inline = render_fragment(
+            "a\nb\nc",
+            &[],
+            &Options {
+                mode: StyleMode::Inline,
+                ..Options::default()
+            },
+            &theme,
+        );
+        assert_eq!(inline.matches(&line_css(&theme, true)).count(), 2);
+        assert_eq!(inline.matches(&line_css(&theme, false)).count(), 1);
+    }
+}
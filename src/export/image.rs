@@ -0,0 +1,596 @@
+//! Pictures of the code for slides and posts, as SVG or rasterized to PNG.
+//!
+//! The code sits on a card in the theme's colors, optionally framed like a macOS window, with
+//! padding around it in a background color of its own. PNGs draw the glyphs of the embedded
+//! DejaVu Sans Mono, SVGs leave the text to the viewer's fonts.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use serde_derive::{Deserialize, Serialize};
+use tiny_skia::{FillRule, Paint, Path, PathBuilder, Pixmap, Rect, Stroke, Transform};
+
+use super::html::escape;
+use super::FONT;
+use crate::highlighter::{lines, Token};
+use crate::theme::{Color, Style, Theme};
+
+const FONT_SIZE: f32 = 14.0;
+const LINE_HEIGHT: f32 = 21.0;
+/// Font stack of SVG text, DejaVu Sans Mono first as PNGs are drawn with it.
+const SVG_FONT_FAMILY: &str = "'DejaVu Sans Mono', Consolas, 'Courier New', monospace";
+/// Space between the edges of the card and the code.
+const CARD_PADDING: f32 = 20.0;
+const CARD_RADIUS: f32 = 8.0;
+/// Space between the line numbers and the code.
+const GUTTER_GAP: f32 = 16.0;
+const TITLE_BAR_HEIGHT: f32 = 36.0;
+/// Close, minimize and zoom buttons of the window frame.
+const TRAFFIC_LIGHTS: [Color; 3] = [
+    Color::rgb(0xff, 0x5f, 0x56),
+    Color::rgb(0xff, 0xbd, 0x2e),
+    Color::rgb(0x27, 0xc9, 0x3f),
+];
+const TRAFFIC_LIGHT_RADIUS: f32 = 6.0;
+const TRAFFIC_LIGHT_SPACING: f32 = 20.0;
+/// Offset, blur and opacity of the drop shadow.
+const SHADOW_OFFSET: f32 = 12.0;
+const SHADOW_BLUR: f32 = 24.0;
+const SHADOW_OPACITY: f32 = 0.4;
+/// Layers of the PNG shadow, which approximates the blur with growing translucent cards.
+const SHADOW_LAYERS: usize = 16;
+/// Horizontal shear slanting italic tokens.
+const ITALIC_SHEAR: f32 = 0.2;
+/// Outline drawn around bold glyphs in PNGs, in pixels.
+const BOLD_STROKE: f32 = 0.6;
+/// Longest side of a PNG, browsers refuse to show larger images.
+pub const MAX_PNG_SIDE: u32 = 16_384;
+/// Pixels of a PNG at most, keeping its pixmap within 256 MiB.
+pub const MAX_PNG_PIXELS: u64 = 1 << 26;
+
+/// Why a PNG couldn't be rendered.
+#[derive(Debug, PartialEq, Eq)]
+pub enum PngError {
+    /// The image would be `width` by `height` pixels, beyond [`MAX_PNG_SIDE`] or
+    /// [`MAX_PNG_PIXELS`].
+    TooLarge { width: u32, height: u32 },
+    /// The encoder failed, with its message.
+    Encode(String),
+}
+
+impl fmt::Display for PngError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PngError::TooLarge { width, height } => write!(
+                f,
+                "the image would be {width}x{height} pixels, try a smaller scale or less code"
+            ),
+            PngError::Encode(message) => write!(f, "encoding the PNG failed: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for PngError {}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Options {
+    /// Space around the card, in pixels.
+    pub padding: u32,
+    /// Color of the space around the card, transparent when `None`.
+    pub background: Option<Color>,
+    pub shadow: bool,
+    /// Frames the card like a macOS window, with `title` in its title bar.
+    pub window: bool,
+    #[serde(skip)]
+    pub title: String,
+    /// Device pixels per pixel, `2` for retina displays.
+    pub scale: u32,
+    #[serde(skip)]
+    pub line_numbers: bool,
+    #[serde(skip)]
+    pub tab_width: usize,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            padding: 48,
+            background: Some(Color::rgb(0x9a, 0xa8, 0xc7)),
+            shadow: true,
+            window: true,
+            title: String::new(),
+            scale: 2,
+            line_numbers: false,
+            tab_width: 4,
+        }
+    }
+}
+
+/// Where everything goes, shared by both formats.
+struct Layout {
+    width: f32,
+    height: f32,
+    /// Left, top, width and height of the card.
+    card: (f32, f32, f32, f32),
+    /// Right edge of the line numbers, `None` without them.
+    gutter_right: Option<f32>,
+    code_left: f32,
+    /// Baseline of the first line.
+    baseline: f32,
+    /// Width of a character.
+    advance: f32,
+    /// Lines with tabs expanded and control characters dropped.
+    lines: Vec<Vec<(Style, String)>>,
+}
+
+impl Layout {
+    fn new(code: &str, tokens: &[Token], options: &Options, theme: &Theme, advance: f32) -> Self {
+        let tab_width = options.tab_width.max(1);
+        let lines = lines(code, tokens)
+            .into_iter()
+            .map(|line| {
+                let mut column = 0;
+                line.into_iter()
+                    .map(|(kind, text)| {
+                        let style = kind.map(|kind| theme.style(kind)).unwrap_or_default();
+                        let mut expanded = String::new();
+                        for c in text.chars() {
+                            match c {
+                                '\t' => {
+                                    let spaces = tab_width - column % tab_width;
+                                    expanded.extend(std::iter::repeat_n(' ', spaces));
+                                    column += spaces;
+                                }
+                                c if c < ' ' => {}
+                                c => {
+                                    expanded.push(c);
+                                    column += 1;
+                                }
+                            }
+                        }
+                        (style, expanded)
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        let columns = lines
+            .iter()
+            .map(|line| line.iter().map(|(_, text)| text.chars().count()).sum())
+            .max()
+            .unwrap_or(0);
+        let gutter_width = match options.line_numbers {
+            true => lines.len().to_string().len() as f32 * advance + GUTTER_GAP,
+            false => 0.0,
+        };
+        let title_bar = if options.window {
+            TITLE_BAR_HEIGHT
+        } else {
+            0.0
+        };
+
+        let padding = options.padding as f32;
+        let card_width = 2.0 * CARD_PADDING + gutter_width + columns as f32 * advance;
+        let card_height = title_bar + 2.0 * CARD_PADDING + lines.len() as f32 * LINE_HEIGHT;
+        let left = padding + CARD_PADDING;
+
+        Layout {
+            width: (card_width + 2.0 * padding).ceil(),
+            height: (card_height + 2.0 * padding).ceil(),
+            card: (padding, padding, card_width, card_height),
+            gutter_right: options
+                .line_numbers
+                .then_some(left + gutter_width - GUTTER_GAP),
+            code_left: left + gutter_width,
+            // centers the glyphs' x-height in the line
+            baseline: padding + title_bar + CARD_PADDING + LINE_HEIGHT * 0.75,
+            advance,
+            lines,
+        }
+    }
+
+    /// Center of the traffic light `index` and the middle of the title bar.
+    fn traffic_light(&self, index: usize) -> (f32, f32) {
+        let (left, top, ..) = self.card;
+        (
+            left + CARD_PADDING + TRAFFIC_LIGHT_RADIUS + index as f32 * TRAFFIC_LIGHT_SPACING,
+            top + TITLE_BAR_HEIGHT / 2.0 + CARD_PADDING / 2.0,
+        )
+    }
+}
+
+fn face() -> ttf_parser::Face<'static> {
+    ttf_parser::Face::parse(FONT, 0).expect("the embedded font is valid")
+}
+
+/// Width of a character in pixels, the font being monospaced.
+fn advance(face: &ttf_parser::Face) -> f32 {
+    let glyph = face.glyph_index('0').unwrap_or_default();
+    let advance = face.glyph_hor_advance(glyph).unwrap_or_default();
+    advance as f32 / face.units_per_em() as f32 * FONT_SIZE
+}
+
+/// Renders an SVG document, its size multiplied by the scale.
+pub fn render_svg(code: &str, tokens: &[Token], options: &Options, theme: &Theme) -> String {
+    let layout = Layout::new(code, tokens, options, theme, advance(&face()));
+    let scale = options.scale.max(1) as f32;
+    let (left, top, width, height) = layout.card;
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\">\n",
+        layout.width * scale,
+        layout.height * scale,
+        layout.width,
+        layout.height
+    );
+
+    if options.shadow {
+        svg.push_str(&format!(
+            "<defs><filter id=\"shadow\" x=\"-50%\" y=\"-50%\" width=\"200%\" height=\"200%\">\
+             <feDropShadow dx=\"0\" dy=\"{SHADOW_OFFSET}\" stdDeviation=\"{}\" flood-color=\"#000\" flood-opacity=\"{SHADOW_OPACITY}\"/>\
+             </filter></defs>\n",
+            SHADOW_BLUR / 2.0
+        ));
+    }
+    if let Some(background) = options.background {
+        svg.push_str(&format!(
+            "<rect width=\"100%\" height=\"100%\" fill=\"{}\"/>\n",
+            background.to_hex()
+        ));
+    }
+    svg.push_str(&format!(
+        "<rect x=\"{left}\" y=\"{top}\" width=\"{width}\" height=\"{height}\" rx=\"{CARD_RADIUS}\" fill=\"{}\"{}/>\n",
+        theme.background.to_hex(),
+        if options.shadow { " filter=\"url(#shadow)\"" } else { "" }
+    ));
+
+    svg.push_str(&format!(
+        "<g font-family=\"{SVG_FONT_FAMILY}\" font-size=\"{FONT_SIZE}\" xml:space=\"preserve\">\n"
+    ));
+    if options.window {
+        for (index, color) in TRAFFIC_LIGHTS.iter().enumerate() {
+            let (x, y) = layout.traffic_light(index);
+            svg.push_str(&format!(
+                "<circle cx=\"{x}\" cy=\"{y}\" r=\"{TRAFFIC_LIGHT_RADIUS}\" fill=\"{}\"/>\n",
+                color.to_hex()
+            ));
+        }
+        let (_, y) = layout.traffic_light(0);
+        svg.push_str(&format!(
+            "<text x=\"{}\" y=\"{}\" text-anchor=\"middle\" fill=\"{}\">{}</text>\n",
+            left + width / 2.0,
+            y + FONT_SIZE * 0.35,
+            theme.gutter.to_hex(),
+            escape(&options.title)
+        ));
+    }
+
+    for (index, line) in layout.lines.iter().enumerate() {
+        let y = layout.baseline + index as f32 * LINE_HEIGHT;
+        if let Some(right) = layout.gutter_right {
+            svg.push_str(&format!(
+                "<text x=\"{right}\" y=\"{y}\" text-anchor=\"end\" fill=\"{}\">{}</text>\n",
+                theme.gutter.to_hex(),
+                index + 1
+            ));
+        }
+
+        let mut x = layout.code_left;
+        for (style, text) in line {
+            let width = text.chars().count() as f32 * layout.advance;
+            if let Some(background) = style.background {
+                svg.push_str(&format!(
+                    "<rect x=\"{x}\" y=\"{}\" width=\"{width}\" height=\"{LINE_HEIGHT}\" fill=\"{}\"/>\n",
+                    y - LINE_HEIGHT * 0.75,
+                    background.to_hex()
+                ));
+            }
+            x += width;
+        }
+
+        svg.push_str(&format!("<text x=\"{}\" y=\"{y}\">", layout.code_left));
+        for (style, text) in line {
+            svg.push_str(&format!(
+                "<tspan fill=\"{}\"{}{}>{}</tspan>",
+                style.color.unwrap_or(theme.text).to_hex(),
+                if style.bold {
+                    " font-weight=\"bold\""
+                } else {
+                    ""
+                },
+                if style.italic {
+                    " font-style=\"italic\""
+                } else {
+                    ""
+                },
+                escape(text)
+            ));
+        }
+        svg.push_str("</text>\n");
+    }
+    svg.push_str("</g>\n</svg>\n");
+    svg
+}
+
+/// Collects a glyph outline into a path in pixels, the baseline at `y = 0`.
+struct Outline {
+    builder: PathBuilder,
+    /// Pixels per font unit.
+    scale: f32,
+}
+
+impl ttf_parser::OutlineBuilder for Outline {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.builder.move_to(x * self.scale, -y * self.scale);
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.builder.line_to(x * self.scale, -y * self.scale);
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        let s = self.scale;
+        self.builder.quad_to(x1 * s, -y1 * s, x * s, -y * s);
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        let s = self.scale;
+        self.builder
+            .cubic_to(x1 * s, -y1 * s, x2 * s, -y2 * s, x * s, -y * s);
+    }
+
+    fn close(&mut self) {
+        self.builder.close();
+    }
+}
+
+/// Glyph outlines by character, each built once.
+struct Glyphs {
+    face: ttf_parser::Face<'static>,
+    paths: HashMap<char, Option<Path>>,
+}
+
+impl Glyphs {
+    fn path(&mut self, c: char) -> Option<&Path> {
+        let face = &self.face;
+        self.paths
+            .entry(c)
+            .or_insert_with(|| {
+                let glyph = face.glyph_index(c)?;
+                let mut outline = Outline {
+                    builder: PathBuilder::new(),
+                    scale: FONT_SIZE / face.units_per_em() as f32,
+                };
+                face.outline_glyph(glyph, &mut outline)?;
+                outline.builder.finish()
+            })
+            .as_ref()
+    }
+}
+
+fn paint(color: Color, opacity: f32) -> Paint<'static> {
+    let mut paint = Paint::default();
+    paint.set_color_rgba8(color.r, color.g, color.b, (opacity * 255.0) as u8);
+    paint.anti_alias = true;
+    paint
+}
+
+/// A rectangle with rounded corners, `None` when it has no area.
+fn rounded_rect(left: f32, top: f32, width: f32, height: f32, radius: f32) -> Option<Path> {
+    let (right, bottom) = (left + width, top + height);
+    let radius = radius.min(width / 2.0).min(height / 2.0);
+    let mut builder = PathBuilder::new();
+    builder.move_to(left + radius, top);
+    builder.line_to(right - radius, top);
+    builder.quad_to(right, top, right, top + radius);
+    builder.line_to(right, bottom - radius);
+    builder.quad_to(right, bottom, right - radius, bottom);
+    builder.line_to(left + radius, bottom);
+    builder.quad_to(left, bottom, left, bottom - radius);
+    builder.line_to(left, top + radius);
+    builder.quad_to(left, top, left + radius, top);
+    builder.close();
+    builder.finish()
+}
+
+/// Draws `text` with its left end of the baseline at `x`, `y`.
+fn draw_text(
+    pixmap: &mut Pixmap,
+    glyphs: &mut Glyphs,
+    text: &str,
+    (x, y): (f32, f32),
+    style: Style,
+    color: Color,
+    transform: Transform,
+) {
+    let advance = advance(&glyphs.face);
+    let paint = paint(style.color.unwrap_or(color), 1.0);
+    let stroke = Stroke {
+        width: BOLD_STROKE,
+        ..Stroke::default()
+    };
+    for (index, c) in text.chars().enumerate() {
+        let Some(path) = glyphs.path(c) else {
+            continue;
+        };
+        let mut transform = transform.pre_translate(x + index as f32 * advance, y);
+        if style.italic {
+            transform = transform.pre_concat(Transform::from_skew(-ITALIC_SHEAR, 0.0));
+        }
+        pixmap.fill_path(path, &paint, FillRule::Winding, transform, None);
+        if style.bold {
+            pixmap.stroke_path(path, &paint, &stroke, transform, None);
+        }
+    }
+}
+
+/// Renders a PNG, its size multiplied by the scale.
+pub fn render_png(
+    code: &str,
+    tokens: &[Token],
+    options: &Options,
+    theme: &Theme,
+) -> Result<Vec<u8>, PngError> {
+    let mut glyphs = Glyphs {
+        face: face(),
+        paths: HashMap::new(),
+    };
+    let layout = Layout::new(code, tokens, options, theme, advance(&glyphs.face));
+    let scale = options.scale.max(1) as f32;
+    let transform = Transform::from_scale(scale, scale);
+    // saturating, so sizes beyond `u32` are too large as well
+    let width = (layout.width * scale).ceil() as u32;
+    let height = (layout.height * scale).ceil() as u32;
+    let too_large = PngError::TooLarge { width, height };
+    if width.max(height) > MAX_PNG_SIDE || width as u64 * height as u64 > MAX_PNG_PIXELS {
+        return Err(too_large);
+    }
+    let mut pixmap = Pixmap::new(width, height).ok_or(too_large)?;
+
+    if let Some(background) = options.background {
+        pixmap.fill(tiny_skia::Color::from_rgba8(
+            background.r,
+            background.g,
+            background.b,
+            255,
+        ));
+    }
+
+    let (left, top, width, height) = layout.card;
+    if options.shadow {
+        // each layer adds a little darkness further out, fading like a blur
+        let black = Color::rgb(0, 0, 0);
+        // so that all layers stacked make up the shadow's opacity
+        let opacity = 1.0 - (1.0 - SHADOW_OPACITY).powf(1.0 / SHADOW_LAYERS as f32);
+        for layer in 0..SHADOW_LAYERS {
+            let spread = SHADOW_BLUR / 2.0 * (1.0 - layer as f32 / SHADOW_LAYERS as f32);
+            if let Some(path) = rounded_rect(
+                left - spread,
+                top - spread + SHADOW_OFFSET,
+                width + 2.0 * spread,
+                height + 2.0 * spread,
+                CARD_RADIUS + spread,
+            ) {
+                pixmap.fill_path(
+                    &path,
+                    &paint(black, opacity),
+                    FillRule::Winding,
+                    transform,
+                    None,
+                );
+            }
+        }
+    }
+    if let Some(path) = rounded_rect(left, top, width, height, CARD_RADIUS) {
+        pixmap.fill_path(
+            &path,
+            &paint(theme.background, 1.0),
+            FillRule::Winding,
+            transform,
+            None,
+        );
+    }
+
+    if options.window {
+        for (index, color) in TRAFFIC_LIGHTS.iter().enumerate() {
+            let (x, y) = layout.traffic_light(index);
+            if let Some(path) = PathBuilder::from_circle(x, y, TRAFFIC_LIGHT_RADIUS) {
+                pixmap.fill_path(
+                    &path,
+                    &paint(*color, 1.0),
+                    FillRule::Winding,
+                    transform,
+                    None,
+                );
+            }
+        }
+        let (_, y) = layout.traffic_light(0);
+        let title_width = options.title.chars().count() as f32 * layout.advance;
+        draw_text(
+            &mut pixmap,
+            &mut glyphs,
+            &options.title,
+            (left + (width - title_width) / 2.0, y + FONT_SIZE * 0.35),
+            Style::default(),
+            theme.gutter,
+            transform,
+        );
+    }
+
+    for (index, line) in layout.lines.iter().enumerate() {
+        let y = layout.baseline + index as f32 * LINE_HEIGHT;
+        if let Some(right) = layout.gutter_right {
+            let number = (index + 1).to_string();
+            let x = right - number.len() as f32 * layout.advance;
+            draw_text(
+                &mut pixmap,
+                &mut glyphs,
+                &number,
+                (x, y),
+                Style::default(),
+                theme.gutter,
+                transform,
+            );
+        }
+
+        let mut x = layout.code_left;
+        for (style, text) in line {
+            let width = text.chars().count() as f32 * layout.advance;
+            if let Some(background) = style.background {
+                if let Some(rect) = Rect::from_xywh(x, y - LINE_HEIGHT * 0.75, width, LINE_HEIGHT) {
+                    pixmap.fill_rect(rect, &paint(background, 1.0), transform, None);
+                }
+            }
+            draw_text(
+                &mut pixmap,
+                &mut glyphs,
+                text,
+                (x, y),
+                *style,
+                theme.text,
+                transform,
+            );
+            x += width;
+        }
+    }
+
+    pixmap
+        .encode_png()
+        .map_err(|error| PngError::Encode(error.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::highlighter::Registry;
+
+    #[test]
+    fn renders_a_png() {
+        let code = "fn main() {}\n";
+        let tokens = Registry::builtin().get("rust").unwrap().tokenize(code);
+        let png = render_png(code, &tokens, &Options::default(), &Theme::light()).unwrap();
+        assert!(png.starts_with(b"\x89PNG"));
+    }
+
+    #[test]
+    fn refuses_oversized_pngs() {
+        let code = "x".repeat(5000);
+        let options = Options {
+            scale: 4,
+            ..Options::default()
+        };
+        assert!(matches!(
+            render_png(&code, &[], &options, &Theme::light()),
+            Err(PngError::TooLarge { width, .. }) if width > MAX_PNG_SIDE
+        ));
+
+        let options = Options {
+            scale: u32::MAX,
+            ..Options::default()
+        };
+        assert!(matches!(
+            render_png("x", &[], &options, &Theme::light()),
+            Err(PngError::TooLarge { .. })
+        ));
+    }
+}
@@ -0,0 +1,72 @@
+use serde_derive::Serialize;
+
+use crate::highlighter::{Token, TokenKind};
+
+#[derive(Serialize)]
+struct Document<'a> {
+    language: &'a str,
+    tokens: Vec<JsonToken<'a>>,
+}
+
+#[derive(Serialize)]
+struct JsonToken<'a> {
+    kind: TokenKind,
+    /// The shCore CSS class of `kind`.
+    class: &'static str,
+    /// Byte offset into the code.
+    start: usize,
+    /// Byte offset into the code, exclusive.
+    end: usize,
+    text: &'a str,
+}
+
+/// Serializes the tokens as JSON, for tools which style the code themselves.
+pub fn render(code: &str, tokens: &[Token], language: &str) -> String {
+    let document = Document {
+        language,
+        tokens: tokens
+            .iter()
+            .map(|token| JsonToken {
+                kind: token.kind,
+                class: token.kind.css_class(),
+                start: token.range.start,
+                end: token.range.end,
+                text: &code[token.range.clone()],
+            })
+            .collect(),
+    };
+    serde_json::to_string_pretty(&document).expect("tokens always serialize")
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    use super::*;
+
+    #[test]
+    fn tokens_keep_their_shape() {
+        let code = "let s = \"é\";";
+        let tokens = [
+            Token::new(TokenKind::Keyword, 0..3),
+            Token::new(TokenKind::String, 8..12),
+        ];
+        let document: Value = serde_json::from_str(&render(code, &tokens, "rust")).unwrap();
+        assert_eq!(
+            document,
+            json!({
+                "language": "rust",
+                "tokens": [
+                    {"kind": "Keyword", "class": "keyword", "start": 0, "end": 3, "text": "let"},
+                    {"kind": "String", "class": "string", "start": 8, "end": 12, "text": "\"é\""},
+                ],
+            })
+        );
+    }
+
+    #[test]
+    fn no_tokens_is_an_empty_list() {
+        let document: Value = serde_json::from_str(&render("x", &[], "plain")).unwrap();
+        assert_eq!(document, json!({"language": "plain", "tokens": []}));
+    }
+}
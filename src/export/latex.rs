@@ -0,0 +1,333 @@
+//! LaTeX, for papers quoting code.
+//!
+//! Either flavor writes a fragment to paste into a document rather than a document of its own,
+//! preceded by comments naming the packages the preamble needs and the color definitions it uses.
+
+use crate::highlighter::{lines, Token, TokenKind};
+use crate::theme::{Color, Style, Theme};
+
+/// How the code is typeset.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Flavor {
+    /// A fancyvrb `Verbatim` block colored token by token through `\textcolor`, on a breakable
+    /// tcolorbox of the theme's background.
+    Verbatim,
+    /// An `lstlisting` environment, leaving the highlighting to the listings package with the
+    /// colors of the theme, so it highlights only what listings knows.
+    Listings,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Options {
+    pub flavor: Flavor,
+    pub line_numbers: bool,
+    pub tab_width: usize,
+    /// Id of the language, selecting the language listings highlights.
+    pub language: String,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            flavor: Flavor::Verbatim,
+            line_numbers: false,
+            tab_width: 4,
+            language: String::new(),
+        }
+    }
+}
+
+/// The listings language of a language id, for the languages listings ships with.
+fn listings_language(id: &str) -> Option<&'static str> {
+    Some(match id {
+        "bash" => "bash",
+        "cpp" => "C++",
+        "csharp" => "[Sharp]C",
+        "delphi" => "Delphi",
+        "java" => "Java",
+        "php" => "PHP",
+        "python" => "Python",
+        "ruby" => "Ruby",
+        "sql" => "SQL",
+        "vb" => "[Visual]Basic",
+        "xml" => "XML",
+        _ => return None,
+    })
+}
+
+/// Names of the colors used, each defined once through `\definecolor`.
+struct Colors(Vec<Color>);
+
+impl Colors {
+    fn name(&mut self, color: Color) -> String {
+        let index = match self.0.iter().position(|c| *c == color) {
+            Some(index) => index,
+            None => {
+                self.0.push(color);
+                self.0.len() - 1
+            }
+        };
+        format!("planetb{index}")
+    }
+
+    fn definitions(&self) -> String {
+        self.0
+            .iter()
+            .enumerate()
+            .map(|(index, color)| {
+                format!(
+                    "\\definecolor{{planetb{index}}}{{HTML}}{{{:02X}{:02X}{:02X}}}\n",
+                    color.r, color.g, color.b
+                )
+            })
+            .collect()
+    }
+}
+
+/// Font switches selecting `style`, for listings' style keys.
+fn switches(style: Style, colors: &mut Colors) -> String {
+    let mut switches = String::new();
+    if let Some(color) = style.color {
+        switches.push_str(&format!("\\color{{{}}}", colors.name(color)));
+    }
+    if style.bold {
+        switches.push_str("\\bfseries");
+    }
+    if style.italic {
+        switches.push_str("\\itshape");
+    }
+    switches
+}
+
+/// Escapes `text` inside a `Verbatim` block with `commandchars=\\\{\}`, where only the command
+/// characters are special. Tabs are expanded, `column` being where `text` starts.
+fn escape_verbatim(text: &str, column: &mut usize, tab_width: usize, latex: &mut String) {
+    for c in text.chars() {
+        match c {
+            '\\' => latex.push_str("\\char92{}"),
+            '{' => latex.push_str("\\char123{}"),
+            '}' => latex.push_str("\\char125{}"),
+            '\t' => {
+                let spaces = tab_width - *column % tab_width;
+                latex.push_str(&" ".repeat(spaces));
+                *column += spaces;
+                continue;
+            }
+            c if c < ' ' => continue,
+            c => latex.push(c),
+        }
+        *column += 1;
+    }
+}
+
+fn render_verbatim(code: &str, tokens: &[Token], options: &Options, theme: &Theme) -> String {
+    let mut colors = Colors(Vec::new());
+    let text = colors.name(theme.text);
+    let background = colors.name(theme.background);
+    let gutter = colors.name(theme.gutter);
+    let tab_width = options.tab_width.max(1);
+
+    let mut lines = lines(code, tokens);
+    if code.ends_with('\n') {
+        lines.pop();
+    }
+    let width = lines.len().to_string().len();
+    let mut body = String::new();
+
+    for (index, line) in lines.into_iter().enumerate() {
+        if options.line_numbers {
+            body.push_str(&format!(
+                "\\textcolor{{{gutter}}}{{{:>width$}}} ",
+                index + 1
+            ));
+        }
+        let mut column = 0;
+        for (kind, part) in line {
+            let style = kind.map(|kind| theme.style(kind)).unwrap_or_default();
+            let mut escaped = String::new();
+            escape_verbatim(part, &mut column, tab_width, &mut escaped);
+            if style.bold {
+                escaped = format!("\\textbf{{{escaped}}}");
+            }
+            if style.italic {
+                escaped = format!("\\textit{{{escaped}}}");
+            }
+            if let Some(color) = style.color {
+                escaped = format!("\\textcolor{{{}}}{{{escaped}}}", colors.name(color));
+            }
+            if let Some(color) = style.background {
+                escaped = format!(
+                    "{{\\fboxsep0pt\\colorbox{{{}}}{{{escaped}}}}}",
+                    colors.name(color)
+                );
+            }
+            body.push_str(&escaped);
+        }
+        body.push('\n');
+    }
+
+    // a box of its own couldn't break across pages, the tcolorbox does
+    format!(
+        "% needs \\usepackage{{xcolor}}, \\usepackage{{fancyvrb}} and \\usepackage[breakable]{{tcolorbox}}\n\
+         {definitions}\
+         \\begin{{tcolorbox}}[breakable,colback={background},colframe={background},boxrule=0pt,arc=0pt]\n\
+         \\begin{{Verbatim}}[commandchars=\\\\\\{{\\}},formatcom=\\color{{{text}}}]\n\
+         {body}\
+         \\end{{Verbatim}}\n\
+         \\end{{tcolorbox}}\n",
+        definitions = colors.definitions(),
+    )
+}
+
+fn render_listings(code: &str, options: &Options, theme: &Theme) -> String {
+    let mut colors = Colors(Vec::new());
+    let mut keys = vec![
+        format!(
+            "basicstyle={{\\ttfamily\\small\\color{{{}}}}}",
+            colors.name(theme.text)
+        ),
+        format!(
+            "backgroundcolor=\\color{{{}}}",
+            colors.name(theme.background)
+        ),
+        format!("tabsize={}", options.tab_width.max(1)),
+        "columns=fullflexible".to_string(),
+        "keepspaces=true".to_string(),
+        "showstringspaces=false".to_string(),
+        "upquote=true".to_string(),
+        "extendedchars=true".to_string(),
+    ];
+    if let Some(language) = listings_language(&options.language) {
+        keys.push(format!("language={{{language}}}"));
+    }
+    for (key, kind) in [
+        ("keywordstyle", TokenKind::Keyword),
+        ("ndkeywordstyle", TokenKind::DataType),
+        ("commentstyle", TokenKind::Comment),
+        ("stringstyle", TokenKind::String),
+        ("directivestyle", TokenKind::Preprocessor),
+    ] {
+        keys.push(format!(
+            "{key}={{{}}}",
+            switches(theme.style(kind), &mut colors)
+        ));
+    }
+    if options.line_numbers {
+        keys.push("numbers=left".to_string());
+        keys.push(format!(
+            "numberstyle={{\\tiny\\color{{{}}}}}",
+            colors.name(theme.gutter)
+        ));
+        keys.push(format!(
+            "frame=l,framerule=2pt,rulecolor=\\color{{{}}}",
+            colors.name(theme.border)
+        ));
+    }
+
+    // the environment ends at the first `\end{lstlisting}`, so one inside the code has its
+    // backslash written through an escape character the code doesn't use
+    let end = "\\end{lstlisting}";
+    let mut code = code.strip_suffix('\n').unwrap_or(code).to_string();
+    if code.contains(end) {
+        let escape = ['`', '|', '@', '!', '§', '¦']
+            .into_iter()
+            .find(|c| !code.contains(*c));
+        if let Some(escape) = escape {
+            keys.push(format!("escapechar={escape}"));
+            code = code.replace(
+                end,
+                &format!("{escape}\\textbackslash{escape}end{{lstlisting}}"),
+            );
+        }
+    }
+
+    format!(
+        "% needs \\usepackage{{xcolor}} and \\usepackage{{listings}}\n\
+         {definitions}\
+         \\begin{{lstlisting}}[{keys}]\n\
+         {code}\n\
+         \\end{{lstlisting}}\n",
+        definitions = colors.definitions(),
+        keys = keys.join(",\n  "),
+    )
+}
+
+/// Renders a LaTeX fragment in the colors of `theme`. The `tokens` go unused with
+/// [`Flavor::Listings`], listings tokenizing the code by itself.
+pub fn render(code: &str, tokens: &[Token], options: &Options, theme: &Theme) -> String {
+    match options.flavor {
+        Flavor::Verbatim => render_verbatim(code, tokens, options, theme),
+        Flavor::Listings => render_listings(code, options, theme),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn escape(text: &str, column: &mut usize) -> String {
+        let mut latex = String::new();
+        escape_verbatim(text, column, 4, &mut latex);
+        latex
+    }
+
+    #[test]
+    fn command_characters_are_escaped() {
+        let mut column = 0;
+        assert_eq!(
+            escape("\\textbf{x}", &mut column),
+            "\\char92{}textbf\\char123{}x\\char125{}"
+        );
+        // each escape takes a single column
+        assert_eq!(column, 10);
+        assert_eq!(escape("%$&#_^~", &mut 0), "%$&#_^~");
+    }
+
+    #[test]
+    fn tabs_expand_to_the_next_stop() {
+        let mut column = 0;
+        assert_eq!(escape("\tab\tc", &mut column), "    ab  c");
+        assert_eq!(column, 9);
+        // continuing from a part that ended mid-stop
+        let mut column = 3;
+        assert_eq!(escape("\t", &mut column), " ");
+        assert_eq!(column, 4);
+    }
+
+    #[test]
+    fn control_characters_are_dropped() {
+        let mut column = 0;
+        assert_eq!(escape("a\u{7}b\u{1b}[0mc", &mut column), "ab[0mc");
+        assert_eq!(column, 6);
+    }
+
+    #[test]
+    fn verbatim_breaks_across_pages() {
+        let code = "fn main() {}\n";
+        let tokens = [Token::new(TokenKind::Keyword, 0..2)];
+        let latex = render(code, &tokens, &Options::default(), &Theme::default());
+        assert!(latex.contains("\\begin{tcolorbox}[breakable,"), "{latex}");
+        assert!(latex.contains("\\begin{Verbatim}[commandchars=\\\\\\{\\}"));
+        assert!(latex.contains("\\textcolor{planetb3}{\\textbf{fn}} main()"));
+        assert!(latex.contains(" main() \\char123{}\\char125{}\n\\end{Verbatim}"));
+        assert!(!latex.contains("BVerbatim"));
+    }
+
+    #[test]
+    fn listings_escape_their_end() {
+        let options = Options {
+            flavor: Flavor::Listings,
+            language: "python".to_string(),
+            ..Options::default()
+        };
+        let code = "s = '`|\\end{lstlisting}'\n";
+        let latex = render(code, &[], &options, &Theme::default());
+        assert!(latex.contains("language={Python}"));
+        assert!(latex.contains("escapechar=@"), "{latex}");
+        assert!(latex.contains("s = '`|@\\textbackslash@end{lstlisting}'\n\\end{lstlisting}\n"));
+        assert_eq!(latex.matches("\\end{lstlisting}").count(), 1);
+
+        let plain = render("x = 1", &[], &options, &Theme::default());
+        assert!(!plain.contains("escapechar"));
+    }
+}
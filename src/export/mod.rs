@@ -0,0 +1,138 @@
+//! Serializes highlighted code into formats other applications understand.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::highlighter::{Language, Token};
+use crate::theme::Theme;
+
+pub mod ansi;
+pub mod docx;
+pub mod html;
+pub mod image;
+pub mod json;
+pub mod latex;
+pub mod pdf;
+pub mod rtf;
+
+/// DejaVu Sans Mono, embedded into PDFs and drawn into images.
+const FONT: &[u8] = include_bytes!("../assets/fonts/DejaVuSansMono.ttf");
+
+/// The formats the command line tool and the server render to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Format {
+    /// A standalone HTML document with an embedded stylesheet.
+    Html,
+    Rtf,
+    /// Text colored for terminals.
+    Ansi,
+    /// The tokens, for tools styling the code themselves.
+    Json,
+    /// A Word document.
+    Docx,
+    Pdf,
+    /// A fancyvrb `Verbatim` block to paste into a LaTeX document.
+    Latex,
+}
+
+impl Format {
+    pub const ALL: [Format; 7] = [
+        Format::Html,
+        Format::Rtf,
+        Format::Ansi,
+        Format::Json,
+        Format::Docx,
+        Format::Pdf,
+        Format::Latex,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Format::Html => "html",
+            Format::Rtf => "rtf",
+            Format::Ansi => "ansi",
+            Format::Json => "json",
+            Format::Docx => "docx",
+            Format::Pdf => "pdf",
+            Format::Latex => "latex",
+        }
+    }
+
+    pub fn mime_type(self) -> &'static str {
+        match self {
+            Format::Html => "text/html; charset=utf-8",
+            Format::Rtf => "application/rtf",
+            Format::Ansi => "text/plain; charset=utf-8",
+            Format::Json => "application/json",
+            Format::Docx => {
+                "application/vnd.openxmlformats-officedocument.wordprocessingml.document"
+            }
+            Format::Pdf => "application/pdf",
+            Format::Latex => "application/x-latex; charset=utf-8",
+        }
+    }
+
+    /// Renders `code`, the `tokens` of `language`, in this format. Line numbers show in every
+    /// format but RTF and JSON.
+    pub fn render(
+        self,
+        code: &str,
+        tokens: &[Token],
+        language: &Language,
+        theme: &Theme,
+        line_numbers: bool,
+    ) -> Vec<u8> {
+        let text = match self {
+            Format::Html => {
+                let options = html::Options {
+                    line_numbers,
+                    title: language.name.clone(),
+                    ..html::Options::default()
+                };
+                html::render_document(code, tokens, &options, theme)
+            }
+            Format::Rtf => rtf::render(code, tokens, theme),
+            Format::Ansi => ansi::render(code, tokens, theme, line_numbers),
+            Format::Json => json::render(code, tokens, &language.id),
+            Format::Docx => return docx::render(code, tokens, theme, line_numbers),
+            Format::Pdf => {
+                let options = pdf::Options {
+                    title: language.name.clone(),
+                    line_numbers,
+                    tab_width: language.tab_width,
+                };
+                return pdf::render(code, tokens, &options, theme);
+            }
+            Format::Latex => {
+                let options = latex::Options {
+                    line_numbers,
+                    tab_width: language.tab_width,
+                    language: language.id.clone(),
+                    ..latex::Options::default()
+                };
+                latex::render(code, tokens, &options, theme)
+            }
+        };
+        text.into_bytes()
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Format::ALL
+            .into_iter()
+            .find(|format| format.name().eq_ignore_ascii_case(name))
+            .ok_or_else(|| {
+                let names = Format::ALL.map(Format::name);
+                format!("unknown format `{name}`, pick one of {}", names.join(", "))
+            })
+    }
+}
@@ -0,0 +1,564 @@
+//! PDF documents laid out from the tokens, with an embedded DejaVu Sans Mono.
+//!
+//! Only the regular face is embedded, subset to the glyphs used. Bold tokens are drawn with an
+//! outline around the glyphs and italic ones slanted, so one font covers every style.
+
+use std::collections::BTreeMap;
+
+use pdf_writer::types::{CidFontType, FontFlags, SystemInfo, TextRenderingMode};
+use pdf_writer::{Content, Filter, Finish, Name, Pdf, Rect, Ref, Str, TextStr};
+
+use super::FONT;
+use crate::highlighter::{lines, Token};
+use crate::theme::{Color, Style, Theme};
+
+/// Name of the embedded font, the prefix marking it as a subset.
+const FONT_NAME: Name = Name(b"PLANTB+DejaVuSansMono");
+const FONT_RESOURCE: Name = Name(b"F1");
+const SYSTEM_INFO: SystemInfo = SystemInfo {
+    registry: Str(b"Adobe"),
+    ordering: Str(b"Identity"),
+    supplement: 0,
+};
+
+/// A4 in points.
+const PAGE_WIDTH: f32 = 595.0;
+const PAGE_HEIGHT: f32 = 842.0;
+const MARGIN: f32 = 40.0;
+const FONT_SIZE: f32 = 9.0;
+const LINE_HEIGHT: f32 = 11.5;
+/// Space taken by the header above the code.
+const HEADER_HEIGHT: f32 = 24.0;
+/// Space between the gutter or the code and the edges of their backgrounds.
+const PADDING: f32 = 4.0;
+/// Marks the rows a long line wraps onto, in place of a line number.
+const CONTINUATION: char = '↪';
+/// Horizontal shear slanting italic tokens.
+const ITALIC_SHEAR: f32 = 0.2;
+/// Outline drawn around bold tokens, in points.
+const BOLD_STROKE: f32 = 0.35;
+/// Gray of the header, which is on the white page rather than the theme's background.
+const HEADER_GRAY: f32 = 0.35;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Options {
+    /// Repeated in the header of every page, e.g. the file name.
+    pub title: String,
+    pub line_numbers: bool,
+    /// Columns a tab advances to the next multiple of.
+    pub tab_width: usize,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            title: "Code".to_string(),
+            line_numbers: true,
+            tab_width: 4,
+        }
+    }
+}
+
+/// A row of the layout, a whole line or the part of a long one wrapping onto it.
+struct Row {
+    /// Index of the line the row belongs to.
+    line: usize,
+    /// Whether an earlier row holds the start of the line.
+    continuation: bool,
+    parts: Vec<(Style, String)>,
+}
+
+/// Splits the lines into rows of at most `columns` characters, tabs expanded to spaces.
+fn layout(
+    code: &str,
+    tokens: &[Token],
+    theme: &Theme,
+    tab_width: usize,
+    columns: usize,
+) -> Vec<Row> {
+    let mut rows = Vec::new();
+
+    for (index, line) in lines(code, tokens).into_iter().enumerate() {
+        let mut row = Row {
+            line: index,
+            continuation: false,
+            parts: Vec::new(),
+        };
+        let mut column = 0;
+
+        for (kind, text) in line {
+            let style = kind.map(|kind| theme.style(kind)).unwrap_or_default();
+            let mut part = String::new();
+            for c in text.chars() {
+                let expanded = match c {
+                    '\t' => tab_width.max(1) - column % tab_width.max(1),
+                    c if c < ' ' => continue,
+                    _ => 1,
+                };
+                for _ in 0..expanded {
+                    if column == columns {
+                        row.parts.push((style, std::mem::take(&mut part)));
+                        rows.push(std::mem::replace(
+                            &mut row,
+                            Row {
+                                line: index,
+                                continuation: true,
+                                parts: Vec::new(),
+                            },
+                        ));
+                        column = 0;
+                    }
+                    part.push(if c == '\t' { ' ' } else { c });
+                    column += 1;
+                }
+            }
+            row.parts.push((style, part));
+        }
+        rows.push(row);
+    }
+
+    rows
+}
+
+/// The font and the glyphs drawn with it, for embedding just those.
+struct Glyphs<'a> {
+    face: ttf_parser::Face<'a>,
+    used: BTreeMap<u16, char>,
+}
+
+impl Glyphs<'_> {
+    /// `text` as the two byte glyph ids the `Identity-H` encoding expects.
+    fn encode(&mut self, text: &str) -> Vec<u8> {
+        let mut encoded = Vec::with_capacity(text.len() * 2);
+        for c in text.chars() {
+            let glyph = self.face.glyph_index(c).map_or(0, |glyph| glyph.0);
+            self.used.entry(glyph).or_insert(c);
+            encoded.extend(glyph.to_be_bytes());
+        }
+        encoded
+    }
+
+    /// Width of a character in points, the font being monospaced.
+    fn advance(&self) -> f32 {
+        let glyph = self.face.glyph_index('0').unwrap_or_default();
+        let advance = self.face.glyph_hor_advance(glyph).unwrap_or_default();
+        advance as f32 / self.face.units_per_em() as f32 * FONT_SIZE
+    }
+
+    /// Converts font units to the thousandths of the text size PDF measures fonts in.
+    fn to_pdf(&self, units: impl Into<f32>) -> f32 {
+        units.into() * 1000.0 / self.face.units_per_em() as f32
+    }
+}
+
+fn fill(content: &mut Content, color: Color) {
+    content.set_fill_rgb(
+        color.r as f32 / 255.0,
+        color.g as f32 / 255.0,
+        color.b as f32 / 255.0,
+    );
+}
+
+fn stroke(content: &mut Content, color: Color) {
+    content.set_stroke_rgb(
+        color.r as f32 / 255.0,
+        color.g as f32 / 255.0,
+        color.b as f32 / 255.0,
+    );
+}
+
+/// Shows `text` at `x`, `y` in `style`, or in `color` where the style has none.
+fn show(
+    content: &mut Content,
+    glyphs: &mut Glyphs,
+    text: &str,
+    (x, y): (f32, f32),
+    style: Style,
+    color: Color,
+) {
+    let width = text.chars().count() as f32 * glyphs.advance();
+    if let Some(background) = style.background {
+        fill(content, background);
+        content
+            .rect(x, y - LINE_HEIGHT * 0.25, width, LINE_HEIGHT)
+            .fill_nonzero();
+    }
+
+    let color = style.color.unwrap_or(color);
+    fill(content, color);
+    content.begin_text();
+    content.set_font(FONT_RESOURCE, FONT_SIZE);
+    if style.bold {
+        stroke(content, color);
+        content.set_line_width(BOLD_STROKE);
+        content.set_text_rendering_mode(TextRenderingMode::FillStroke);
+    } else {
+        // the mode outlives the text object
+        content.set_text_rendering_mode(TextRenderingMode::Fill);
+    }
+    let shear = if style.italic { ITALIC_SHEAR } else { 0.0 };
+    content.set_text_matrix([1.0, 0.0, shear, 1.0, x, y]);
+    content.show(Str(&glyphs.encode(text)));
+    content.end_text();
+}
+
+/// Deflates a stream, PDF readers inflate `FlateDecode` streams.
+fn compress(data: &[u8]) -> Vec<u8> {
+    miniz_oxide::deflate::compress_to_vec_zlib(data, 6)
+}
+
+/// Renders a PDF document, the code in the theme's colors on A4 pages.
+pub fn render(code: &str, tokens: &[Token], options: &Options, theme: &Theme) -> Vec<u8> {
+    let face = ttf_parser::Face::parse(FONT, 0).expect("the embedded font is valid");
+    let mut glyphs = Glyphs {
+        face,
+        used: BTreeMap::from([(0, '\u{fffd}')]),
+    };
+    let advance = glyphs.advance();
+
+    let line_count = code.split('\n').count();
+    // the continuation marker needs a column even without line numbers
+    let gutter_columns = match options.line_numbers {
+        true => line_count.to_string().len(),
+        false => 1,
+    };
+    let gutter_width = gutter_columns as f32 * advance + 2.0 * PADDING;
+    let code_x = MARGIN + gutter_width + PADDING;
+    let columns = (((PAGE_WIDTH - MARGIN - PADDING - code_x) / advance) as usize).max(1);
+    let rows = layout(code, tokens, theme, options.tab_width, columns);
+
+    let code_top = PAGE_HEIGHT - MARGIN - HEADER_HEIGHT;
+    let rows_per_page = ((code_top - MARGIN) / LINE_HEIGHT) as usize;
+    let page_count = rows.len().div_ceil(rows_per_page);
+
+    let mut next = Ref::new(1);
+    let mut alloc = || next.bump();
+    let catalog = alloc();
+    let page_tree = alloc();
+    let info = alloc();
+    let font = alloc();
+    let cid_font = alloc();
+    let descriptor = alloc();
+    let to_unicode = alloc();
+    let font_file = alloc();
+    let pages = (0..page_count)
+        .map(|_| (alloc(), alloc()))
+        .collect::<Vec<_>>();
+
+    let mut pdf = Pdf::new();
+    pdf.catalog(catalog).pages(page_tree);
+    pdf.pages(page_tree)
+        .kids(pages.iter().map(|(page, _)| *page))
+        .count(page_count as i32);
+    pdf.document_info(info)
+        .title(TextStr(&options.title))
+        .producer(TextStr(concat!(
+            "PlanetB SyntaxHighlighter ",
+            env!("CARGO_PKG_VERSION")
+        )));
+
+    // every line has a row, so there is at least one chunk
+    let chunks = rows.chunks(rows_per_page);
+    for ((index, chunk), (page, content_id)) in chunks.enumerate().zip(&pages) {
+        let mut content = Content::new();
+
+        // the header, repeated on every page
+        let header_y = PAGE_HEIGHT - MARGIN - FONT_SIZE;
+        let gray = Color::rgb(
+            (HEADER_GRAY * 255.0) as u8,
+            (HEADER_GRAY * 255.0) as u8,
+            (HEADER_GRAY * 255.0) as u8,
+        );
+        let page_number = format!("{} / {}", index + 1, page_count);
+        let number_x = PAGE_WIDTH - MARGIN - page_number.chars().count() as f32 * advance;
+        // leaves room for the page number, cutting long titles off
+        let title_columns = ((number_x - MARGIN) / advance) as usize;
+        let title = options
+            .title
+            .chars()
+            .take(title_columns.saturating_sub(2))
+            .collect::<String>();
+        show(
+            &mut content,
+            &mut glyphs,
+            &title,
+            (MARGIN, header_y),
+            Style::default(),
+            gray,
+        );
+        show(
+            &mut content,
+            &mut glyphs,
+            &page_number,
+            (number_x, header_y),
+            Style::default(),
+            gray,
+        );
+        stroke(&mut content, gray);
+        content
+            .set_line_width(0.5)
+            .move_to(MARGIN, header_y - 6.0)
+            .line_to(PAGE_WIDTH - MARGIN, header_y - 6.0)
+            .stroke();
+
+        // the backgrounds of the gutter and the code
+        let height = chunk.len() as f32 * LINE_HEIGHT;
+        let bottom = code_top - height;
+        fill(&mut content, theme.gutter_background);
+        content
+            .rect(MARGIN, bottom, gutter_width, height)
+            .fill_nonzero();
+        for (row_index, row) in chunk.iter().enumerate() {
+            // like the other exports, starting with an `alt` line
+            let background = match row.line % 2 {
+                0 => theme.alt_background,
+                _ => theme.background,
+            };
+            fill(&mut content, background);
+            content
+                .rect(
+                    MARGIN + gutter_width,
+                    code_top - (row_index + 1) as f32 * LINE_HEIGHT,
+                    PAGE_WIDTH - 2.0 * MARGIN - gutter_width,
+                    LINE_HEIGHT,
+                )
+                .fill_nonzero();
+        }
+        if !chunk.is_empty() {
+            stroke(&mut content, theme.border);
+            content
+                .set_line_width(2.0)
+                .move_to(MARGIN + gutter_width, bottom)
+                .line_to(MARGIN + gutter_width, code_top)
+                .stroke();
+        }
+
+        for (row_index, row) in chunk.iter().enumerate() {
+            let y = code_top - (row_index + 1) as f32 * LINE_HEIGHT + LINE_HEIGHT * 0.25;
+            let marker = match (row.continuation, options.line_numbers) {
+                (true, _) => Some(CONTINUATION.to_string()),
+                (false, true) => Some((row.line + 1).to_string()),
+                (false, false) => None,
+            };
+            if let Some(marker) = marker {
+                let x = MARGIN + gutter_width - PADDING - marker.chars().count() as f32 * advance;
+                show(
+                    &mut content,
+                    &mut glyphs,
+                    &marker,
+                    (x, y),
+                    Style::default(),
+                    theme.gutter,
+                );
+            }
+
+            let mut x = code_x;
+            for (style, text) in &row.parts {
+                if !text.is_empty() {
+                    show(&mut content, &mut glyphs, text, (x, y), *style, theme.text);
+                    x += text.chars().count() as f32 * advance;
+                }
+            }
+        }
+
+        let mut page_writer = pdf.page(*page);
+        page_writer
+            .media_box(Rect::new(0.0, 0.0, PAGE_WIDTH, PAGE_HEIGHT))
+            .parent(page_tree)
+            .contents(*content_id);
+        page_writer.resources().fonts().pair(FONT_RESOURCE, font);
+        page_writer.finish();
+        pdf.stream(*content_id, &compress(&content.finish()))
+            .filter(Filter::FlateDecode);
+    }
+
+    // the font, subset to the glyphs drawn
+    let face = &glyphs.face;
+    let used = glyphs.used.keys().copied().collect::<Vec<_>>();
+    let font_data = subsetter::subset(FONT, 0, subsetter::Profile::pdf(&used))
+        .unwrap_or_else(|_| FONT.to_vec());
+
+    pdf.type0_font(font)
+        .base_font(FONT_NAME)
+        .encoding_predefined(Name(b"Identity-H"))
+        .descendant_font(cid_font)
+        .to_unicode(to_unicode);
+    pdf.cid_font(cid_font)
+        .subtype(CidFontType::Type2)
+        .base_font(FONT_NAME)
+        .system_info(SYSTEM_INFO)
+        .font_descriptor(descriptor)
+        .default_width(advance / FONT_SIZE * 1000.0)
+        .cid_to_gid_map_predefined(Name(b"Identity"));
+    let bbox = face.global_bounding_box();
+    pdf.font_descriptor(descriptor)
+        .name(FONT_NAME)
+        .flags(FontFlags::FIXED_PITCH | FontFlags::NON_SYMBOLIC)
+        .bbox(Rect::new(
+            glyphs.to_pdf(bbox.x_min),
+            glyphs.to_pdf(bbox.y_min),
+            glyphs.to_pdf(bbox.x_max),
+            glyphs.to_pdf(bbox.y_max),
+        ))
+        .italic_angle(0.0)
+        .ascent(glyphs.to_pdf(face.ascender()))
+        .descent(glyphs.to_pdf(face.descender()))
+        .cap_height(glyphs.to_pdf(face.capital_height().unwrap_or(face.ascender())))
+        .stem_v(80.0)
+        .font_file2(font_file);
+
+    let mut cmap = pdf_writer::types::UnicodeCmap::new(Name(b"Custom"), SYSTEM_INFO);
+    for (glyph, c) in &glyphs.used {
+        cmap.pair(*glyph, *c);
+    }
+    pdf.cmap(to_unicode, &cmap.finish());
+    pdf.stream(font_file, &compress(&font_data))
+        .filter(Filter::FlateDecode)
+        .pair(Name(b"Length1"), font_data.len() as i32);
+
+    pdf.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::highlighter::TokenKind;
+
+    fn pdf(code: &str, tokens: &[Token]) -> Vec<u8> {
+        render(code, tokens, &Options::default(), &Theme::default())
+    }
+
+    fn count(pdf: &[u8], needle: &str) -> usize {
+        pdf.windows(needle.len())
+            .filter(|window| *window == needle.as_bytes())
+            .count()
+    }
+
+    /// The streams of a document, inflated where they are deflated.
+    fn streams(pdf: &[u8]) -> Vec<Vec<u8>> {
+        let find = |haystack: &[u8], needle: &[u8]| {
+            haystack
+                .windows(needle.len())
+                .position(|window| window == needle)
+        };
+        let mut streams = Vec::new();
+        let mut rest = pdf;
+        while let Some(start) = find(rest, b"/Length ") {
+            rest = &rest[start + 8..];
+            let digits = rest.iter().take_while(|b| b.is_ascii_digit()).count();
+            let length = std::str::from_utf8(&rest[..digits])
+                .unwrap()
+                .parse::<usize>()
+                .unwrap();
+            rest = &rest[find(rest, b"stream\n").unwrap() + 7..];
+            let data = &rest[..length];
+            streams
+                .push(miniz_oxide::inflate::decompress_to_vec_zlib(data).unwrap_or(data.to_vec()));
+            rest = &rest[length..];
+        }
+        streams
+    }
+
+    #[test]
+    fn documents_are_pdf() {
+        let pdf = pdf("fn main() {}", &[Token::new(TokenKind::Keyword, 0..2)]);
+        assert!(pdf.starts_with(b"%PDF-"));
+        assert!(pdf.ends_with(b"%%EOF") || pdf.ends_with(b"%%EOF\n"));
+        assert_eq!(count(&pdf, "/Type /Page\n"), 1);
+        assert_eq!(count(&pdf, "/Count 1\n"), 1);
+    }
+
+    #[test]
+    fn pages_follow_the_lines() {
+        let code_top = PAGE_HEIGHT - MARGIN - HEADER_HEIGHT;
+        let rows_per_page = ((code_top - MARGIN) / LINE_HEIGHT) as usize;
+        let lines = |count: usize| vec!["x"; count].join("\n");
+
+        let full = pdf(&lines(rows_per_page), &[]);
+        assert_eq!(count(&full, "/Type /Page\n"), 1);
+        let over = pdf(&lines(rows_per_page + 1), &[]);
+        assert_eq!(count(&over, "/Type /Page\n"), 2);
+        assert_eq!(count(&over, "/Count 2\n"), 1);
+        let many = pdf(&lines(2 * rows_per_page + 1), &[]);
+        assert_eq!(count(&many, "/Count 3\n"), 1);
+    }
+
+    #[test]
+    fn long_lines_wrap() {
+        let code = format!("{}\nshort", "a".repeat(25));
+        let rows = layout(&code, &[], &Theme::default(), 4, 10);
+        let rows = rows
+            .iter()
+            .map(|row| {
+                let text = row.parts.iter().map(|(_, text)| text.as_str()).collect();
+                (row.line, row.continuation, text)
+            })
+            .collect::<Vec<(usize, bool, String)>>();
+        assert_eq!(
+            rows,
+            [
+                (0, false, "a".repeat(10)),
+                (0, true, "a".repeat(10)),
+                (0, true, "a".repeat(5)),
+                (1, false, "short".to_string()),
+            ]
+        );
+
+        // tabs expand before wrapping, keeping the tokens' styles
+        let theme = Theme::default();
+        let rows = layout(
+            "a\tbc",
+            &[Token::new(TokenKind::Keyword, 2..4)],
+            &theme,
+            4,
+            5,
+        );
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].parts[0], (Style::default(), "a   ".to_string()));
+        assert_eq!(
+            rows[0].parts[1],
+            (theme.style(TokenKind::Keyword), "b".to_string())
+        );
+        assert_eq!(
+            rows[1].parts[0],
+            (theme.style(TokenKind::Keyword), "c".to_string())
+        );
+
+        // the wrapped rows of a long line all make it into the document
+        let code = "x".repeat(1000);
+        assert_eq!(count(&pdf(&code, &[]), "/Type /Page\n"), 1);
+        let drawn = streams(&pdf(&code, &[]))
+            .iter()
+            .map(|stream| count(stream, " Tj"))
+            .sum::<usize>();
+        assert!(drawn > 10, "{drawn} texts shown");
+    }
+
+    #[test]
+    fn non_ascii_survives_subsetting() {
+        let pdf = pdf("let π = \"héllo — 世界\";", &[]);
+        let streams = streams(&pdf);
+        let font = streams
+            .iter()
+            .find_map(|stream| ttf_parser::Face::parse(stream, 0).ok())
+            .expect("the font is embedded");
+
+        for c in ['π', 'é', '—', 'l'] {
+            let glyph = font.glyph_index(c).unwrap();
+            assert!(font.glyph_bounding_box(glyph).is_some(), "{c} is kept");
+        }
+        // glyphs not drawn are left out
+        let unused = font.glyph_index('Z').unwrap();
+        assert!(font.glyph_bounding_box(unused).is_none());
+
+        // and copying the text maps the glyphs back to the characters
+        let cmap = streams
+            .iter()
+            .find(|stream| count(stream, "beginbfchar") > 0)
+            .unwrap();
+        let cmap = String::from_utf8_lossy(cmap);
+        let face = ttf_parser::Face::parse(FONT, 0).unwrap();
+        let glyph = face.glyph_index('π').unwrap().0;
+        assert!(cmap.contains(&format!("<{glyph:04X}> <03C0>")), "{cmap}");
+    }
+}
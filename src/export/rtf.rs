@@ -0,0 +1,161 @@
+use crate::highlighter::{lines, Token};
+use crate::theme::{Color, Theme, FONT_SIZE_PT};
+
+/// Escapes `text` for use inside an RTF group, non ASCII characters becoming `\uN?` escapes.
+fn escape(text: &str, rtf: &mut String) {
+    for c in text.chars() {
+        match c {
+            '\\' | '{' | '}' => {
+                rtf.push('\\');
+                rtf.push(c);
+            }
+            '\t' => rtf.push_str("\\tab "),
+            c if c.is_ascii() => rtf.push(c),
+            c => {
+                let mut units = [0; 2];
+                for unit in c.encode_utf16(&mut units) {
+                    rtf.push_str(&format!("\\u{}?", *unit as i16));
+                }
+            }
+        }
+    }
+}
+
+/// Indices of the colors used, RTF refers to them through a table in the header.
+struct ColorTable(Vec<Color>);
+
+impl ColorTable {
+    fn index(&mut self, color: Color) -> usize {
+        // index 0 is the "auto" color, so the table starts at 1
+        match self.0.iter().position(|c| *c == color) {
+            Some(index) => index + 1,
+            None => {
+                self.0.push(color);
+                self.0.len()
+            }
+        }
+    }
+}
+
+/// Renders an RTF document in the monospace font of the preview.
+pub fn render(code: &str, tokens: &[Token], theme: &Theme) -> String {
+    let mut colors = ColorTable(Vec::new());
+    let text = colors.index(theme.text);
+    let background = colors.index(theme.background);
+    let mut body = String::new();
+
+    for (index, line) in lines(code, tokens).into_iter().enumerate() {
+        if index > 0 {
+            body.push_str("\\par\n");
+        }
+        for (kind, part) in line {
+            let Some(kind) = kind else {
+                escape(part, &mut body);
+                continue;
+            };
+
+            let style = theme.style(kind);
+            body.push('{');
+            if let Some(color) = style.color {
+                body.push_str(&format!("\\cf{}", colors.index(color)));
+            }
+            if let Some(color) = style.background {
+                body.push_str(&format!("\\chcbpat{}", colors.index(color)));
+            }
+            if style.bold {
+                body.push_str("\\b");
+            }
+            if style.italic {
+                body.push_str("\\i");
+            }
+            body.push(' ');
+            escape(part, &mut body);
+            body.push('}');
+        }
+    }
+
+    let color_table = colors
+        .0
+        .iter()
+        .map(|c| format!("\\red{}\\green{}\\blue{};", c.r, c.g, c.b))
+        .collect::<String>();
+
+    format!(
+        "{{\\rtf1\\ansi\\deff0{{\\fonttbl{{\\f0\\fmodern Consolas;}}}}{{\\colortbl;{color_table}}}\n\\f0\\fs{size}\\cf{text}\\chshdng0\\chcbpat{background} {body}}}",
+        size = (FONT_SIZE_PT * 2.0).round() as u32,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::highlighter::TokenKind;
+
+    fn escaped(text: &str) -> String {
+        let mut rtf = String::new();
+        escape(text, &mut rtf);
+        rtf
+    }
+
+    #[test]
+    fn non_ascii_becomes_unicode_escapes() {
+        assert_eq!(escaped("é"), "\\u233?");
+        assert_eq!(escaped("a→b"), "a\\u8594?b");
+        // RTF takes signed 16 bit values, wrapping above U+7FFF
+        assert_eq!(escaped("\u{8000}"), "\\u-32768?");
+        assert_eq!(escaped("\u{ff01}"), "\\u-255?");
+        // and a surrogate pair outside the basic plane
+        assert_eq!(escaped("😀"), "\\u-10179?\\u-8704?");
+    }
+
+    #[test]
+    fn control_words_are_escaped() {
+        assert_eq!(escaped(r"\par {x}"), r"\\par \{x\}");
+        assert_eq!(escaped("\ta"), "\\tab a");
+
+        let rtf = render(
+            "s = \"{\\\\}\"",
+            &[Token::new(TokenKind::String, 4..10)],
+            &Theme::default(),
+        );
+        assert!(rtf.contains(r#" "\{\\\\\}"}"#), "{rtf}");
+        // the braces of the document stay balanced
+        let unescaped = rtf.replace(r"\\", "").replace(r"\{", "").replace(r"\}", "");
+        assert_eq!(
+            unescaped.matches('{').count(),
+            unescaped.matches('}').count()
+        );
+    }
+
+    #[test]
+    fn colors_are_indexed_from_one() {
+        let mut colors = ColorTable(Vec::new());
+        let red = Color::rgb(255, 0, 0);
+        let blue = Color::rgb(0, 0, 255);
+        assert_eq!(colors.index(red), 1);
+        assert_eq!(colors.index(blue), 2);
+        assert_eq!(colors.index(red), 1);
+        assert_eq!(colors.0, [red, blue]);
+
+        let theme = Theme::default();
+        let keyword = theme.style(TokenKind::Keyword).color.unwrap();
+        let rtf = render(
+            "if if\nif",
+            &[
+                Token::new(TokenKind::Keyword, 0..2),
+                Token::new(TokenKind::Keyword, 3..5),
+                Token::new(TokenKind::Keyword, 6..8),
+            ],
+            &theme,
+        );
+        let table = [theme.text, theme.background, keyword]
+            .iter()
+            .map(|c| format!("\\red{}\\green{}\\blue{};", c.r, c.g, c.b))
+            .collect::<String>();
+        assert!(rtf.contains(&format!("{{\\colortbl;{table}}}")), "{rtf}");
+        assert!(rtf.contains("\\f0\\fs"));
+        assert!(rtf.contains("\\cf1\\chshdng0\\chcbpat2 "));
+        assert_eq!(rtf.matches("{\\cf3\\b if}").count(), 3);
+        assert!(rtf.contains("{\\cf3\\b if}\\par\n{\\cf3\\b if}}"));
+    }
+}
@@ -0,0 +1,200 @@
+use crate::highlighter::scanner::{contains, contains_ignore_case, is_word_char, Scanner};
+use crate::highlighter::{Brush, LineState, Token, TokenKind};
+
+const BLOCK_COMMENT: u32 = 1;
+
+/// Brush for the languages shCore highlighted with `//` and `/* */` comments plus keyword lists.
+pub(crate) struct CLike {
+    pub keywords: &'static [&'static str],
+    pub datatypes: &'static [&'static str],
+    /// Functions, matched ignoring case.
+    pub functions: &'static [&'static str],
+    /// Lines starting with `#` are preprocessor directives.
+    pub preprocessor: bool,
+    pub numbers: bool,
+    /// `@name` annotations.
+    pub annotations: bool,
+    /// `$name` variables.
+    pub variables: bool,
+    /// `#` starts a comment as well.
+    pub hash_comments: bool,
+}
+
+impl CLike {
+    pub const fn new(keywords: &'static [&'static str]) -> Self {
+        CLike {
+            keywords,
+            datatypes: &[],
+            functions: &[],
+            preprocessor: false,
+            numbers: false,
+            annotations: false,
+            variables: false,
+            hash_comments: false,
+        }
+    }
+}
+
+impl Brush for CLike {
+    fn tokenize_line(&self, line: &str, state: &mut LineState, tokens: &mut Vec<Token>) {
+        let mut s = Scanner::new(line, tokens);
+
+        if state.mode == BLOCK_COMMENT {
+            let closed = s.eat_until("*/");
+            s.token(TokenKind::Comment, 0);
+            if !closed {
+                return;
+            }
+            state.mode = 0;
+        }
+
+        while !s.is_done() {
+            let start = s.pos();
+
+            if self.preprocessor && s.at_line_start() && s.peek() == Some('#') {
+                s.eat_to_end();
+                s.token(TokenKind::Preprocessor, start);
+            } else if s.eat("//") || (self.hash_comments && s.eat_char('#')) {
+                s.eat_to_end();
+                s.token(TokenKind::Comment, start);
+            } else if s.eat("/*") {
+                if !s.eat_until("*/") {
+                    state.mode = BLOCK_COMMENT;
+                }
+                s.token(TokenKind::Comment, start);
+            } else if let Some(quote) = s.peek().filter(|c| *c == '"' || *c == '\'') {
+                s.bump();
+                s.eat_quoted(quote, Some('\\'));
+                s.token(TokenKind::String, start);
+            } else if self.variables
+                && s.peek() == Some('$')
+                && s.peek_nth(1).is_some_and(is_word_char)
+            {
+                s.bump();
+                s.eat_word();
+                s.token(TokenKind::Variable, start);
+            } else if self.annotations
+                && s.peek() == Some('@')
+                && s.peek_nth(1).is_some_and(is_word_char)
+            {
+                s.bump();
+                s.eat_while(|c| is_word_char(c) || c == '$');
+                if s.slice(start) == "@interface" {
+                    s.token(TokenKind::Keyword, start);
+                } else {
+                    s.token(TokenKind::Annotation, start);
+                }
+            } else if self.numbers && s.eat_number() {
+                s.token(TokenKind::Number, start);
+            } else if s.at_word_boundary() && s.eat_word() {
+                let word = s.slice(start);
+                if contains(self.datatypes, word) {
+                    s.token(TokenKind::DataType, start);
+                } else if contains_ignore_case(self.functions, word) {
+                    s.token(TokenKind::Function, start);
+                } else if contains(self.keywords, word) {
+                    s.token(TokenKind::Keyword, start);
+                }
+            } else {
+                s.bump();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::cpp::CPP;
+    use super::super::csharp::CSHARP;
+    use super::super::java::JAVA;
+    use super::super::php::PHP;
+    use super::*;
+    use crate::highlighter::testing::{assert_token, highlight};
+
+    #[test]
+    fn block_comments_span_lines() {
+        assert_eq!(
+            highlight(&CPP, "int a; /* one\ntwo\nthree */ int b;"),
+            [
+                (TokenKind::DataType, "int"),
+                (TokenKind::Comment, "/* one"),
+                (TokenKind::Comment, "two"),
+                (TokenKind::Comment, "three */"),
+                (TokenKind::DataType, "int"),
+            ]
+        );
+    }
+
+    #[test]
+    fn preprocessor_lines_only_at_the_start() {
+        assert_token(
+            &CPP,
+            "#include <vector>",
+            "#include <vector>",
+            TokenKind::Preprocessor,
+        );
+        assert_token(
+            &CSHARP,
+            "  #region Name",
+            "#region Name",
+            TokenKind::Preprocessor,
+        );
+        assert_eq!(highlight(&CPP, "x = a # b;"), []);
+    }
+
+    #[test]
+    fn strings_with_escaped_quotes() {
+        assert_eq!(
+            highlight(&CPP, r#"s = "a \" b" + 'c';"#),
+            [
+                (TokenKind::String, r#""a \" b""#),
+                (TokenKind::String, "'c'"),
+            ]
+        );
+        // an unclosed string ends with its line
+        assert_eq!(
+            highlight(&CPP, "s = \"open\nint"),
+            [(TokenKind::String, "\"open"), (TokenKind::DataType, "int")]
+        );
+    }
+
+    #[test]
+    fn keywords_and_datatypes_are_whole_words() {
+        assert_eq!(
+            highlight(&CPP, "DWORD integer = int_max;"),
+            [(TokenKind::DataType, "DWORD")]
+        );
+    }
+
+    #[test]
+    fn java_annotations_and_numbers() {
+        assert_eq!(
+            highlight(&JAVA, "@Override public int x = 42;"),
+            [
+                (TokenKind::Annotation, "@Override"),
+                (TokenKind::Keyword, "public"),
+                (TokenKind::Keyword, "int"),
+                (TokenKind::Number, "42"),
+            ]
+        );
+        assert_token(
+            &JAVA,
+            "public @interface Marker {}",
+            "@interface",
+            TokenKind::Keyword,
+        );
+    }
+
+    #[test]
+    fn php_variables_functions_and_hash_comments() {
+        assert_eq!(
+            highlight(&PHP, "$n = StrLen($text); # length"),
+            [
+                (TokenKind::Variable, "$n"),
+                (TokenKind::Function, "StrLen"),
+                (TokenKind::Variable, "$text"),
+                (TokenKind::Comment, "# length"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,107 @@
+//! Terminal sessions, commands after a prompt mixed with their output.
+//!
+//! Lines starting with a `$ ` or `# ` prompt are Bash commands, those starting with `PS> ` or
+//! `PS C:\path> ` PowerShell commands. Every other line is output and stays plain, unless the
+//! command before it continues on it.
+
+use crate::highlighter::{Brush, LineState, Token, TokenKind};
+
+use super::powershell::PowerShell;
+use super::shell::Shell;
+
+/// Set on the mode when the command is PowerShell, the bits below belonging to its brush.
+const POWERSHELL: u32 = 1 << 31;
+/// Set on the mode when the command continues on the next line after a `\` or `` ` ``.
+const CONTINUED: u32 = 1 << 30;
+
+pub(crate) struct Console;
+
+/// Length of the prompt `line` starts with and whether it is PowerShell's.
+fn prompt(line: &str) -> Option<(usize, bool)> {
+    if line.starts_with("PS>") || line.starts_with("PS ") {
+        let end = line.find("> ")?;
+        return Some((end + 2, true));
+    }
+    // like `$ `, or with the user, host and directory before it, like `me@box:~/src$ `
+    let end = line.find(['$', '#'])?;
+    let before = &line[..end];
+    if before.contains(char::is_whitespace) || !line[end + 1..].starts_with(' ') {
+        return None;
+    }
+    Some((end + 2, false))
+}
+
+impl Brush for Console {
+    fn tokenize_line(&self, line: &str, state: &mut LineState, tokens: &mut Vec<Token>) {
+        let powershell = state.mode & POWERSHELL != 0;
+        let continued = state.mode & CONTINUED != 0;
+        state.mode &= !(POWERSHELL | CONTINUED);
+
+        // a command also goes on while inside a string, heredoc or substitution
+        let (offset, powershell) = if continued || state.mode != 0 || !state.stack.is_empty() {
+            (0, powershell)
+        } else if let Some((length, powershell)) = prompt(line) {
+            tokens.push(Token::new(TokenKind::Preprocessor, 0..length));
+            (length, powershell)
+        } else {
+            *state = LineState::default();
+            return;
+        };
+
+        let command = &line[offset..];
+        let mut command_tokens = Vec::new();
+        if powershell {
+            PowerShell.tokenize_line(command, state, &mut command_tokens);
+        } else {
+            Shell.tokenize_line(command, state, &mut command_tokens);
+        }
+        tokens.extend(command_tokens.into_iter().map(|token| {
+            Token::new(
+                token.kind,
+                token.range.start + offset..token.range.end + offset,
+            )
+        }));
+
+        let continuation = if powershell { '`' } else { '\\' };
+        if command.ends_with(continuation) {
+            state.mode |= CONTINUED;
+        }
+        if powershell {
+            state.mode |= POWERSHELL;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::highlighter::testing::highlight;
+
+    #[test]
+    fn prompts_start_commands() {
+        assert_eq!(prompt("$ ls"), Some((2, false)));
+        assert_eq!(prompt("me@box:~/src$ ls"), Some((14, false)));
+        assert_eq!(prompt("# ls"), Some((2, false)));
+        assert_eq!(prompt("PS C:\\> dir"), Some((8, true)));
+        assert_eq!(prompt("total 0 $ x"), None);
+        assert_eq!(prompt("$HOME"), None);
+    }
+
+    #[test]
+    fn output_stays_plain() {
+        assert_eq!(
+            highlight(
+                &Console,
+                "$ echo 'a\nb' \\\n  | cat\nif echo\nPS> echo `\n  $x"
+            ),
+            [
+                (TokenKind::Preprocessor, "$ "),
+                (TokenKind::Builtin, "echo"),
+                (TokenKind::String, "'a"),
+                (TokenKind::String, "b'"),
+                (TokenKind::Preprocessor, "PS> "),
+                (TokenKind::Variable, "$x"),
+            ]
+        );
+    }
+}
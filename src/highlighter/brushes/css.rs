@@ -0,0 +1,486 @@
+use crate::highlighter::scanner::{contains, contains_ignore_case, is_word_char, Scanner};
+use crate::highlighter::{Brush, LineState, Token, TokenKind};
+
+const BLOCK_COMMENT: u32 = 1;
+
+const FONTS: &[&str] = &[
+    "arial",
+    "helvetica",
+    "monospace",
+    "sans-serif",
+    "serif",
+    "tahoma",
+    "verdana",
+];
+
+const KEYWORDS: &[&str] = &[
+    "ascent",
+    "azimuth",
+    "background",
+    "background-attachment",
+    "background-color",
+    "background-image",
+    "background-position",
+    "background-repeat",
+    "baseline",
+    "bbox",
+    "border",
+    "border-bottom",
+    "border-bottom-color",
+    "border-bottom-style",
+    "border-bottom-width",
+    "border-collapse",
+    "border-color",
+    "border-left",
+    "border-left-color",
+    "border-left-style",
+    "border-left-width",
+    "border-right",
+    "border-right-color",
+    "border-right-style",
+    "border-right-width",
+    "border-spacing",
+    "border-style",
+    "border-top",
+    "border-top-color",
+    "border-top-style",
+    "border-top-width",
+    "border-width",
+    "cap-height",
+    "caption-side",
+    "centerline",
+    "clear",
+    "clip",
+    "color",
+    "content",
+    "counter-increment",
+    "counter-reset",
+    "cue",
+    "cue-after",
+    "cue-before",
+    "cursor",
+    "definition-src",
+    "descent",
+    "direction",
+    "display",
+    "elevation",
+    "empty-cells",
+    "float",
+    "font",
+    "font-family",
+    "font-size",
+    "font-size-adjust",
+    "font-stretch",
+    "font-style",
+    "font-variant",
+    "font-weight",
+    "height",
+    "letter-spacing",
+    "line-height",
+    "list-style",
+    "list-style-image",
+    "list-style-position",
+    "list-style-type",
+    "margin",
+    "margin-bottom",
+    "margin-left",
+    "margin-right",
+    "margin-top",
+    "marker-offset",
+    "marks",
+    "mathline",
+    "max-height",
+    "max-width",
+    "min-height",
+    "min-width",
+    "orphans",
+    "outline",
+    "outline-color",
+    "outline-style",
+    "outline-width",
+    "overflow",
+    "padding",
+    "padding-bottom",
+    "padding-left",
+    "padding-right",
+    "padding-top",
+    "page",
+    "page-break-after",
+    "page-break-before",
+    "page-break-inside",
+    "pause",
+    "pause-after",
+    "pause-before",
+    "pitch",
+    "pitch-range",
+    "play-during",
+    "position",
+    "quotes",
+    "richness",
+    "size",
+    "slope",
+    "speak",
+    "speak-header",
+    "speak-numeral",
+    "speak-punctuation",
+    "speech-rate",
+    "src",
+    "stemh",
+    "stemv",
+    "stress",
+    "table-layout",
+    "text-align",
+    "text-decoration",
+    "text-indent",
+    "text-shadow",
+    "text-transform",
+    "unicode-bidi",
+    "unicode-range",
+    "units-per-em",
+    "vertical-align",
+    "visibility",
+    "voice-family",
+    "volume",
+    "white-space",
+    "widows",
+    "width",
+    "widths",
+    "word-spacing",
+    "x-height",
+    "z-index",
+];
+
+const VALUES: &[&str] = &[
+    "above",
+    "absolute",
+    "all",
+    "always",
+    "aqua",
+    "armenian",
+    "attr",
+    "aural",
+    "auto",
+    "avoid",
+    "baseline",
+    "behind",
+    "below",
+    "bidi-override",
+    "black",
+    "blink",
+    "block",
+    "blue",
+    "bold",
+    "bolder",
+    "both",
+    "bottom",
+    "braille",
+    "capitalize",
+    "caption",
+    "center",
+    "center-left",
+    "center-right",
+    "circle",
+    "close-quote",
+    "code",
+    "collapse",
+    "compact",
+    "condensed",
+    "continuous",
+    "counter",
+    "counters",
+    "crop",
+    "cross",
+    "crosshair",
+    "cursive",
+    "dashed",
+    "decimal",
+    "decimal-leading-zero",
+    "default",
+    "digits",
+    "disc",
+    "dotted",
+    "double",
+    "e-resize",
+    "embed",
+    "embossed",
+    "expanded",
+    "extra-condensed",
+    "extra-expanded",
+    "fantasy",
+    "far-left",
+    "far-right",
+    "fast",
+    "faster",
+    "fixed",
+    "format",
+    "fuchsia",
+    "gray",
+    "green",
+    "groove",
+    "handheld",
+    "hebrew",
+    "help",
+    "hidden",
+    "hide",
+    "high",
+    "higher",
+    "icon",
+    "inline",
+    "inline-table",
+    "inset",
+    "inside",
+    "invert",
+    "italic",
+    "justify",
+    "landscape",
+    "large",
+    "larger",
+    "left",
+    "left-side",
+    "leftwards",
+    "level",
+    "lighter",
+    "lime",
+    "line-through",
+    "list-item",
+    "local",
+    "loud",
+    "low",
+    "lower",
+    "lower-alpha",
+    "lower-greek",
+    "lower-latin",
+    "lower-roman",
+    "lowercase",
+    "ltr",
+    "marker",
+    "maroon",
+    "medium",
+    "message-box",
+    "middle",
+    "mix",
+    "move",
+    "n-resize",
+    "narrower",
+    "navy",
+    "ne-resize",
+    "no-close-quote",
+    "no-open-quote",
+    "no-repeat",
+    "none",
+    "normal",
+    "nowrap",
+    "nw-resize",
+    "oblique",
+    "olive",
+    "once",
+    "open-quote",
+    "outset",
+    "outside",
+    "overline",
+    "pointer",
+    "portrait",
+    "pre",
+    "print",
+    "projection",
+    "purple",
+    "red",
+    "relative",
+    "repeat",
+    "repeat-x",
+    "repeat-y",
+    "rgb",
+    "ridge",
+    "right",
+    "right-side",
+    "rightwards",
+    "rtl",
+    "run-in",
+    "s-resize",
+    "screen",
+    "scroll",
+    "se-resize",
+    "semi-condensed",
+    "semi-expanded",
+    "separate",
+    "show",
+    "silent",
+    "silver",
+    "slow",
+    "slower",
+    "small",
+    "small-caps",
+    "small-caption",
+    "smaller",
+    "soft",
+    "solid",
+    "speech",
+    "spell-out",
+    "square",
+    "static",
+    "status-bar",
+    "sub",
+    "super",
+    "sw-resize",
+    "table-caption",
+    "table-cell",
+    "table-column",
+    "table-column-group",
+    "table-footer-group",
+    "table-header-group",
+    "table-row",
+    "table-row-group",
+    "teal",
+    "text-bottom",
+    "text-top",
+    "thick",
+    "thin",
+    "top",
+    "transparent",
+    "tty",
+    "tv",
+    "ultra-condensed",
+    "ultra-expanded",
+    "underline",
+    "upper-alpha",
+    "upper-latin",
+    "upper-roman",
+    "uppercase",
+    "url",
+    "visible",
+    "w-resize",
+    "wait",
+    "white",
+    "wider",
+    "x-fast",
+    "x-high",
+    "x-large",
+    "x-loud",
+    "x-low",
+    "x-slow",
+    "x-small",
+    "x-soft",
+    "xx-large",
+    "xx-small",
+    "yellow",
+];
+
+fn is_css_word_char(c: char) -> bool {
+    is_word_char(c) || c == '-'
+}
+
+pub(crate) struct Css;
+
+impl Brush for Css {
+    fn tokenize_line(&self, line: &str, state: &mut LineState, tokens: &mut Vec<Token>) {
+        let mut s = Scanner::new(line, tokens);
+
+        if state.mode == BLOCK_COMMENT {
+            let closed = s.eat_until("*/");
+            s.token(TokenKind::Comment, 0);
+            if !closed {
+                return;
+            }
+            state.mode = 0;
+        }
+
+        while !s.is_done() {
+            let start = s.pos();
+            let at_boundary = !s.prev().is_some_and(is_css_word_char);
+
+            if s.eat("/*") {
+                if !s.eat_until("*/") {
+                    state.mode = BLOCK_COMMENT;
+                }
+                s.token(TokenKind::Comment, start);
+            } else if let Some(quote) = s.peek().filter(|c| *c == '"' || *c == '\'') {
+                s.bump();
+                s.eat_quoted(quote, Some('\\'));
+                s.token(TokenKind::String, start);
+            } else if s.eat("!important") {
+                s.token(TokenKind::Important, start);
+            } else if s.peek() == Some('#') && s.peek_nth(1).is_some_and(|c| c.is_ascii_hexdigit())
+            {
+                s.bump();
+                s.eat_while(is_word_char);
+                let digits = s.slice(start + 1);
+                if matches!(digits.len(), 3 | 4 | 6 | 8)
+                    && digits.chars().all(|c| c.is_ascii_hexdigit())
+                {
+                    s.token(TokenKind::Value, start);
+                }
+            } else if at_boundary
+                && (s.peek().is_some_and(|c| c.is_ascii_digit())
+                    || (s.peek() == Some('-') && s.peek_nth(1).is_some_and(|c| c.is_ascii_digit())))
+            {
+                // sizes like `-1.5em` or `100%`
+                s.eat_char('-');
+                s.eat_while(|c| c.is_ascii_digit() || c == '.');
+                s.eat_while(|c| c.is_ascii_alphabetic() || c == '%');
+                s.token(TokenKind::Value, start);
+            } else if at_boundary && s.eat_while(is_css_word_char) {
+                let word = s.slice(start);
+                let is_property = s.rest().trim_start().starts_with(':');
+                if is_property && contains(KEYWORDS, word.trim_start_matches('_')) {
+                    s.token(TokenKind::Keyword, start);
+                } else if !is_property
+                    && (contains(VALUES, word) || contains_ignore_case(FONTS, word))
+                {
+                    s.token(TokenKind::Value, start);
+                }
+            } else {
+                s.bump();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::highlighter::testing::{assert_token, highlight};
+
+    #[test]
+    fn declarations() {
+        assert_eq!(
+            highlight(
+                &Css,
+                "p { color: #ff0000 !important; margin: -1.5em 100%; }"
+            ),
+            [
+                (TokenKind::Keyword, "color"),
+                (TokenKind::Value, "#ff0000"),
+                (TokenKind::Important, "!important"),
+                (TokenKind::Keyword, "margin"),
+                (TokenKind::Value, "-1.5em"),
+                (TokenKind::Value, "100%"),
+            ]
+        );
+    }
+
+    #[test]
+    fn values_and_fonts_only_after_the_colon() {
+        assert_token(
+            &Css,
+            "a { font-family: Verdana, serif; }",
+            "Verdana",
+            TokenKind::Value,
+        );
+        assert_token(&Css, "a { display: none; }", "none", TokenKind::Value);
+        // only hexadecimal words of a color's length are colors
+        assert_eq!(highlight(&Css, "#cafe00x, #abcde {}"), []);
+    }
+
+    #[test]
+    fn comments_span_lines() {
+        assert_eq!(
+            highlight(&Css, "/* a\nb */ margin: 0"),
+            [
+                (TokenKind::Comment, "/* a"),
+                (TokenKind::Comment, "b */"),
+                (TokenKind::Keyword, "margin"),
+                (TokenKind::Value, "0"),
+            ]
+        );
+    }
+}
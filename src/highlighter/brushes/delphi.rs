@@ -0,0 +1,212 @@
+use crate::highlighter::scanner::{contains_ignore_case, Scanner};
+use crate::highlighter::{Brush, LineState, Token, TokenKind};
+
+const PAREN_COMMENT: u32 = 1;
+const BRACE_COMMENT: u32 = 2;
+
+const KEYWORDS: &[&str] = &[
+    "abs",
+    "addr",
+    "and",
+    "ansichar",
+    "ansistring",
+    "array",
+    "as",
+    "asm",
+    "begin",
+    "boolean",
+    "byte",
+    "cardinal",
+    "case",
+    "char",
+    "class",
+    "comp",
+    "const",
+    "constructor",
+    "currency",
+    "destructor",
+    "div",
+    "do",
+    "double",
+    "downto",
+    "else",
+    "end",
+    "except",
+    "exports",
+    "extended",
+    "false",
+    "file",
+    "finalization",
+    "finally",
+    "for",
+    "function",
+    "goto",
+    "if",
+    "implementation",
+    "in",
+    "inherited",
+    "initialization",
+    "int64",
+    "integer",
+    "interface",
+    "is",
+    "label",
+    "library",
+    "longint",
+    "longword",
+    "mod",
+    "nil",
+    "not",
+    "object",
+    "of",
+    "on",
+    "or",
+    "packed",
+    "pansichar",
+    "pansistring",
+    "pchar",
+    "pcurrency",
+    "pdatetime",
+    "pextended",
+    "pint64",
+    "pointer",
+    "private",
+    "procedure",
+    "program",
+    "property",
+    "protected",
+    "pshortstring",
+    "pstring",
+    "public",
+    "published",
+    "pvariant",
+    "pwidechar",
+    "pwidestring",
+    "raise",
+    "real",
+    "real48",
+    "record",
+    "repeat",
+    "set",
+    "shl",
+    "shortint",
+    "shortstring",
+    "shr",
+    "single",
+    "smallint",
+    "string",
+    "then",
+    "threadvar",
+    "to",
+    "true",
+    "try",
+    "type",
+    "unit",
+    "until",
+    "uses",
+    "val",
+    "var",
+    "varirnt",
+    "while",
+    "widechar",
+    "widestring",
+    "with",
+    "word",
+    "write",
+    "writeln",
+    "xor",
+];
+
+pub(crate) struct Delphi;
+
+impl Brush for Delphi {
+    fn tokenize_line(&self, line: &str, state: &mut LineState, tokens: &mut Vec<Token>) {
+        let mut s = Scanner::new(line, tokens);
+
+        if state.mode != 0 {
+            let closed = s.eat_until(if state.mode == PAREN_COMMENT {
+                "*)"
+            } else {
+                "}"
+            });
+            s.token(TokenKind::Comment, 0);
+            if !closed {
+                return;
+            }
+            state.mode = 0;
+        }
+
+        while !s.is_done() {
+            let start = s.pos();
+
+            if s.eat("{$") {
+                s.eat_until("}");
+                s.token(TokenKind::Directive, start);
+            } else if s.eat("(*") {
+                if !s.eat_until("*)") {
+                    state.mode = PAREN_COMMENT;
+                }
+                s.token(TokenKind::Comment, start);
+            } else if s.eat_char('{') {
+                if !s.eat_until("}") {
+                    state.mode = BRACE_COMMENT;
+                }
+                s.token(TokenKind::Comment, start);
+            } else if s.eat("//") {
+                s.eat_to_end();
+                s.token(TokenKind::Comment, start);
+            } else if s.eat_char('\'') {
+                // quotes are escaped by doubling them, which scans as two adjacent strings
+                s.eat_quoted('\'', None);
+                s.token(TokenKind::String, start);
+            } else if s.peek() == Some('$') && s.peek_nth(1).is_some_and(|c| c.is_ascii_hexdigit())
+            {
+                s.bump();
+                s.eat_while(|c| c.is_ascii_hexdigit());
+                s.token(TokenKind::Number, start);
+            } else if s.eat_number() {
+                s.token(TokenKind::Number, start);
+            } else if s.at_word_boundary() && s.eat_word() {
+                if contains_ignore_case(KEYWORDS, s.slice(start)) {
+                    s.token(TokenKind::Keyword, start);
+                }
+            } else {
+                s.bump();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::highlighter::testing::highlight;
+
+    #[test]
+    fn comments_of_both_kinds_span_lines() {
+        assert_eq!(
+            highlight(&Delphi, "{ one\ntwo } (* three\nfour *) BEGIN"),
+            [
+                (TokenKind::Comment, "{ one"),
+                (TokenKind::Comment, "two }"),
+                (TokenKind::Comment, "(* three"),
+                (TokenKind::Comment, "four *)"),
+                (TokenKind::Keyword, "BEGIN"),
+            ]
+        );
+    }
+
+    #[test]
+    fn directives_strings_and_numbers() {
+        assert_eq!(
+            highlight(&Delphi, "{$R *.res} s := 'it''s'; n := $1F + 2.5;"),
+            [
+                (TokenKind::Directive, "{$R *.res}"),
+                (TokenKind::String, "'it'"),
+                (TokenKind::String, "'s'"),
+                (TokenKind::Number, "$1F"),
+                (TokenKind::Number, "2.5"),
+            ]
+        );
+    }
+}
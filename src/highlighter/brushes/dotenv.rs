@@ -0,0 +1,262 @@
+//! `.env` files, `KEY=value` lines as dotenv, Docker Compose and the shells sourcing them read.
+
+use crate::highlighter::scanner::Scanner;
+use crate::highlighter::{Brush, LineState, SyntaxError, Token, TokenKind};
+
+/// A double quoted value continued from the line before, expanding variables.
+const DOUBLE_QUOTED: u32 = 1;
+/// A single quoted value continued from the line before, taken literally.
+const SINGLE_QUOTED: u32 = 2;
+
+pub(crate) struct Dotenv;
+
+fn is_key_start(c: char) -> bool {
+    c.is_ascii_alphabetic() || c == '_'
+}
+
+fn is_key_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || c == '.'
+}
+
+/// Length of the `$NAME` or `${NAME}` reference `rest` starts with.
+fn variable_len(rest: &str) -> Option<usize> {
+    let name = rest.strip_prefix('$')?;
+    if name.starts_with('{') {
+        return Some(2 + name.find('}')?);
+    }
+    name.starts_with(is_key_start)
+        .then(|| 1 + name.len() - name.trim_start_matches(is_key_char).len())
+}
+
+/// Consumes a double quoted value's body up to the closing quote, expanding variables, returning
+/// whether it closed. The string's token starts at `start`, before the opening quote if it is on
+/// this line.
+fn expanding(s: &mut Scanner, mut start: usize) -> bool {
+    while let Some(c) = s.peek() {
+        if c == '\\' {
+            s.bump();
+            s.bump();
+        } else if c == '"' {
+            s.bump();
+            s.token(TokenKind::String, start);
+            return true;
+        } else if let Some(len) = variable_len(s.rest()) {
+            s.token(TokenKind::String, start);
+            let variable = s.pos();
+            s.eat(&s.rest()[..len]);
+            s.token(TokenKind::Variable, variable);
+            start = s.pos();
+        } else {
+            s.bump();
+        }
+    }
+    s.token(TokenKind::String, start);
+    false
+}
+
+/// Byte offset past the closing `quote` in `code`, escapes skipped in double quoted values.
+fn closing_quote(code: &str, quote: char) -> Option<usize> {
+    let mut chars = code.char_indices();
+    while let Some((index, c)) = chars.next() {
+        if c == '\\' && quote == '"' {
+            chars.next();
+        } else if c == quote {
+            return Some(index + 1);
+        }
+    }
+    None
+}
+
+/// Checks that every line is blank, a comment or an assignment, quoted values spanning lines.
+fn check_dotenv(code: &str) -> Result<(), SyntaxError> {
+    let mut offset = 0;
+    while offset < code.len() {
+        let line_end = code[offset..]
+            .find('\n')
+            .map_or(code.len(), |end| offset + end);
+        let line = &code[offset..line_end];
+        let content = line.trim_start();
+        let start = offset + line.len() - content.len();
+        offset = line_end + 1;
+
+        if content.trim_end().is_empty() || content.starts_with('#') {
+            continue;
+        }
+        let assignment = content
+            .strip_prefix("export")
+            .filter(|rest| rest.starts_with([' ', '\t']))
+            .map_or(content, str::trim_start);
+        let key_start = start + content.len() - assignment.len();
+        let key = assignment.len() - assignment.trim_start_matches(is_key_char).len();
+        if key == 0 || !assignment.starts_with(is_key_start) {
+            return Err(SyntaxError::at_offset(
+                code,
+                key_start,
+                "expected a variable name",
+            ));
+        }
+        let value_start = key_start + key;
+        let Some(value) = code[value_start..line_end].strip_prefix('=') else {
+            return Err(SyntaxError::at_offset(
+                code,
+                value_start,
+                "expected `=` after the variable name",
+            ));
+        };
+        let value_start = value_start + 1;
+
+        let Some(quote) = value.chars().next().filter(|c| *c == '"' || *c == '\'') else {
+            continue;
+        };
+        // quoted values may span lines
+        let Some(end) = closing_quote(&code[value_start + 1..], quote) else {
+            return Err(SyntaxError::at_offset(code, value_start, "unclosed quote"));
+        };
+        let after_start = value_start + 1 + end;
+        let after_end = code[after_start..]
+            .find('\n')
+            .map_or(code.len(), |end| after_start + end);
+        let after = code[after_start..after_end].trim_end();
+        let text = after.trim_start();
+        if !text.is_empty() && !text.starts_with('#') {
+            return Err(SyntaxError::at_offset(
+                code,
+                after_start + after.len() - text.len(),
+                "unexpected text after the quoted value",
+            ));
+        }
+        offset = after_end + 1;
+    }
+    Ok(())
+}
+
+impl Brush for Dotenv {
+    fn tokenize_line(&self, line: &str, state: &mut LineState, tokens: &mut Vec<Token>) {
+        let mut s = Scanner::new(line, tokens);
+
+        match state.mode {
+            DOUBLE_QUOTED => {
+                if !expanding(&mut s, 0) {
+                    return;
+                }
+            }
+            SINGLE_QUOTED => {
+                let closed = s.eat_until("'");
+                s.token(TokenKind::String, 0);
+                if !closed {
+                    return;
+                }
+            }
+            _ => {
+                s.eat_while(char::is_whitespace);
+                let start = s.pos();
+                if s.starts_with("#") {
+                    s.eat_to_end();
+                    s.token(TokenKind::Comment, start);
+                    return;
+                }
+                if s.rest()
+                    .strip_prefix("export")
+                    .is_some_and(|rest| rest.starts_with([' ', '\t']))
+                {
+                    s.eat("export");
+                    s.token(TokenKind::Keyword, start);
+                    s.eat_while(char::is_whitespace);
+                }
+                let key = s.pos();
+                if !s.eat_while(is_key_char) || !s.starts_with("=") {
+                    return;
+                }
+                s.token(TokenKind::Variable, key);
+                s.bump();
+
+                let start = s.pos();
+                if s.eat_char('"') {
+                    if !expanding(&mut s, start) {
+                        state.mode = DOUBLE_QUOTED;
+                        return;
+                    }
+                } else if s.eat_char('\'') {
+                    let closed = s.eat_until("'");
+                    s.token(TokenKind::String, start);
+                    if !closed {
+                        state.mode = SINGLE_QUOTED;
+                        return;
+                    }
+                } else {
+                    // unquoted values run up to a comment, which needs a blank before it
+                    let mut plain = s.pos();
+                    while let Some(c) = s.peek() {
+                        if c == '#' && s.prev().is_some_and(char::is_whitespace) {
+                            break;
+                        } else if let Some(len) = variable_len(s.rest()) {
+                            s.token(TokenKind::String, plain);
+                            let variable = s.pos();
+                            s.eat(&s.rest()[..len]);
+                            s.token(TokenKind::Variable, variable);
+                            plain = s.pos();
+                        } else {
+                            s.bump();
+                        }
+                    }
+                    s.token(TokenKind::String, plain);
+                }
+            }
+        }
+        state.mode = 0;
+
+        s.eat_while(|c| c != '#');
+        let start = s.pos();
+        s.eat_to_end();
+        s.token(TokenKind::Comment, start);
+    }
+
+    fn check(&self, code: &str) -> Option<Result<(), SyntaxError>> {
+        Some(check_dotenv(code))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::highlighter::testing::highlight;
+
+    #[test]
+    fn values_expand_variables() {
+        assert_eq!(
+            highlight(&Dotenv, "export A=x$B#c # d\nE=\"${F}\n$G\" # h\nI='$J'"),
+            [
+                (TokenKind::Keyword, "export"),
+                (TokenKind::Variable, "A"),
+                (TokenKind::String, "x"),
+                (TokenKind::Variable, "$B"),
+                (TokenKind::String, "#c "),
+                (TokenKind::Comment, "# d"),
+                (TokenKind::Variable, "E"),
+                (TokenKind::String, "\""),
+                (TokenKind::Variable, "${F}"),
+                (TokenKind::Variable, "$G"),
+                (TokenKind::String, "\""),
+                (TokenKind::Comment, "# h"),
+                (TokenKind::Variable, "I"),
+                (TokenKind::String, "'$J'"),
+            ]
+        );
+    }
+
+    #[test]
+    fn check_reports_the_error_position() {
+        let valid = "# c\nexport A=1\nB=\"x\n\\\"y\"  # d\nC='z'\nD=\n";
+        assert_eq!(Dotenv.check(valid), Some(Ok(())));
+        for (code, position) in [
+            ("A=1\n  1B=2", (2, 3)),
+            ("A=1\nB = 2", (2, 2)),
+            ("A=\"é\nB=2", (1, 3)),
+            ("A='x' y", (1, 7)),
+            ("export =1", (1, 8)),
+        ] {
+            let error = Dotenv.check(code).unwrap().unwrap_err();
+            assert_eq!((error.line, error.column), position, "{code:?}");
+        }
+    }
+}
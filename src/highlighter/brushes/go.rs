@@ -0,0 +1,242 @@
+use crate::highlighter::scanner::{contains, Scanner};
+use crate::highlighter::{Brush, LineState, Token, TokenKind};
+
+const BLOCK_COMMENT: u32 = 1;
+/// Inside a backtick quoted raw string.
+const RAW_STRING: u32 = 2;
+
+const KEYWORDS: &[&str] = &[
+    "break",
+    "case",
+    "chan",
+    "const",
+    "continue",
+    "default",
+    "defer",
+    "else",
+    "fallthrough",
+    "for",
+    "func",
+    "go",
+    "goto",
+    "if",
+    "import",
+    "interface",
+    "map",
+    "package",
+    "range",
+    "return",
+    "select",
+    "struct",
+    "switch",
+    "type",
+    "var",
+];
+
+const DATATYPES: &[&str] = &[
+    "any",
+    "bool",
+    "byte",
+    "comparable",
+    "complex128",
+    "complex64",
+    "error",
+    "float32",
+    "float64",
+    "int",
+    "int16",
+    "int32",
+    "int64",
+    "int8",
+    "rune",
+    "string",
+    "uint",
+    "uint16",
+    "uint32",
+    "uint64",
+    "uint8",
+    "uintptr",
+];
+
+/// The predeclared constants and zero value.
+const VALUES: &[&str] = &["false", "iota", "nil", "true"];
+
+/// The predeclared functions.
+const BUILTINS: &[&str] = &[
+    "append", "cap", "clear", "close", "complex", "copy", "delete", "imag", "len", "make", "max",
+    "min", "new", "panic", "print", "println", "real", "recover",
+];
+
+pub(crate) struct Go;
+
+/// Splits a raw string's body reading as a struct tag, `key:"value"` pairs separated by spaces,
+/// into the lengths of its gaps, keys and values.
+fn struct_tag(mut body: &str) -> Option<Vec<(usize, usize, usize)>> {
+    let mut pairs = Vec::new();
+    loop {
+        let key = body.trim_start_matches(' ');
+        let gap = body.len() - key.len();
+        if key.is_empty() {
+            break;
+        }
+        if gap == 0 && !pairs.is_empty() {
+            return None;
+        }
+        let name = key.len()
+            - key
+                .trim_start_matches(|c: char| c.is_ascii_graphic() && c != ':' && c != '"')
+                .len();
+        let value = key[name..].strip_prefix(':')?;
+        if name == 0 || !value.starts_with('"') {
+            return None;
+        }
+        let value = 2 + value[1..].find('"')?;
+        pairs.push((gap, name, value));
+        body = &key[name + 1 + value..];
+    }
+    (!pairs.is_empty()).then_some(pairs)
+}
+
+impl Brush for Go {
+    fn tokenize_line(&self, line: &str, state: &mut LineState, tokens: &mut Vec<Token>) {
+        let mut s = Scanner::new(line, tokens);
+
+        match state.mode {
+            BLOCK_COMMENT => {
+                let closed = s.eat_until("*/");
+                s.token(TokenKind::Comment, 0);
+                if !closed {
+                    return;
+                }
+            }
+            RAW_STRING => {
+                let closed = s.eat_until("`");
+                s.token(TokenKind::String, 0);
+                if !closed {
+                    return;
+                }
+            }
+            _ => {}
+        }
+        state.mode = 0;
+
+        while !s.is_done() {
+            let start = s.pos();
+
+            // `//go:build` and friends, read by the toolchain, must start the line
+            if start == 0 && s.starts_with("//go:") {
+                s.eat_to_end();
+                s.token(TokenKind::Preprocessor, start);
+            } else if s.eat("//") {
+                s.eat_to_end();
+                s.token(TokenKind::Comment, start);
+            } else if s.eat("/*") {
+                if !s.eat_until("*/") {
+                    state.mode = BLOCK_COMMENT;
+                }
+                s.token(TokenKind::Comment, start);
+            } else if s.eat_char('`') {
+                let tag = s
+                    .rest()
+                    .find('`')
+                    .and_then(|end| struct_tag(&s.rest()[..end]));
+                if let Some(tag) = tag {
+                    s.token(TokenKind::String, start);
+                    for (gap, key, value) in tag {
+                        s.eat(&" ".repeat(gap));
+                        let key_start = s.pos();
+                        s.eat(&s.rest()[..key]);
+                        s.token(TokenKind::Attribute, key_start);
+                        s.eat_char(':');
+                        let value_start = s.pos();
+                        s.eat(&s.rest()[..value]);
+                        s.token(TokenKind::AttributeValue, value_start);
+                    }
+                    let end = s.pos();
+                    s.eat_until("`");
+                    s.token(TokenKind::String, end);
+                } else {
+                    if !s.eat_until("`") {
+                        state.mode = RAW_STRING;
+                    }
+                    s.token(TokenKind::String, start);
+                }
+            } else if let Some(quote) = s.peek().filter(|c| *c == '"' || *c == '\'') {
+                s.bump();
+                s.eat_quoted(quote, Some('\\'));
+                s.token(TokenKind::String, start);
+            } else if s.eat_separated_number() {
+                s.token(TokenKind::Number, start);
+            } else if s.at_word_boundary() && s.eat_word() {
+                let word = s.slice(start);
+                if contains(KEYWORDS, word) {
+                    s.token(TokenKind::Keyword, start);
+                } else if contains(DATATYPES, word) {
+                    s.token(TokenKind::DataType, start);
+                } else if contains(VALUES, word) {
+                    s.token(TokenKind::Value, start);
+                } else if contains(BUILTINS, word) && s.peek() == Some('(') {
+                    s.token(TokenKind::Builtin, start);
+                }
+            } else {
+                s.bump();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::highlighter::testing::highlight;
+
+    #[test]
+    fn struct_tags_split_into_keys_and_values() {
+        assert_eq!(
+            highlight(&Go, "Name string `json:\"name\"  db:\"n\"`"),
+            [
+                (TokenKind::DataType, "string"),
+                (TokenKind::String, "`"),
+                (TokenKind::Attribute, "json"),
+                (TokenKind::AttributeValue, "\"name\""),
+                (TokenKind::Attribute, "db"),
+                (TokenKind::AttributeValue, "\"n\""),
+                (TokenKind::String, "`"),
+            ]
+        );
+        assert_eq!(struct_tag("json:\"a\"db:\"b\""), None);
+        assert_eq!(struct_tag("not a tag"), None);
+    }
+
+    #[test]
+    fn raw_strings_and_comments_span_lines() {
+        assert_eq!(
+            highlight(&Go, "s := `a\n\"b` /* c\nd */ // e"),
+            [
+                (TokenKind::String, "`a"),
+                (TokenKind::String, "\"b`"),
+                (TokenKind::Comment, "/* c"),
+                (TokenKind::Comment, "d */"),
+                (TokenKind::Comment, "// e"),
+            ]
+        );
+    }
+
+    #[test]
+    fn directives_values_and_builtins() {
+        assert_eq!(
+            highlight(
+                &Go,
+                "//go:generate x\nx := len(a) + 1_000 + 0x1F\nlen := nil // go:no"
+            ),
+            [
+                (TokenKind::Preprocessor, "//go:generate x"),
+                (TokenKind::Builtin, "len"),
+                (TokenKind::Number, "1_000"),
+                (TokenKind::Number, "0x1F"),
+                (TokenKind::Value, "nil"),
+                (TokenKind::Comment, "// go:no"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,149 @@
+//! INI files, `[section]` headers over `key = value` lines, as Windows, PHP, Git and Python's
+//! `configparser` read them.
+
+use crate::highlighter::scanner::{contains_ignore_case, Scanner};
+use crate::highlighter::{Brush, LineState, SyntaxError, Token, TokenKind};
+
+/// The booleans the common readers accept, lower case.
+const VALUES: &[&str] = &["false", "no", "off", "on", "true", "yes"];
+
+pub(crate) struct Ini;
+
+/// Byte offset of the `=` or `:` separating `line`'s key from its value.
+fn separator(line: &str) -> Option<usize> {
+    line.find(['=', ':'])
+}
+
+/// Checks that every line is blank, a comment, a section header or a key with a value.
+fn check_ini(code: &str) -> Result<(), SyntaxError> {
+    for (index, line) in code.lines().enumerate() {
+        let content = line.trim_start();
+        let column = line.len() - content.len() + 1;
+        let error = |message| Err(SyntaxError::new(index + 1, column, message));
+
+        if content.is_empty() || content.starts_with([';', '#']) {
+            continue;
+        }
+        if let Some(header) = content.strip_prefix('[') {
+            let Some((name, after)) = header.split_once(']') else {
+                return error("unclosed section header");
+            };
+            if name.trim().is_empty() {
+                return error("empty section name");
+            }
+            let after = after.trim_start();
+            if !after.is_empty() && !after.starts_with([';', '#']) {
+                return error("unexpected text after the section header");
+            }
+        } else {
+            match separator(content) {
+                None => return error("expected `key = value` or a `[section]` header"),
+                Some(0) => return error("missing key before the value"),
+                Some(_) => {}
+            }
+        }
+    }
+    Ok(())
+}
+
+impl Brush for Ini {
+    fn tokenize_line(&self, line: &str, _state: &mut LineState, tokens: &mut Vec<Token>) {
+        let mut s = Scanner::new(line, tokens);
+        s.eat_while(char::is_whitespace);
+        let start = s.pos();
+
+        if s.starts_with(";") || s.starts_with("#") {
+            s.eat_to_end();
+            s.token(TokenKind::Comment, start);
+            return;
+        }
+        if s.starts_with("[") {
+            s.eat_until("]");
+            s.token(TokenKind::Keyword, start);
+        } else if let Some(end) = separator(s.rest()) {
+            let key = s.rest()[..end].trim_end();
+            s.eat(key);
+            s.token(TokenKind::Attribute, start);
+            s.eat_while(char::is_whitespace);
+            s.bump();
+            s.eat_while(char::is_whitespace);
+
+            let start = s.pos();
+            if let Some(quote) = s.peek().filter(|c| *c == '"' || *c == '\'') {
+                s.bump();
+                s.eat_quoted(quote, Some('\\'));
+                s.token(TokenKind::String, start);
+            } else {
+                // values run up to a comment, which needs a blank before it
+                let value = s
+                    .rest()
+                    .find([';', '#'])
+                    .filter(|index| s.rest()[..*index].ends_with(char::is_whitespace))
+                    .map_or(s.rest(), |index| &s.rest()[..index])
+                    .trim_end();
+                s.eat(value);
+                if contains_ignore_case(VALUES, value) {
+                    s.token(TokenKind::Value, start);
+                } else if value.contains(|c: char| c.is_ascii_digit())
+                    && value.parse::<f64>().is_ok()
+                {
+                    s.token(TokenKind::Number, start);
+                }
+            }
+        }
+
+        s.eat_while(char::is_whitespace);
+        let start = s.pos();
+        if s.starts_with(";") || s.starts_with("#") {
+            s.eat_to_end();
+            s.token(TokenKind::Comment, start);
+        }
+    }
+
+    fn check(&self, code: &str) -> Option<Result<(), SyntaxError>> {
+        Some(check_ini(code))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::highlighter::testing::highlight;
+
+    #[test]
+    fn values_and_comments() {
+        assert_eq!(
+            highlight(
+                &Ini,
+                "[s] ; a\nkey = On ; b\nurl: http://x#y\nn = 1.5\nq = \"c;d\""
+            ),
+            [
+                (TokenKind::Keyword, "[s]"),
+                (TokenKind::Comment, "; a"),
+                (TokenKind::Attribute, "key"),
+                (TokenKind::Value, "On"),
+                (TokenKind::Comment, "; b"),
+                (TokenKind::Attribute, "url"),
+                (TokenKind::Attribute, "n"),
+                (TokenKind::Number, "1.5"),
+                (TokenKind::Attribute, "q"),
+                (TokenKind::String, "\"c;d\""),
+            ]
+        );
+    }
+
+    #[test]
+    fn check_reports_the_error_position() {
+        assert_eq!(Ini.check("; c\n[s]\na = 1\n\n  b: 2 # d\n"), Some(Ok(())));
+        for (code, position) in [
+            ("[s\n", (1, 1)),
+            ("a = 1\n  [ ]", (2, 3)),
+            ("[s] x", (1, 1)),
+            ("a = 1\n\n  value", (3, 3)),
+            ("= 1", (1, 1)),
+        ] {
+            let error = Ini.check(code).unwrap().unwrap_err();
+            assert_eq!((error.line, error.column), position, "{code:?}");
+        }
+    }
+}
@@ -0,0 +1,493 @@
+//! JavaScript as of ECMAScript 2023, TypeScript, and either with JSX.
+//!
+//! Template literals and JSX embed script in other constructs and script again in those, so the
+//! constructs left to return to are kept on the [`LineState::stack`].
+
+use crate::highlighter::scanner::{contains, is_word_char, Scanner};
+use crate::highlighter::{Brush, LineState, Token, TokenKind};
+
+/// Script, `depth` counting the braces opened since the construct it is embedded in.
+const SCRIPT: u32 = 0;
+/// Inside `/* */`, returning to script with the same `depth`.
+const BLOCK_COMMENT: u32 = 1;
+/// The text of a template literal.
+const TEMPLATE: u32 = 2;
+/// The attributes of a JSX opening tag, `depth` counting the open elements including its own.
+const JSX_TAG: u32 = 3;
+/// The children of a JSX element, `depth` counting the open elements.
+const JSX_CHILDREN: u32 = 4;
+/// A JSX closing tag, `depth` counting the elements still open after it.
+const JSX_CLOSING_TAG: u32 = 5;
+
+const KEYWORDS: &[&str] = &[
+    "async",
+    "await",
+    "break",
+    "case",
+    "catch",
+    "class",
+    "const",
+    "continue",
+    "debugger",
+    "default",
+    "delete",
+    "do",
+    "else",
+    "export",
+    "extends",
+    "false",
+    "finally",
+    "for",
+    "function",
+    "if",
+    "import",
+    "in",
+    "instanceof",
+    "let",
+    "new",
+    "null",
+    "of",
+    "return",
+    "static",
+    "super",
+    "switch",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typeof",
+    "undefined",
+    "var",
+    "void",
+    "while",
+    "with",
+    "yield",
+];
+
+const TYPESCRIPT_KEYWORDS: &[&str] = &[
+    "abstract",
+    "as",
+    "asserts",
+    "declare",
+    "enum",
+    "implements",
+    "infer",
+    "interface",
+    "is",
+    "keyof",
+    "namespace",
+    "override",
+    "private",
+    "protected",
+    "public",
+    "readonly",
+    "satisfies",
+    "type",
+    "unique",
+];
+
+const TYPESCRIPT_TYPES: &[&str] = &[
+    "any", "bigint", "boolean", "never", "number", "object", "string", "symbol", "unknown",
+];
+
+const BUILTINS: &[&str] = &[
+    "Array",
+    "BigInt",
+    "Boolean",
+    "Date",
+    "Error",
+    "JSON",
+    "Map",
+    "Math",
+    "Number",
+    "Object",
+    "Promise",
+    "Proxy",
+    "Reflect",
+    "RegExp",
+    "Set",
+    "String",
+    "Symbol",
+    "WeakMap",
+    "WeakSet",
+    "console",
+    "document",
+    "globalThis",
+    "window",
+];
+
+/// Keywords after which an expression follows, rather than an operator.
+const EXPRESSION_KEYWORDS: &[&str] = &[
+    "await",
+    "case",
+    "delete",
+    "do",
+    "else",
+    "in",
+    "instanceof",
+    "new",
+    "of",
+    "return",
+    "throw",
+    "typeof",
+    "void",
+    "yield",
+];
+
+pub(crate) struct Script {
+    pub typescript: bool,
+    /// Markup in expressions, `<` starting a tag where an operand is expected.
+    pub jsx: bool,
+}
+
+pub(crate) const JAVASCRIPT: Script = Script {
+    typescript: false,
+    jsx: true,
+};
+
+pub(crate) const TYPESCRIPT: Script = Script {
+    typescript: true,
+    // `<T>value` is a type assertion in `.ts` files, JSX only in `.tsx` files
+    jsx: false,
+};
+
+pub(crate) const TSX: Script = Script {
+    typescript: true,
+    jsx: true,
+};
+
+fn is_identifier_char(c: char) -> bool {
+    is_word_char(c) || c == '$'
+}
+
+fn is_name_char(c: char) -> bool {
+    is_identifier_char(c) || matches!(c, ':' | '-' | '.')
+}
+
+/// Whether an operand is expected at the cursor, so `/` starts a regular expression and `<` a
+/// JSX tag rather than dividing or comparing.
+fn operand_expected(s: &Scanner) -> bool {
+    let before = s.consumed().trim_end();
+    match before.chars().next_back() {
+        None => true,
+        Some(c) if is_identifier_char(c) => {
+            let word = &before[before.trim_end_matches(is_identifier_char).len()..];
+            contains(EXPRESSION_KEYWORDS, word)
+        }
+        Some(c) => !matches!(c, ')' | ']' | '"' | '\'' | '`' | '.'),
+    }
+}
+
+/// Length of the regular expression literal `rest` starts with, flags included, `None` when it
+/// isn't closed on this line.
+fn regex_len(rest: &str) -> Option<usize> {
+    let mut chars = rest.char_indices().skip(1);
+    let mut class = false;
+    while let Some((index, c)) = chars.next() {
+        match c {
+            '\\' => {
+                chars.next();
+            }
+            '[' => class = true,
+            ']' => class = false,
+            '/' if !class => {
+                let flags = &rest[index + 1..];
+                return Some(rest.len() - flags.trim_start_matches(is_word_char).len());
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Tokenizes a tag's `<` or `</` up to the cursor and the element name after it.
+fn tag_start(s: &mut Scanner, start: usize) {
+    s.token(TokenKind::Tag, start);
+    let name = s.pos();
+    if s.eat_while(is_name_char) {
+        s.token(TokenKind::TagName, name);
+    }
+}
+
+impl Script {
+    fn script(&self, s: &mut Scanner, state: &mut LineState) {
+        while state.mode == SCRIPT && !s.is_done() {
+            let start = s.pos();
+
+            if start == 0 && s.starts_with("#!") {
+                s.eat_to_end();
+                s.token(TokenKind::Preprocessor, start);
+            } else if s.eat("//") {
+                s.eat_to_end();
+                s.token(TokenKind::Comment, start);
+            } else if s.eat("/*") {
+                if !s.eat_until("*/") {
+                    state.mode = BLOCK_COMMENT;
+                }
+                s.token(TokenKind::Comment, start);
+            } else if s.eat_char('`') {
+                s.token(TokenKind::String, start);
+                state.enter(TEMPLATE, 0);
+            } else if let Some(quote) = s.peek().filter(|c| *c == '"' || *c == '\'') {
+                s.bump();
+                s.eat_quoted(quote, Some('\\'));
+                s.token(TokenKind::String, start);
+            } else if let Some(len) = (s.peek() == Some('/') && operand_expected(s))
+                .then(|| regex_len(s.rest()))
+                .flatten()
+            {
+                s.eat(&s.rest()[..len]);
+                s.token(TokenKind::String, start);
+            } else if self.jsx
+                && s.peek() == Some('<')
+                && s.peek_nth(1).is_some_and(|c| c.is_alphabetic() || c == '>')
+                && operand_expected(s)
+            {
+                s.bump();
+                tag_start(s, start);
+                state.enter(JSX_TAG, 1);
+            } else if s.peek() == Some('@') && s.peek_nth(1).is_some_and(is_identifier_char) {
+                s.bump();
+                s.eat_while(|c| is_identifier_char(c) || c == '.');
+                s.token(TokenKind::Decorator, start);
+            } else if s.eat_char('{') {
+                state.depth += 1;
+            } else if s.eat_char('}') {
+                if state.depth > 0 {
+                    state.depth = state.depth.saturating_sub(1);
+                } else if !state.stack.is_empty() {
+                    state.leave();
+                    if state.mode == TEMPLATE {
+                        s.token(TokenKind::Special, start);
+                    }
+                }
+            } else if s.eat_separated_number() {
+                s.token(TokenKind::Number, start);
+            } else if !s.prev().is_some_and(is_identifier_char) && s.eat_while(is_identifier_char) {
+                let word = s.slice(start);
+                if contains(KEYWORDS, word)
+                    || self.typescript && contains(TYPESCRIPT_KEYWORDS, word)
+                {
+                    s.token(TokenKind::Keyword, start);
+                } else if self.typescript && contains(TYPESCRIPT_TYPES, word) {
+                    s.token(TokenKind::DataType, start);
+                } else if contains(BUILTINS, word) {
+                    s.token(TokenKind::Builtin, start);
+                }
+            } else {
+                s.bump();
+            }
+        }
+    }
+
+    fn template(s: &mut Scanner, state: &mut LineState) {
+        let start = s.pos();
+        while let Some(c) = s.peek() {
+            if c == '\\' {
+                s.bump();
+                s.bump();
+            } else if c == '`' {
+                s.bump();
+                s.token(TokenKind::String, start);
+                state.leave();
+                return;
+            } else if s.starts_with("${") {
+                s.token(TokenKind::String, start);
+                let open = s.pos();
+                s.eat("${");
+                s.token(TokenKind::Special, open);
+                state.enter(SCRIPT, 0);
+                return;
+            } else {
+                s.bump();
+            }
+        }
+        s.token(TokenKind::String, start);
+    }
+
+    fn jsx_tag(s: &mut Scanner, state: &mut LineState) {
+        while state.mode == JSX_TAG && !s.is_done() {
+            let start = s.pos();
+
+            if s.eat("/>") {
+                s.token(TokenKind::Tag, start);
+                state.depth = state.depth.saturating_sub(1);
+                if state.depth == 0 {
+                    state.leave();
+                } else {
+                    state.mode = JSX_CHILDREN;
+                }
+            } else if s.eat_char('>') {
+                s.token(TokenKind::Tag, start);
+                state.mode = JSX_CHILDREN;
+            } else if s.eat_char('{') {
+                state.enter(SCRIPT, 0);
+            } else if let Some(quote) = s.peek().filter(|c| *c == '"' || *c == '\'') {
+                s.bump();
+                s.eat_quoted(quote, None);
+                s.token(TokenKind::AttributeValue, start);
+            } else if s.eat_while(is_name_char) {
+                s.token(TokenKind::Attribute, start);
+            } else {
+                s.bump();
+            }
+        }
+    }
+
+    fn jsx_children(s: &mut Scanner, state: &mut LineState) {
+        while state.mode == JSX_CHILDREN && !s.is_done() {
+            let start = s.pos();
+
+            if s.eat("</") {
+                tag_start(s, start);
+                state.mode = JSX_CLOSING_TAG;
+                state.depth = state.depth.saturating_sub(1);
+            } else if s.peek() == Some('<')
+                && s.peek_nth(1).is_some_and(|c| c.is_alphabetic() || c == '>')
+            {
+                s.bump();
+                tag_start(s, start);
+                state.mode = JSX_TAG;
+                state.depth += 1;
+            } else if s.eat_char('{') {
+                state.enter(SCRIPT, 0);
+            } else {
+                s.bump();
+            }
+        }
+    }
+
+    fn jsx_closing_tag(s: &mut Scanner, state: &mut LineState) {
+        while state.mode == JSX_CLOSING_TAG && !s.is_done() {
+            let start = s.pos();
+
+            if s.eat_char('>') {
+                s.token(TokenKind::Tag, start);
+                if state.depth == 0 {
+                    state.leave();
+                } else {
+                    state.mode = JSX_CHILDREN;
+                }
+            } else if s.eat_while(is_name_char) {
+                s.token(TokenKind::TagName, start);
+            } else {
+                s.bump();
+            }
+        }
+    }
+}
+
+impl Brush for Script {
+    fn tokenize_line(&self, line: &str, state: &mut LineState, tokens: &mut Vec<Token>) {
+        let mut s = Scanner::new(line, tokens);
+
+        while !s.is_done() {
+            match state.mode {
+                BLOCK_COMMENT => {
+                    let start = s.pos();
+                    if s.eat_until("*/") {
+                        state.mode = SCRIPT;
+                    }
+                    s.token(TokenKind::Comment, start);
+                }
+                TEMPLATE => Script::template(&mut s, state),
+                JSX_TAG => Script::jsx_tag(&mut s, state),
+                JSX_CHILDREN => Script::jsx_children(&mut s, state),
+                JSX_CLOSING_TAG => Script::jsx_closing_tag(&mut s, state),
+                _ => self.script(&mut s, state),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::highlighter::testing::highlight;
+
+    #[test]
+    fn slashes_divide_after_operands() {
+        assert_eq!(
+            highlight(
+                &JAVASCRIPT,
+                "x = a / b / c; y = /[/]\\//gi.test(s) ? (n) / 2 : /x/;"
+            ),
+            [
+                (TokenKind::String, "/[/]\\//gi"),
+                (TokenKind::Number, "2"),
+                (TokenKind::String, "/x/"),
+            ]
+        );
+        assert_eq!(
+            highlight(&JAVASCRIPT, "return /a/.source"),
+            [(TokenKind::Keyword, "return"), (TokenKind::String, "/a/")]
+        );
+    }
+
+    #[test]
+    fn template_literals_nest_across_lines() {
+        assert_eq!(
+            highlight(&JAVASCRIPT, "`a ${b + `c ${{d: 1}.d}`}\ne\\` f`"),
+            [
+                (TokenKind::String, "`"),
+                (TokenKind::String, "a "),
+                (TokenKind::Special, "${"),
+                (TokenKind::String, "`"),
+                (TokenKind::String, "c "),
+                (TokenKind::Special, "${"),
+                (TokenKind::Number, "1"),
+                (TokenKind::Special, "}"),
+                (TokenKind::String, "`"),
+                (TokenKind::Special, "}"),
+                (TokenKind::String, "e\\` f`"),
+            ]
+        );
+    }
+
+    #[test]
+    fn jsx_elements_embed_script() {
+        assert_eq!(
+            highlight(&JAVASCRIPT, "<A b=\"c\" d={e < 1}>\n  <br/>{'f'}\n</A>;"),
+            [
+                (TokenKind::Tag, "<"),
+                (TokenKind::TagName, "A"),
+                (TokenKind::Attribute, "b"),
+                (TokenKind::AttributeValue, "\"c\""),
+                (TokenKind::Attribute, "d"),
+                (TokenKind::Number, "1"),
+                (TokenKind::Tag, ">"),
+                (TokenKind::Tag, "<"),
+                (TokenKind::TagName, "br"),
+                (TokenKind::Tag, "/>"),
+                (TokenKind::String, "'f'"),
+                (TokenKind::Tag, "</"),
+                (TokenKind::TagName, "A"),
+                (TokenKind::Tag, ">"),
+            ]
+        );
+    }
+
+    #[test]
+    fn typescript_types_and_assertions() {
+        let code = "let n: number = <number>x;";
+        assert_eq!(
+            highlight(&TYPESCRIPT, code),
+            [
+                (TokenKind::Keyword, "let"),
+                (TokenKind::DataType, "number"),
+                (TokenKind::DataType, "number"),
+            ]
+        );
+        assert_eq!(
+            highlight(&TSX, code),
+            [
+                (TokenKind::Keyword, "let"),
+                (TokenKind::DataType, "number"),
+                (TokenKind::Tag, "<"),
+                (TokenKind::TagName, "number"),
+                (TokenKind::Tag, ">"),
+            ]
+        );
+        assert_eq!(highlight(&JAVASCRIPT, "interface number"), []);
+    }
+}
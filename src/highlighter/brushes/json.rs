@@ -0,0 +1,354 @@
+//! JSON, and JSON5 with its comments, single quoted strings, unquoted keys and extra numbers.
+//!
+//! Both highlight the same, so comments in `tsconfig.json` and the like read fine, the difference
+//! being what [`Brush::check`] accepts.
+
+use crate::highlighter::scanner::{contains, is_word_char, Scanner};
+use crate::highlighter::{Brush, LineState, SyntaxError, Token, TokenKind};
+
+const BLOCK_COMMENT: u32 = 1;
+
+const VALUES: &[&str] = &["false", "null", "true"];
+
+/// The numbers JSON5 spells as words.
+const NUMBERS: &[&str] = &["Infinity", "NaN"];
+
+/// Nesting beyond which JSON5 fails to check rather than the stack overflowing, as in `serde_json`.
+const MAX_DEPTH: usize = 128;
+
+pub(crate) struct Json {
+    pub json5: bool,
+}
+
+pub(crate) const JSON: Json = Json { json5: false };
+
+pub(crate) const JSON5: Json = Json { json5: true };
+
+fn is_identifier_char(c: char) -> bool {
+    is_word_char(c) || c == '$'
+}
+
+/// Whether a `:` follows the cursor, making what was just consumed a key.
+fn at_key_end(s: &Scanner) -> bool {
+    s.rest().trim_start().starts_with(':')
+}
+
+fn check_json(code: &str) -> Result<(), SyntaxError> {
+    let Err(error) = serde_json::from_str::<serde::de::IgnoredAny>(code) else {
+        return Ok(());
+    };
+    // the position is part of the message as well
+    let message = error.to_string();
+    let message = message
+        .rsplit_once(" at line ")
+        .map_or(message.as_str(), |(message, _)| message);
+    Err(SyntaxError::new(
+        error.line(),
+        error.column().max(1),
+        message,
+    ))
+}
+
+/// Whether `word` is a JSON5 number: signed, hexadecimal, with a leading or trailing point, or
+/// `Infinity` and `NaN`.
+fn is_json5_number(word: &str) -> bool {
+    let unsigned = word.strip_prefix(['+', '-']).unwrap_or(word);
+    if contains(NUMBERS, unsigned) {
+        return true;
+    }
+    if let Some(hex) = unsigned
+        .strip_prefix("0x")
+        .or_else(|| unsigned.strip_prefix("0X"))
+    {
+        return !hex.is_empty() && hex.chars().all(|c| c.is_ascii_hexdigit());
+    }
+    let digits = |part: &str| part.chars().all(|c| c.is_ascii_digit());
+    let (mantissa, exponent) = match unsigned.split_once(['e', 'E']) {
+        Some((mantissa, exponent)) => (mantissa, Some(exponent)),
+        None => (unsigned, None),
+    };
+    let (integer, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    let exponent = exponent.is_none_or(|exponent| {
+        let exponent = exponent.strip_prefix(['+', '-']).unwrap_or(exponent);
+        !exponent.is_empty() && digits(exponent)
+    });
+    // like JSON, leading zeros aren't allowed
+    let leading_zero = integer.len() > 1 && integer.starts_with('0');
+    (!integer.is_empty() || !fraction.is_empty())
+        && !leading_zero
+        && digits(integer)
+        && digits(fraction)
+        && exponent
+}
+
+/// Recursive descent over JSON5, only finding out whether and where it fails to parse.
+struct Json5Parser<'a> {
+    code: &'a str,
+    pos: usize,
+}
+
+impl<'a> Json5Parser<'a> {
+    fn rest(&self) -> &'a str {
+        &self.code[self.pos..]
+    }
+
+    fn eat(&mut self, expected: &str) -> bool {
+        let eaten = self.rest().starts_with(expected);
+        if eaten {
+            self.pos += expected.len();
+        }
+        eaten
+    }
+
+    fn error(&self, message: impl Into<String>) -> SyntaxError {
+        SyntaxError::at_offset(self.code, self.pos, message)
+    }
+
+    /// Skips whitespace and comments.
+    fn blank(&mut self) -> Result<(), SyntaxError> {
+        loop {
+            let rest = self.rest();
+            self.pos += rest.len()
+                - rest
+                    .trim_start_matches(|c: char| c.is_whitespace() || c == '\u{feff}')
+                    .len();
+            if self.eat("//") {
+                self.pos += self.rest().find('\n').unwrap_or(self.rest().len());
+            } else if self.rest().starts_with("/*") {
+                match self.rest()[2..].find("*/") {
+                    Some(end) => self.pos += end + 4,
+                    None => return Err(self.error("unclosed comment")),
+                }
+            } else {
+                return Ok(());
+            }
+        }
+    }
+
+    fn value(&mut self, depth: usize) -> Result<(), SyntaxError> {
+        if depth > MAX_DEPTH {
+            return Err(self.error("nested too deeply"));
+        }
+        match self.rest().chars().next() {
+            Some('{') => self.collection('}', depth),
+            Some('[') => self.collection(']', depth),
+            Some(quote @ ('"' | '\'')) => self.string(quote),
+            _ => {
+                let rest = self.rest();
+                let len = rest.len()
+                    - rest
+                        .trim_start_matches(|c: char| is_identifier_char(c) || "+-.".contains(c))
+                        .len();
+                let word = &rest[..len];
+                if !contains(VALUES, word) && !is_json5_number(word) {
+                    return Err(self.error("expected a value"));
+                }
+                self.pos += len;
+                Ok(())
+            }
+        }
+    }
+
+    /// An object or array, up to the `closing` bracket.
+    fn collection(&mut self, closing: char, depth: usize) -> Result<(), SyntaxError> {
+        let closing = closing.to_string();
+        self.pos += 1;
+        loop {
+            self.blank()?;
+            if self.eat(&closing) {
+                return Ok(());
+            }
+            if closing == "}" {
+                self.key()?;
+                self.blank()?;
+                if !self.eat(":") {
+                    return Err(self.error("expected `:` after the key"));
+                }
+                self.blank()?;
+            }
+            self.value(depth + 1)?;
+            self.blank()?;
+            // a trailing comma is fine
+            if !self.eat(",") {
+                if self.eat(&closing) {
+                    return Ok(());
+                }
+                return Err(self.error(format!("expected `,` or `{closing}`")));
+            }
+        }
+    }
+
+    fn key(&mut self) -> Result<(), SyntaxError> {
+        if let Some(quote @ ('"' | '\'')) = self.rest().chars().next() {
+            return self.string(quote);
+        }
+        let rest = self.rest();
+        if !rest.starts_with(|c: char| c.is_alphabetic() || c == '_' || c == '$') {
+            return Err(self.error("expected a key"));
+        }
+        self.pos += rest.len() - rest.trim_start_matches(is_identifier_char).len();
+        Ok(())
+    }
+
+    fn string(&mut self, quote: char) -> Result<(), SyntaxError> {
+        let start = self.pos;
+        let mut chars = self.rest().char_indices().skip(1).peekable();
+        while let Some((index, c)) = chars.next() {
+            match c {
+                // escaping a line break continues the string on the next line
+                '\\' => {
+                    if let Some((_, '\r')) = chars.next() {
+                        chars.next_if(|(_, c)| *c == '\n');
+                    }
+                }
+                '\n' | '\r' => break,
+                _ if c == quote => {
+                    self.pos += index + 1;
+                    return Ok(());
+                }
+                _ => {}
+            }
+        }
+        self.pos = start;
+        Err(self.error("unclosed string"))
+    }
+}
+
+fn check_json5(code: &str) -> Result<(), SyntaxError> {
+    let mut parser = Json5Parser { code, pos: 0 };
+    parser.blank()?;
+    parser.value(0)?;
+    parser.blank()?;
+    if parser.pos < code.len() {
+        return Err(parser.error("unexpected text after the value"));
+    }
+    Ok(())
+}
+
+impl Brush for Json {
+    fn tokenize_line(&self, line: &str, state: &mut LineState, tokens: &mut Vec<Token>) {
+        let mut s = Scanner::new(line, tokens);
+
+        if state.mode == BLOCK_COMMENT {
+            let closed = s.eat_until("*/");
+            s.token(TokenKind::Comment, 0);
+            if !closed {
+                return;
+            }
+            state.mode = 0;
+        }
+
+        while !s.is_done() {
+            let start = s.pos();
+
+            if s.eat("//") {
+                s.eat_to_end();
+                s.token(TokenKind::Comment, start);
+            } else if s.eat("/*") {
+                if !s.eat_until("*/") {
+                    state.mode = BLOCK_COMMENT;
+                }
+                s.token(TokenKind::Comment, start);
+            } else if let Some(quote) = s.peek().filter(|c| *c == '"' || *c == '\'') {
+                s.bump();
+                s.eat_quoted(quote, Some('\\'));
+                if at_key_end(&s) {
+                    s.token(TokenKind::Attribute, start);
+                } else {
+                    s.token(TokenKind::String, start);
+                }
+            } else if matches!(s.peek(), Some('-' | '+'))
+                && s.peek_nth(1)
+                    .is_some_and(|c| c.is_ascii_digit() || c == '.' || c == 'I' || c == 'N')
+            {
+                // the sign belongs to the number
+                s.bump();
+                if !s.eat_separated_number() {
+                    s.eat_while(is_identifier_char);
+                }
+                s.token(TokenKind::Number, start);
+            } else if s.eat_separated_number() {
+                s.token(TokenKind::Number, start);
+            } else if !s.prev().is_some_and(is_identifier_char) && s.eat_while(is_identifier_char) {
+                let word = s.slice(start);
+                if at_key_end(&s) {
+                    s.token(TokenKind::Attribute, start);
+                } else if contains(VALUES, word) {
+                    s.token(TokenKind::Value, start);
+                } else if contains(NUMBERS, word) {
+                    s.token(TokenKind::Number, start);
+                }
+            } else {
+                s.bump();
+            }
+        }
+    }
+
+    fn check(&self, code: &str) -> Option<Result<(), SyntaxError>> {
+        Some(if self.json5 {
+            check_json5(code)
+        } else {
+            check_json(code)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::highlighter::testing::highlight;
+
+    fn error(result: Option<Result<(), SyntaxError>>) -> (usize, usize) {
+        let error = result.unwrap().unwrap_err();
+        (error.line, error.column)
+    }
+
+    #[test]
+    fn keys_values_and_signed_numbers() {
+        assert_eq!(
+            highlight(&JSON5, "{a: -Infinity, 'b' : [\"c\", +.5, null] /* d */}"),
+            [
+                (TokenKind::Attribute, "a"),
+                (TokenKind::Number, "-Infinity"),
+                (TokenKind::Attribute, "'b'"),
+                (TokenKind::String, "\"c\""),
+                (TokenKind::Number, "+.5"),
+                (TokenKind::Value, "null"),
+                (TokenKind::Comment, "/* d */"),
+            ]
+        );
+    }
+
+    #[test]
+    fn json_checks_with_serde() {
+        assert_eq!(JSON.check("{\"a\": [1, 2.5, true]}"), Some(Ok(())));
+        assert_eq!(error(JSON.check("{\n  \"a\": 1,\n}")), (3, 1));
+        assert_eq!(error(JSON.check("{\"a\": 'b'}")), (1, 7));
+        let message = JSON.check("[1,]").unwrap().unwrap_err().message;
+        assert!(!message.contains(" at line "), "{message}");
+    }
+
+    #[test]
+    fn json5_checks_its_extensions() {
+        let code = "// c\n{a: 0x1F, 'b': [.5, +Infinity,], /* d */ c: 'e\\\nf',}";
+        assert_eq!(JSON5.check(code), Some(Ok(())));
+        assert_eq!(error(JSON5.check("{a: 1\n b: 2}")), (2, 2));
+        assert_eq!(error(JSON5.check("{a: 01}")), (1, 5));
+        assert_eq!(error(JSON5.check("['é\n']")), (1, 2));
+        assert_eq!(error(JSON5.check("{} []")), (1, 4));
+        assert_eq!(error(JSON5.check("/* open")), (1, 1));
+        assert_eq!(error(JSON5.check("{1: 2}")), (1, 2));
+        let nested = format!("{}{}", "[".repeat(200), "]".repeat(200));
+        assert_eq!(error(JSON5.check(&nested)), (1, 130));
+    }
+
+    #[test]
+    fn json5_numbers() {
+        for number in ["0", "-1.5e+3", ".5", "5.", "0xFf", "+Infinity", "NaN"] {
+            assert!(is_json5_number(number), "{number}");
+        }
+        for word in ["", "01", ".", "0x", "1e", "1.2.3", "infinity", "--1"] {
+            assert!(!is_json5_number(word), "{word}");
+        }
+    }
+}
@@ -0,0 +1,626 @@
+mod c_like;
+mod console;
+mod cpp;
+mod csharp;
+mod css;
+mod delphi;
+mod dotenv;
+mod go;
+mod ini;
+mod java;
+mod jscript;
+mod json;
+mod php;
+mod powershell;
+mod python;
+mod ruby;
+mod rust;
+mod shell;
+mod sql;
+mod toml;
+mod vb;
+mod xml;
+mod yaml;
+
+use std::sync::Arc;
+
+use super::registry::{Icon, Language};
+
+fn strings(values: &[&str]) -> Vec<String> {
+    values.iter().map(|value| value.to_string()).collect()
+}
+
+fn hints(values: &[(&str, f32)]) -> Vec<(String, f32)> {
+    values
+        .iter()
+        .map(|(hint, weight)| (hint.to_string(), *weight))
+        .collect()
+}
+
+/// Languages shipped with the app, in the order of the former `PROGRAMMING_LANGUAGES` picker.
+pub(super) fn builtin() -> Vec<Language> {
+    vec![
+        Language {
+            aliases: strings(&["c", "c++"]),
+            extensions: strings(&["c", "cc", "cpp", "cxx", "h", "hh", "hpp", "hxx"]),
+            mime_types: strings(&["text/x-c", "text/x-csrc", "text/x-c++src", "text/x-chdr"]),
+            icon: Some(Icon::new("images/cpp", "png")),
+            hints: hints(&[
+                ("#include", 3.0),
+                ("#define", 2.0),
+                ("std::", 2.0),
+                ("cout", 1.5),
+                ("nullptr", 1.5),
+                ("template<", 1.5),
+                ("printf(", 1.0),
+                ("->", 0.5),
+            ]),
+            ..Language::new("cpp", "C / C++", Arc::new(cpp::CPP))
+        },
+        Language {
+            aliases: strings(&["c#", "c-sharp", "cs"]),
+            extensions: strings(&["cs"]),
+            mime_types: strings(&["text/x-csharp"]),
+            icon: Some(Icon::new("images/csharp", "png")),
+            hints: hints(&[
+                ("using System", 3.0),
+                ("Console.Write", 2.0),
+                ("{ get;", 2.0),
+                ("namespace ", 1.0),
+                ("public class", 0.5),
+                ("string ", 0.5),
+                ("var ", 0.3),
+            ]),
+            ..Language::new("csharp", "C#", Arc::new(csharp::CSHARP))
+        },
+        Language {
+            aliases: strings(&["py"]),
+            extensions: strings(&["py", "pyw", "pyi"]),
+            mime_types: strings(&["text/x-python", "text/x-script.python"]),
+            icon: Some(Icon::new("images/python", "png")),
+            interpreters: strings(&["python", "pypy"]),
+            hints: hints(&[
+                ("elif ", 2.0),
+                ("__init__", 2.0),
+                ("def ", 1.5),
+                ("self.", 1.0),
+                ("\"\"\"", 1.0),
+                ("import ", 0.5),
+                ("print(", 0.5),
+                ("None", 0.5),
+            ]),
+            ..Language::new("python", "Python", Arc::new(python::Python))
+        },
+        Language {
+            extensions: strings(&["css"]),
+            mime_types: strings(&["text/css"]),
+            icon: Some(Icon::new("images/css", "png")),
+            tab_width: 2,
+            hints: hints(&[
+                ("@media", 2.0),
+                ("px;", 2.0),
+                ("color:", 1.5),
+                ("margin:", 1.0),
+                ("!important", 1.0),
+                ("; }", 0.5),
+            ]),
+            ..Language::new("css", "CSS", Arc::new(css::Css))
+        },
+        Language {
+            aliases: strings(&["pascal"]),
+            extensions: strings(&["pas", "dpr", "dpk", "pp"]),
+            mime_types: strings(&["text/x-pascal"]),
+            icon: Some(Icon::new("images/delphi", "png")),
+            tab_width: 2,
+            hints: hints(&[
+                ("procedure ", 2.0),
+                ("implementation", 2.0),
+                ("end;", 1.5),
+                (":=", 1.0),
+                ("begin", 1.0),
+                ("writeln", 1.0),
+                ("unit ", 1.0),
+            ]),
+            ..Language::new("delphi", "Delphi", Arc::new(delphi::Delphi))
+        },
+        Language {
+            aliases: strings(&["vb.net"]),
+            extensions: strings(&["vb", "bas", "vbs"]),
+            mime_types: strings(&["text/x-vb", "text/vbscript"]),
+            icon: Some(Icon::new("images/viauslbasic", "svg")),
+            hints: hints(&[
+                ("End Sub", 3.0),
+                ("Dim ", 2.0),
+                ("End If", 2.0),
+                ("Sub ", 1.0),
+                ("Then", 0.5),
+            ]),
+            ..Language::new("vb", "VisualBasic", Arc::new(vb::Vb))
+        },
+        Language {
+            extensions: strings(&["java"]),
+            mime_types: strings(&["text/x-java", "text/x-java-source"]),
+            icon: Some(Icon::new("images/java", "png")),
+            hints: hints(&[
+                ("public static void main", 3.0),
+                ("System.out", 3.0),
+                ("import java.", 3.0),
+                ("@Override", 2.0),
+                ("private final", 1.0),
+                ("String[]", 1.0),
+                ("extends ", 0.5),
+            ]),
+            ..Language::new("java", "Java", Arc::new(java::JAVA))
+        },
+        Language {
+            aliases: strings(&["jscript", "javascript", "jsx"]),
+            extensions: strings(&["js", "mjs", "cjs", "jsx"]),
+            mime_types: strings(&["text/javascript", "application/javascript"]),
+            icon: Some(Icon::new("images/javascript", "png")),
+            tab_width: 2,
+            interpreters: strings(&["node", "nodejs", "deno"]),
+            hints: hints(&[
+                ("console.log", 2.0),
+                ("document.", 1.5),
+                ("require(", 1.5),
+                ("===", 1.5),
+                ("function ", 1.0),
+                ("const ", 0.7),
+                ("let ", 0.5),
+                ("var ", 0.5),
+                ("=>", 0.5),
+            ]),
+            ..Language::new("js", "JavaScript", Arc::new(jscript::JAVASCRIPT))
+        },
+        Language {
+            aliases: strings(&["typescript"]),
+            extensions: strings(&["ts", "mts", "cts"]),
+            mime_types: strings(&["text/typescript", "application/typescript"]),
+            icon: Some(Icon::new("images/typescript", "svg")),
+            tab_width: 2,
+            interpreters: strings(&["ts-node", "tsx", "deno", "bun"]),
+            hints: hints(&[
+                ("interface ", 1.5),
+                (": string", 2.0),
+                (": number", 2.0),
+                (": boolean", 2.0),
+                ("import type ", 3.0),
+                ("export type ", 2.0),
+                (" as const", 2.0),
+                ("readonly ", 1.5),
+                (" satisfies ", 2.0),
+                ("console.log", 1.0),
+                ("=>", 0.5),
+            ]),
+            ..Language::new("ts", "TypeScript", Arc::new(jscript::TYPESCRIPT))
+        },
+        Language {
+            extensions: strings(&["tsx"]),
+            mime_types: strings(&["text/tsx"]),
+            icon: Some(Icon::new("images/typescript", "svg")),
+            tab_width: 2,
+            hints: hints(&[
+                (": React.FC", 3.0),
+                ("Props>", 2.0),
+                ("useState<", 3.0),
+                ("className=", 1.5),
+                ("interface ", 1.0),
+                (": string", 1.5),
+                ("</", 0.5),
+                ("/>", 0.5),
+            ]),
+            ..Language::new("tsx", "TSX", Arc::new(jscript::TSX))
+        },
+        Language {
+            aliases: strings(&["rb", "rails", "ror"]),
+            extensions: strings(&["rb", "rake", "gemspec"]),
+            mime_types: strings(&["text/x-ruby", "application/x-ruby"]),
+            icon: Some(Icon::new("images/ruby", "png")),
+            tab_width: 2,
+            interpreters: strings(&["ruby", "irb"]),
+            hints: hints(&[
+                ("attr_accessor", 3.0),
+                ("elsif ", 3.0),
+                ("puts ", 2.0),
+                (".each do", 2.0),
+                ("require '", 1.5),
+                ("def ", 1.0),
+                ("end\n", 0.5),
+            ]),
+            ..Language::new("ruby", "Ruby", Arc::new(ruby::Ruby))
+        },
+        Language {
+            extensions: strings(&["sql"]),
+            mime_types: strings(&["application/sql", "text/x-sql"]),
+            icon: Some(Icon::new("images/sql", "png")),
+            hints: hints(&[
+                ("INSERT INTO", 3.0),
+                ("CREATE TABLE", 3.0),
+                ("SELECT ", 2.0),
+                ("select ", 2.0),
+                ("FROM ", 1.0),
+                ("from ", 0.5),
+                ("WHERE ", 1.0),
+                ("where ", 0.5),
+            ]),
+            ..Language::new("sql", "SQL", Arc::new(sql::Sql))
+        },
+        Language {
+            aliases: strings(&["html", "xhtml", "xslt"]),
+            extensions: strings(&["xml", "html", "htm", "xhtml", "xsl", "xslt", "svg"]),
+            mime_types: strings(&[
+                "text/html",
+                "text/xml",
+                "application/xml",
+                "application/xhtml+xml",
+                "image/svg+xml",
+            ]),
+            icon: Some(Icon::new("images/html", "png")),
+            tab_width: 2,
+            hints: hints(&[
+                ("<?xml", 3.0),
+                ("<!DOCTYPE", 3.0),
+                ("<div", 2.0),
+                ("</", 1.5),
+                ("/>", 1.0),
+            ]),
+            ..Language::new("xml", "HTML / XML", Arc::new(xml::Xml))
+        },
+        Language {
+            extensions: strings(&["php", "phtml"]),
+            mime_types: strings(&["application/x-httpd-php", "text/x-php"]),
+            icon: Some(Icon::new("images/php", "png")),
+            interpreters: strings(&["php"]),
+            hints: hints(&[
+                ("<?php", 5.0),
+                ("$this->", 3.0),
+                ("echo ", 1.0),
+                ("function ", 0.5),
+                ("=>", 0.3),
+                ("$", 0.3),
+            ]),
+            ..Language::new("php", "PHP", Arc::new(php::PHP))
+        },
+        Language {
+            aliases: strings(&["rs"]),
+            extensions: strings(&["rs"]),
+            mime_types: strings(&["text/rust", "text/x-rust"]),
+            icon: Some(Icon::new("images/rust", "svg")),
+            hints: hints(&[
+                ("fn main()", 3.0),
+                ("println!", 3.0),
+                ("let mut ", 3.0),
+                ("impl ", 2.0),
+                ("pub fn ", 2.0),
+                ("#[derive(", 3.0),
+                ("&self", 1.5),
+                ("::new(", 1.0),
+                ("-> ", 0.5),
+            ]),
+            ..Language::new("rust", "Rust", Arc::new(rust::Rust))
+        },
+        Language {
+            aliases: strings(&["golang"]),
+            extensions: strings(&["go"]),
+            mime_types: strings(&["text/x-go"]),
+            icon: Some(Icon::new("images/go", "svg")),
+            tab_width: 8,
+            hints: hints(&[
+                ("package main", 4.0),
+                ("func main()", 3.0),
+                ("fmt.Print", 3.0),
+                (":= ", 2.0),
+                ("func (", 2.0),
+                ("if err != nil", 4.0),
+                ("import (", 2.0),
+                ("chan ", 1.0),
+            ]),
+            ..Language::new("go", "Go", Arc::new(go::Go))
+        },
+        Language {
+            aliases: strings(&["sh", "shell", "zsh"]),
+            extensions: strings(&["sh", "bash", "zsh", "ksh", "bashrc", "zshrc", "profile"]),
+            mime_types: strings(&["application/x-sh", "text/x-sh", "text/x-shellscript"]),
+            icon: Some(Icon::new("images/bash", "svg")),
+            tab_width: 2,
+            interpreters: strings(&["sh", "bash", "zsh", "ksh", "dash"]),
+            hints: hints(&[
+                ("#!/bin/", 3.0),
+                ("fi\n", 2.0),
+                ("esac", 3.0),
+                ("; then", 3.0),
+                ("; do", 2.0),
+                ("done", 1.0),
+                ("${", 1.0),
+                ("$(", 1.0),
+                ("echo ", 1.0),
+            ]),
+            ..Language::new("bash", "Bash / Shell", Arc::new(shell::Shell))
+        },
+        Language {
+            aliases: strings(&["ps", "ps1", "pwsh", "posh"]),
+            extensions: strings(&["ps1", "psm1", "psd1"]),
+            mime_types: strings(&["text/x-powershell", "application/x-powershell"]),
+            icon: Some(Icon::new("images/powershell", "svg")),
+            interpreters: strings(&["pwsh", "powershell"]),
+            hints: hints(&[
+                ("Write-Host", 3.0),
+                ("Get-", 2.0),
+                ("Set-", 1.5),
+                ("param(", 2.0),
+                ("$_", 1.5),
+                ("-eq ", 1.5),
+                ("-ne ", 1.0),
+                ("[string]", 1.5),
+                ("$env:", 2.0),
+            ]),
+            ..Language::new("powershell", "PowerShell", Arc::new(powershell::PowerShell))
+        },
+        Language {
+            aliases: strings(&["shell-session", "terminal", "shellsession"]),
+            extensions: strings(&["sh-session"]),
+            icon: Some(Icon::new("images/bash", "svg")),
+            hints: hints(&[("$ ", 1.5), ("PS> ", 2.0), ("PS C:\\", 2.0)]),
+            ..Language::new("console", "Console session", Arc::new(console::Console))
+        },
+        Language {
+            extensions: strings(&["json", "jsonc", "geojson", "webmanifest"]),
+            mime_types: strings(&["application/json", "application/manifest+json"]),
+            icon: Some(Icon::new("images/json", "svg")),
+            tab_width: 2,
+            hints: hints(&[
+                ("\": ", 2.0),
+                ("\": {", 2.0),
+                ("\": [", 2.0),
+                ("\",\n", 1.0),
+                ("null", 0.5),
+            ]),
+            ..Language::new("json", "JSON", Arc::new(json::JSON))
+        },
+        Language {
+            extensions: strings(&["json5"]),
+            mime_types: strings(&["application/json5"]),
+            icon: Some(Icon::new("images/json", "svg")),
+            tab_width: 2,
+            hints: hints(&[("Infinity", 1.0), ("NaN", 0.5)]),
+            ..Language::new("json5", "JSON5", Arc::new(json::JSON5))
+        },
+        Language {
+            aliases: strings(&["yml"]),
+            extensions: strings(&["yaml", "yml"]),
+            mime_types: strings(&["application/yaml", "application/x-yaml", "text/yaml"]),
+            icon: Some(Icon::new("images/yaml", "svg")),
+            tab_width: 2,
+            hints: hints(&[
+                ("---\n", 2.0),
+                ("\n- ", 1.5),
+                (": |\n", 2.0),
+                (": >\n", 1.5),
+                ("  - name: ", 2.0),
+                (": &", 1.5),
+                ("<<: *", 3.0),
+            ]),
+            ..Language::new("yaml", "YAML", Arc::new(yaml::Yaml))
+        },
+        Language {
+            extensions: strings(&["toml"]),
+            mime_types: strings(&["application/toml"]),
+            icon: Some(Icon::new("images/toml", "svg")),
+            hints: hints(&[
+                ("[[", 1.5),
+                ("[package]", 4.0),
+                ("[dependencies]", 4.0),
+                ("\"\"\"", 0.5),
+                (" = \"", 1.0),
+                (" = [", 1.0),
+                (" = {", 1.0),
+            ]),
+            ..Language::new("toml", "TOML", Arc::new(toml::Toml))
+        },
+        Language {
+            aliases: strings(&["cfg", "conf", "properties"]),
+            extensions: strings(&[
+                "ini",
+                "cfg",
+                "conf",
+                "properties",
+                "editorconfig",
+                "gitconfig",
+            ]),
+            mime_types: strings(&["text/x-ini", "text/x-java-properties"]),
+            icon: Some(Icon::new("images/ini", "svg")),
+            hints: hints(&[("\n[", 1.0), ("\n; ", 1.5)]),
+            ..Language::new("ini", "INI", Arc::new(ini::Ini))
+        },
+        Language {
+            aliases: strings(&["env"]),
+            extensions: strings(&["env"]),
+            icon: Some(Icon::new("images/dotenv", "svg")),
+            hints: hints(&[
+                ("export ", 0.5),
+                ("_URL=", 2.0),
+                ("_KEY=", 2.0),
+                ("_HOST=", 1.5),
+            ]),
+            ..Language::new("dotenv", ".env", Arc::new(dotenv::Dotenv))
+        },
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::highlighter::testing::{assert_well_formed, highlight};
+    use crate::highlighter::{lines, tokenize};
+
+    /// A snippet per language, spanning its multi line constructs.
+    const SAMPLES: &[(&str, &str)] = &[
+        (
+            "cpp",
+            "#include <stdio.h>\n/* block\n   comment */\nint main(void) {\n    DWORD n = 0x1f; // count\n    printf(\"%d\\n\", n);\n    return 0;\n}\n",
+        ),
+        (
+            "csharp",
+            "using System;\n#region Demo\n/// <summary>Docs</summary>\npublic class Point {\n    public int X { get; set; } = 1;\n    string s = @\"C:\\path\"\"quoted\"\"\";\n    string t = $\"{X} apples\";\n}\n#endregion\n",
+        ),
+        (
+            "python",
+            "@decorator\ndef greet(name):\n    \"\"\"Docstring\n    over lines.\"\"\"\n    if name is None:\n        return 'anonymous'  # fallback\n    return \"hi \" + name * 2\n",
+        ),
+        (
+            "css",
+            "@media screen and (max-width: 600px) {\n  /* narrow\n     screens */\n  body > .nav:hover, #id::after {\n    color: #fff !important;\n    margin: 0 auto 1.5em;\n    background: url(\"a.png\") no-repeat;\n  }\n}\n",
+        ),
+        (
+            "delphi",
+            "unit Demo;\n{ curly\n  comment }\n(* paren\n   comment *)\nprocedure Run(const S: string);\nbegin\n  WriteLn('it''s ', $FF, #13); // done\nend;\n",
+        ),
+        (
+            "vb",
+            "' comment\nImports System\nModule Demo\n    Sub Main()\n        Dim s As String = \"say \"\"hi\"\"\"\n        #If DEBUG Then\n        Console.WriteLine(s & 42)\n        #End If\n    End Sub\nEnd Module\n",
+        ),
+        (
+            "java",
+            "package demo;\n/**\n * Docs.\n */\n@Override\npublic final class Main {\n    public static void main(String[] args) {\n        char c = '\\'';\n        long n = 42L + 0xFF;\n    }\n}\n",
+        ),
+        (
+            "js",
+            "// comment\nconst re = /a\\/b[/]c/gi;\nlet s = `template ${a + `nested ${b}`}\nsecond line`;\n/* block\n comment */\nfunction f(x) { return x / 2 / 3; }\n",
+        ),
+        (
+            "ruby",
+            "# comment\nclass Greeter < Base\n  attr_reader :name\n  def greet(who = \"world\")\n    puts \"Hello #{who}\"\n    @count += 1\n  end\nend\n=begin\nblock comment\n=end\n",
+        ),
+        (
+            "sql",
+            "-- comment\n/* block\n   comment */\nSELECT name, COUNT(*) AS n\nFROM users u\nWHERE u.name LIKE 'o''brien' AND id > 10\nGROUP BY name;\n",
+        ),
+        (
+            "xml",
+            "<?xml version=\"1.0\"?>\n<!-- a\n  comment -->\n<root attr=\"value\" other='x'>\n  <![CDATA[ <raw>\n  data ]]>\n  <child/>\n</root>\n",
+        ),
+        (
+            "php",
+            "<html>\n<?php\n/* block\n   comment */\n$name = \"world\";\necho \"Hello $name\";\n$text = <<<EOT\nheredoc $name\nEOT;\n?>\n</html>\n",
+        ),
+        (
+            "rust",
+            "#![allow(dead_code)]\n/* outer /* nested\n   */ still comment */\n#[derive(Debug,\n    Clone)]\nstruct Wrapper<'a>(&'a str);\nfn main() {\n    let r#type = r#\"raw \"quoted\"\n    text\"#;\n    println!(\"{} {}\", b'x', 0xffu8 as usize);\n}\n",
+        ),
+        (
+            "go",
+            "//go:build linux\npackage main\n\n/* block\n   comment */\ntype User struct {\n\tName string `json:\"name\" db:\"user_name\"`\n}\n\nfunc main() {\n\tquery := `SELECT *\n\tFROM users`\n\tn := len(query) + 1_000 + 0x1F\n\tfmt.Println(n, 'x', nil)\n}\n",
+        ),
+        (
+            "ts",
+            "import { Injectable } from './di';\n\n@Injectable()\nexport abstract class Store<T> implements Disposable {\n    private readonly items: Map<string, T> = new Map();\n    /* block\n       comment */\n    find(id: string): T | undefined {\n        const key = <string>id.replace(/\\s+/g, '');\n        return this.items.get(`${key}`) as T;\n    }\n}\n",
+        ),
+        (
+            "tsx",
+            "export function List({ items }: { items: string[] }) {\n    return (\n        <ul className=\"list\">\n            {items.map((item) => (\n                <li key={item}>{`#${item}`}</li>\n            ))}\n            <br/>\n        </ul>\n    );\n}\n",
+        ),
+        (
+            "bash",
+            "#!/usr/bin/env bash\nset -euo pipefail\n# comment\nname=\"${1:-world}\"\nfor f in *.txt; do\n    echo \"$f has $(wc -l < \"$f\") lines\"\ndone\ncat <<-EOF\n\tHello $name\n\tEOF\nprintf '%s\\n' \"multi\nline\" $((1 << 2))\n",
+        ),
+        (
+            "powershell",
+            "<# block\n   comment #>\nparam([string]$Path = $env:TEMP)\n# comment\n$files = Get-ChildItem -Path $Path -Filter *.log\nforeach ($file in $files) {\n    if ($file.Length -gt 1KB) {\n        Write-Host \"$($file.Name) is `\"big`\"\"\n    }\n}\n$text = @\"\nHello $Path\n\"@\n",
+        ),
+        (
+            "console",
+            "$ cargo build --release\n   Compiling demo v0.1.0\n    Finished release [optimized]\nme@box:~/src$ echo \"two \\\nlines\" | \\\n    wc -l\n1\nPS C:\\src> Get-Item . | Select-Object Name\nName\n----\nsrc\n",
+        ),
+        (
+            "json",
+            "{\n  \"name\": \"demo\",\n  \"version\": 1.5e3,\n  \"tags\": [\"a\", \"b\\\"c\"],\n  \"private\": true,\n  \"parent\": null\n}\n",
+        ),
+        (
+            "json5",
+            "// comment\n{\n  /* block\n     comment */\n  unquoted: 'single',\n  hex: 0xFF,\n  signed: -Infinity,\n  point: .5,\n  trailing: [1, 2,],\n}\n",
+        ),
+        (
+            "yaml",
+            "%YAML 1.2\n---\n# comment\nname: demo\nbase: &base\n  enabled: true\n  ratio: 0.5\nderived:\n  <<: *base\n  tags: !!set { a, b }\n  script: |\n    echo one\n    echo two\n  quoted: \"multi\n    line\"\n...\n",
+        ),
+        (
+            "toml",
+            "# comment\ntitle = \"demo\"\n\n[owner]\nname.first = 'Tom'\ndob = 1979-05-27 07:32:00-08:00\n\n[[products]]\nsizes = [\n  1_000,\n  +inf,\n]\ntext = \"\"\"\nmulti \"quoted\"\nline\"\"\"\"\n",
+        ),
+        (
+            "ini",
+            "; comment\n[database]\nhost = localhost  ; inline\nport = 5432\nenabled = yes\npath = \"C:\\\\data\"\n# another comment\n",
+        ),
+        (
+            "dotenv",
+            "# comment\nexport NAME=demo\nURL=\"http://${HOST}:$PORT/path\"  # inline\nKEY='-----BEGIN KEY-----\nsecret\n-----END KEY-----'\nEMPTY=\n",
+        ),
+    ];
+
+    fn language(id: &str) -> Language {
+        builtin()
+            .into_iter()
+            .find(|language| language.id == id)
+            .unwrap_or_else(|| panic!("no language `{id}`"))
+    }
+
+    #[test]
+    fn every_language_has_a_sample() {
+        for language in builtin() {
+            assert!(
+                SAMPLES.iter().any(|(id, _)| *id == language.id),
+                "no sample for `{}`",
+                language.id
+            );
+        }
+    }
+
+    #[test]
+    fn samples_highlight_something() {
+        for (id, sample) in SAMPLES {
+            let tokens = highlight(language(id).brush.as_ref(), sample);
+            assert!(tokens.len() > 3, "{id}: {tokens:?}");
+        }
+    }
+
+    /// Brushes mustn't trip over code of other languages, which is what detection and a wrong
+    /// choice in the picker feed them.
+    #[test]
+    fn every_brush_handles_every_sample() {
+        for language in builtin() {
+            for (id, sample) in SAMPLES {
+                let tokens = tokenize(sample, language.brush.as_ref());
+                assert_well_formed(sample, &tokens);
+                // cut off mid construct, and with Windows line breaks
+                let half = &sample[..sample.floor_char_boundary(sample.len() / 2)];
+                assert_well_formed(half, &tokenize(half, language.brush.as_ref()));
+                let crlf = sample.replace('\n', "\r\n");
+                let tokens = tokenize(&crlf, language.brush.as_ref());
+                assert_well_formed(&crlf, &tokens);
+                assert!(
+                    tokens
+                        .iter()
+                        .all(|token| !crlf[token.range.clone()].contains('\r')),
+                    "{} highlights a line break in the {id} sample",
+                    language.id
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn lines_cover_the_code() {
+        for (id, sample) in SAMPLES {
+            let tokens = tokenize(sample, language(id).brush.as_ref());
+            let lines = lines(sample, &tokens);
+            let text = lines
+                .iter()
+                .map(|line| line.iter().map(|(_, text)| *text).collect::<String>())
+                .collect::<Vec<_>>()
+                .join("\n");
+            assert_eq!(text, *sample, "{id}");
+            let highlighted = lines.iter().flatten().filter(|(kind, _)| kind.is_some());
+            assert_eq!(highlighted.count(), tokens.len(), "{id}");
+        }
+    }
+}
@@ -0,0 +1,386 @@
+use crate::highlighter::scanner::{contains_ignore_case, is_word_char, Scanner};
+use crate::highlighter::{Brush, LineState, Token, TokenKind};
+
+/// Commands, `depth` counting the parentheses opened since the `$(` they are substituted by.
+const SCRIPT: u32 = 0;
+const BLOCK_COMMENT: u32 = 1;
+const SINGLE_QUOTED: u32 = 2;
+const DOUBLE_QUOTED: u32 = 3;
+/// A `@"` here-string, up to the line starting with `"@`.
+const HERE_STRING: u32 = 4;
+/// A `@'` here-string, up to the line starting with `'@`, leaving `$` unexpanded.
+const QUOTED_HERE_STRING: u32 = 5;
+
+const KEYWORDS: &[&str] = &[
+    "begin",
+    "break",
+    "catch",
+    "class",
+    "continue",
+    "data",
+    "define",
+    "do",
+    "dynamicparam",
+    "else",
+    "elseif",
+    "end",
+    "enum",
+    "exit",
+    "filter",
+    "finally",
+    "for",
+    "foreach",
+    "from",
+    "function",
+    "hidden",
+    "if",
+    "in",
+    "param",
+    "process",
+    "return",
+    "static",
+    "switch",
+    "throw",
+    "trap",
+    "try",
+    "until",
+    "using",
+    "var",
+    "while",
+    "workflow",
+];
+
+/// Operators, without their leading `-`.
+const OPERATORS: &[&str] = &[
+    "and",
+    "as",
+    "band",
+    "bnot",
+    "bor",
+    "bxor",
+    "ccontains",
+    "ceq",
+    "cge",
+    "cgt",
+    "cle",
+    "clike",
+    "clt",
+    "cmatch",
+    "cne",
+    "cnotcontains",
+    "cnotlike",
+    "cnotmatch",
+    "contains",
+    "creplace",
+    "csplit",
+    "eq",
+    "f",
+    "ge",
+    "gt",
+    "icontains",
+    "ieq",
+    "ilike",
+    "imatch",
+    "in",
+    "is",
+    "isnot",
+    "join",
+    "le",
+    "like",
+    "lt",
+    "match",
+    "ne",
+    "not",
+    "notcontains",
+    "notin",
+    "notlike",
+    "notmatch",
+    "or",
+    "replace",
+    "shl",
+    "shr",
+    "split",
+    "xor",
+];
+
+/// The automatic variables holding constants.
+const VALUES: &[&str] = &["$false", "$null", "$true"];
+
+pub(crate) struct PowerShell;
+
+/// Whether a variable or a `$(` subexpression starts at the cursor, rather than a literal `$`.
+fn at_variable(s: &Scanner) -> bool {
+    s.peek() == Some('$')
+        && s.peek_nth(1)
+            .is_some_and(|c| is_word_char(c) || "{($?^".contains(c))
+}
+
+/// Consumes the variable at the cursor, `$env:Path` and `${odd name}` included, or the `$(`
+/// opening a subexpression, which enters script until the matching `)`.
+fn eat_variable(s: &mut Scanner, state: &mut LineState) {
+    let start = s.pos();
+    if s.eat("$(") {
+        s.token(TokenKind::Variable, start);
+        state.enter(SCRIPT, 0);
+        return;
+    }
+    if s.eat("${") {
+        s.eat_until("}");
+    } else {
+        s.bump();
+        if s.eat_word() {
+            // a scope or drive, `$script:count` or `$env:HOME`
+            if s.peek() == Some(':') && s.peek_nth(1).is_some_and(is_word_char) {
+                s.bump();
+                s.eat_word();
+            }
+        } else {
+            s.bump();
+        }
+    }
+    if contains_ignore_case(VALUES, s.slice(start)) {
+        s.token(TokenKind::Value, start);
+    } else {
+        s.token(TokenKind::Variable, start);
+    }
+}
+
+/// Consumes a single quoted string's body, where `''` is a quote, returning whether it closed.
+fn eat_single_quoted(s: &mut Scanner) -> bool {
+    while s.eat_until("'") {
+        if !s.eat_char('\'') {
+            return true;
+        }
+    }
+    false
+}
+
+/// Consumes the body of a double quoted string, or of a here-string when `closing` is `None`, up
+/// to the closing quote or a subexpression entering script.
+fn expanding(s: &mut Scanner, state: &mut LineState, closing: Option<char>) {
+    let mode = state.mode;
+    let mut start = s.pos();
+    while let Some(c) = s.peek() {
+        if c == '`' || closing == Some('"') && s.starts_with("\"\"") {
+            s.bump();
+            s.bump();
+        } else if Some(c) == closing {
+            s.bump();
+            s.token(TokenKind::String, start);
+            state.leave();
+            return;
+        } else if at_variable(s) {
+            s.token(TokenKind::String, start);
+            eat_variable(s, state);
+            if state.mode != mode {
+                return;
+            }
+            start = s.pos();
+        } else {
+            s.bump();
+        }
+    }
+    s.token(TokenKind::String, start);
+}
+
+/// Length of the `[type]` literal `rest` starts with, `None` when the brackets hold anything else.
+fn type_len(rest: &str) -> Option<usize> {
+    let inner = rest.strip_prefix('[')?;
+    if !inner.starts_with(|c: char| c.is_alphabetic()) {
+        return None;
+    }
+    let name = inner.len()
+        - inner
+            .trim_start_matches(|c: char| is_word_char(c) || matches!(c, '.' | '[' | ']' | ','))
+            .len();
+    // generic arguments nest brackets, the type ends with the last one
+    let end = inner[..name].rfind(']')?;
+    Some(end + 2)
+}
+
+impl PowerShell {
+    fn script(s: &mut Scanner, state: &mut LineState) {
+        while state.mode == SCRIPT && !s.is_done() {
+            let start = s.pos();
+            let word_start = s
+                .prev()
+                .is_none_or(|c| c.is_whitespace() || ";|(){}=,".contains(c));
+            // brackets after a value index it rather than naming a type
+            let indexing = s
+                .prev()
+                .is_some_and(|c| is_word_char(c) || c == ']' || c == ')');
+
+            if s.eat("<#") {
+                if !s.eat_until("#>") {
+                    state.enter(BLOCK_COMMENT, 0);
+                }
+                s.token(TokenKind::Comment, start);
+            } else if word_start && s.eat_char('#') {
+                s.eat_to_end();
+                s.token(TokenKind::Comment, start);
+            } else if (s.starts_with("@\"") || s.starts_with("@'"))
+                && s.rest()[2..].trim().is_empty()
+            {
+                let mode = if s.starts_with("@'") {
+                    QUOTED_HERE_STRING
+                } else {
+                    HERE_STRING
+                };
+                s.eat_to_end();
+                s.token(TokenKind::String, start);
+                state.enter(mode, 0);
+            } else if s.eat_char('\'') {
+                if !eat_single_quoted(s) {
+                    state.enter(SINGLE_QUOTED, 0);
+                }
+                s.token(TokenKind::String, start);
+            } else if s.eat_char('"') {
+                s.token(TokenKind::String, start);
+                state.enter(DOUBLE_QUOTED, 0);
+            } else if at_variable(s) {
+                eat_variable(s, state);
+            } else if s.eat_char('`') {
+                s.bump();
+            } else if s.eat_char('(') {
+                state.depth += 1;
+            } else if s.eat_char(')') {
+                if state.depth > 0 {
+                    state.depth -= 1;
+                } else if !state.stack.is_empty() {
+                    s.token(TokenKind::Variable, start);
+                    state.leave();
+                }
+            } else if let Some(len) = type_len(s.rest()).filter(|_| !indexing) {
+                s.eat(&s.rest()[..len]);
+                s.token(TokenKind::DataType, start);
+            } else if word_start
+                && s.peek() == Some('-')
+                && s.peek_nth(1).is_some_and(char::is_alphabetic)
+            {
+                s.bump();
+                s.eat_word();
+                if contains_ignore_case(OPERATORS, &s.slice(start)[1..]) {
+                    s.token(TokenKind::Operator, start);
+                }
+            } else if s.eat_number() {
+                s.token(TokenKind::Number, start);
+            } else if word_start && s.eat_while(|c| is_word_char(c) || c == '-') {
+                let word = s.slice(start);
+                // cmdlets are named verb-noun, `Get-ChildItem`
+                let cmdlet = word.split_once('-').is_some_and(|(verb, noun)| {
+                    verb.chars().all(char::is_alphabetic) && noun.starts_with(char::is_alphabetic)
+                });
+                if cmdlet {
+                    s.token(TokenKind::Function, start);
+                } else if contains_ignore_case(KEYWORDS, word) {
+                    s.token(TokenKind::Keyword, start);
+                }
+            } else {
+                s.bump();
+            }
+        }
+    }
+
+    fn here_string(s: &mut Scanner, state: &mut LineState) {
+        let closing = if state.mode == HERE_STRING {
+            "\"@"
+        } else {
+            "'@"
+        };
+        if s.pos() == 0 && s.starts_with(closing) {
+            s.eat(closing);
+            s.token(TokenKind::String, 0);
+            state.leave();
+        } else if state.mode == QUOTED_HERE_STRING {
+            let start = s.pos();
+            s.eat_to_end();
+            s.token(TokenKind::String, start);
+        } else {
+            expanding(s, state, None);
+        }
+    }
+}
+
+impl Brush for PowerShell {
+    fn tokenize_line(&self, line: &str, state: &mut LineState, tokens: &mut Vec<Token>) {
+        let mut s = Scanner::new(line, tokens);
+
+        while !s.is_done() {
+            let start = s.pos();
+            match state.mode {
+                BLOCK_COMMENT => {
+                    if s.eat_until("#>") {
+                        state.leave();
+                    }
+                    s.token(TokenKind::Comment, start);
+                }
+                SINGLE_QUOTED => {
+                    if eat_single_quoted(&mut s) {
+                        state.leave();
+                    }
+                    s.token(TokenKind::String, start);
+                }
+                DOUBLE_QUOTED => expanding(&mut s, state, Some('"')),
+                HERE_STRING | QUOTED_HERE_STRING => PowerShell::here_string(&mut s, state),
+                _ => PowerShell::script(&mut s, state),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::highlighter::testing::highlight;
+
+    #[test]
+    fn cmdlets_operators_and_types() {
+        assert_eq!(
+            highlight(
+                &PowerShell,
+                "if ($x -GT [int[]]$a[0]) { Get-Item -Path $env:HOME }"
+            ),
+            [
+                (TokenKind::Keyword, "if"),
+                (TokenKind::Variable, "$x"),
+                (TokenKind::Operator, "-GT"),
+                (TokenKind::DataType, "[int[]]"),
+                (TokenKind::Variable, "$a"),
+                (TokenKind::Number, "0"),
+                (TokenKind::Function, "Get-Item"),
+                (TokenKind::Variable, "$env:HOME"),
+            ]
+        );
+    }
+
+    #[test]
+    fn strings_expand_subexpressions() {
+        assert_eq!(
+            highlight(&PowerShell, "\"a `\"$($b + 'c''d')\" + $NULL"),
+            [
+                (TokenKind::String, "\""),
+                (TokenKind::String, "a `\""),
+                (TokenKind::Variable, "$("),
+                (TokenKind::Variable, "$b"),
+                (TokenKind::String, "'c''d'"),
+                (TokenKind::Variable, ")"),
+                (TokenKind::String, "\""),
+                (TokenKind::Value, "$NULL"),
+            ]
+        );
+    }
+
+    #[test]
+    fn here_strings_and_block_comments_span_lines() {
+        assert_eq!(
+            highlight(&PowerShell, "<# a\n#> @'\n$b \"@\n'@"),
+            [
+                (TokenKind::Comment, "<# a"),
+                (TokenKind::Comment, "#>"),
+                (TokenKind::String, "@'"),
+                (TokenKind::String, "$b \"@"),
+                (TokenKind::String, "'@"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,100 @@
+use crate::highlighter::scanner::{contains, Scanner};
+use crate::highlighter::{Brush, LineState, Token, TokenKind};
+
+const TRIPLE_QUOTED: u32 = 1;
+
+const KEYWORDS: &[&str] = &[
+    "and", "assert", "break", "class", "continue", "def", "del", "elif", "else", "except", "exec",
+    "finally", "for", "from", "global", "if", "import", "in", "is", "lambda", "not", "or", "pass",
+    "print", "raise", "return", "try", "while", "yield",
+];
+
+const SPECIAL: &[&str] = &["False", "None", "True", "class_", "cls", "self"];
+
+pub(crate) struct Python;
+
+impl Brush for Python {
+    fn tokenize_line(&self, line: &str, state: &mut LineState, tokens: &mut Vec<Token>) {
+        let mut s = Scanner::new(line, tokens);
+
+        // shCore highlighted triple quoted strings as comments, being mostly docstrings
+        if state.mode == TRIPLE_QUOTED {
+            let closed = s.eat_until(&state.delimiter);
+            s.token(TokenKind::Comment, 0);
+            if !closed {
+                return;
+            }
+            state.mode = 0;
+            state.delimiter.clear();
+        }
+
+        while !s.is_done() {
+            let start = s.pos();
+
+            if s.eat_char('#') {
+                s.eat_to_end();
+                s.token(TokenKind::Comment, start);
+            } else if s.at_line_start() && s.peek() == Some('@') {
+                s.bump();
+                s.eat_word();
+                s.token(TokenKind::Decorator, start);
+            } else if let Some(quotes) = ["\"\"\"", "'''"].into_iter().find(|q| s.starts_with(q)) {
+                s.eat(quotes);
+                if !s.eat_until(quotes) {
+                    state.mode = TRIPLE_QUOTED;
+                    state.delimiter = quotes.to_string();
+                }
+                s.token(TokenKind::Comment, start);
+            } else if let Some(quote) = s.peek().filter(|c| *c == '"' || *c == '\'') {
+                s.bump();
+                s.eat_quoted(quote, Some('\\'));
+                s.token(TokenKind::String, start);
+            } else if s.eat_number() {
+                s.token(TokenKind::Number, start);
+            } else if s.at_word_boundary() && s.eat_word() {
+                let word = s.slice(start);
+                if contains(KEYWORDS, word) {
+                    s.token(TokenKind::Keyword, start);
+                } else if contains(SPECIAL, word) {
+                    s.token(TokenKind::Special, start);
+                }
+            } else {
+                s.bump();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::highlighter::testing::highlight;
+
+    #[test]
+    fn docstrings_span_lines() {
+        assert_eq!(
+            highlight(&Python, "def f():\n    '''one\n    two''' + \"\"\"x\"\"\""),
+            [
+                (TokenKind::Keyword, "def"),
+                (TokenKind::Comment, "'''one"),
+                (TokenKind::Comment, "    two'''"),
+                (TokenKind::Comment, "\"\"\"x\"\"\""),
+            ]
+        );
+    }
+
+    #[test]
+    fn decorators_strings_and_specials() {
+        assert_eq!(
+            highlight(&Python, "@cache\nreturn self.x or 'a\\'b' # done"),
+            [
+                (TokenKind::Decorator, "@cache"),
+                (TokenKind::Keyword, "return"),
+                (TokenKind::Special, "self"),
+                (TokenKind::Keyword, "or"),
+                (TokenKind::String, "'a\\'b'"),
+                (TokenKind::Comment, "# done"),
+            ]
+        );
+    }
+}
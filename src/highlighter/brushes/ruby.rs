@@ -0,0 +1,179 @@
+use crate::highlighter::scanner::{contains, Scanner};
+use crate::highlighter::{Brush, LineState, Token, TokenKind};
+
+const EMBEDDED_DOCUMENT: u32 = 1;
+
+const BUILTINS: &[&str] = &[
+    "Array",
+    "Bignum",
+    "Binding",
+    "Class",
+    "Continuation",
+    "Dir",
+    "Exception",
+    "FalseClass",
+    "File",
+    "File::Stat",
+    "Fixnum",
+    "Fload",
+    "Hash",
+    "IO",
+    "Integer",
+    "MatchData",
+    "Method",
+    "Module",
+    "NilClass",
+    "Numeric",
+    "Object",
+    "Proc",
+    "Range",
+    "Regexp",
+    "String",
+    "Struct::TMS",
+    "Symbol",
+    "Thread",
+    "ThreadGroup",
+    "Time",
+    "TrueClass",
+];
+
+const KEYWORDS: &[&str] = &[
+    "BEGIN",
+    "END",
+    "alias",
+    "and",
+    "begin",
+    "break",
+    "case",
+    "class",
+    "def",
+    "define_method",
+    "defined",
+    "do",
+    "each",
+    "else",
+    "elsif",
+    "end",
+    "ensure",
+    "false",
+    "for",
+    "if",
+    "in",
+    "module",
+    "new",
+    "next",
+    "nil",
+    "not",
+    "or",
+    "raise",
+    "redo",
+    "rescue",
+    "retry",
+    "return",
+    "self",
+    "super",
+    "then",
+    "throw",
+    "true",
+    "undef",
+    "unless",
+    "until",
+    "when",
+    "while",
+    "yield",
+];
+
+pub(crate) struct Ruby;
+
+impl Brush for Ruby {
+    fn tokenize_line(&self, line: &str, state: &mut LineState, tokens: &mut Vec<Token>) {
+        let mut s = Scanner::new(line, tokens);
+
+        if state.mode == EMBEDDED_DOCUMENT {
+            if line.starts_with("=end") {
+                state.mode = 0;
+            }
+            s.eat_to_end();
+            s.token(TokenKind::Comment, 0);
+            return;
+        }
+
+        if line.starts_with("=begin") {
+            state.mode = EMBEDDED_DOCUMENT;
+            s.eat_to_end();
+            s.token(TokenKind::Comment, 0);
+            return;
+        }
+
+        while !s.is_done() {
+            let start = s.pos();
+
+            if s.eat_char('#') {
+                s.eat_to_end();
+                s.token(TokenKind::Comment, start);
+            } else if let Some(quote) = s.peek().filter(|c| *c == '"' || *c == '\'') {
+                s.bump();
+                s.eat_quoted(quote, Some('\\'));
+                s.token(TokenKind::String, start);
+            } else if s.peek() == Some(':')
+                && s.prev() != Some(':')
+                && s.peek_nth(1).is_some_and(|c| c.is_ascii_lowercase())
+            {
+                s.bump();
+                s.eat_word();
+                s.token(TokenKind::Symbol, start);
+            } else if let Some(sigil) = ["@@", "@", "$"].into_iter().find(|p| s.starts_with(p)) {
+                s.eat(sigil);
+                if s.eat_word() {
+                    s.token(TokenKind::Variable, start);
+                }
+            } else if s.at_word_boundary() && s.eat_word() {
+                let word = s.slice(start);
+                if contains(KEYWORDS, word) {
+                    s.token(TokenKind::Keyword, start);
+                } else if contains(BUILTINS, word) {
+                    s.token(TokenKind::Builtin, start);
+                }
+            } else {
+                s.bump();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::highlighter::testing::highlight;
+
+    #[test]
+    fn embedded_documents_span_lines() {
+        assert_eq!(
+            highlight(&Ruby, "=begin\nnot code\n=end\nnil"),
+            [
+                (TokenKind::Comment, "=begin"),
+                (TokenKind::Comment, "not code"),
+                (TokenKind::Comment, "=end"),
+                (TokenKind::Keyword, "nil"),
+            ]
+        );
+    }
+
+    #[test]
+    fn symbols_variables_and_strings() {
+        assert_eq!(
+            highlight(
+                &Ruby,
+                "@@count = $stdout || :name || File::open \"#{a}\" # c"
+            ),
+            [
+                (TokenKind::Variable, "@@count"),
+                (TokenKind::Variable, "$stdout"),
+                (TokenKind::Symbol, ":name"),
+                (TokenKind::Builtin, "File"),
+                (TokenKind::String, "\"#{a}\""),
+                (TokenKind::Comment, "# c"),
+            ]
+        );
+    }
+}
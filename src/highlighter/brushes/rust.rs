@@ -0,0 +1,285 @@
+use crate::highlighter::scanner::{contains, is_word_char, Scanner};
+use crate::highlighter::{Brush, LineState, Token, TokenKind};
+
+/// Inside `/* */`, `depth` counting the comments open as they nest.
+const BLOCK_COMMENT: u32 = 1;
+/// Inside a string continuing on the next line.
+const STRING: u32 = 2;
+/// Inside a raw string, closed by `delimiter`.
+const RAW_STRING: u32 = 3;
+/// Inside `#[...]`, `depth` counting the open brackets.
+const ATTRIBUTE: u32 = 4;
+
+const KEYWORDS: &[&str] = &[
+    "Self", "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum",
+    "extern", "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move",
+    "mut", "pub", "ref", "return", "self", "static", "struct", "super", "trait", "true", "type",
+    "union", "unsafe", "use", "where", "while", "yield",
+];
+
+const DATATYPES: &[&str] = &[
+    "bool", "char", "f32", "f64", "i128", "i16", "i32", "i64", "i8", "isize", "str", "u128", "u16",
+    "u32", "u64", "u8", "usize",
+];
+
+/// The types and variants of the prelude.
+const BUILTINS: &[&str] = &[
+    "Box", "Err", "None", "Ok", "Option", "Result", "Some", "String", "Vec",
+];
+
+pub(crate) struct Rust;
+
+/// Consumes block comments up to where the outermost of the `depth` open ones closes.
+///
+/// Returns the depth still open at the end of the line.
+fn eat_block_comment(s: &mut Scanner, mut depth: u32) -> u32 {
+    while depth > 0 {
+        let rest = s.rest();
+        let open = rest.find("/*");
+        let close = rest.find("*/");
+        match (open, close) {
+            (Some(open), close) if close.is_none_or(|close| open < close) => {
+                s.eat(&rest[..open + 2]);
+                depth += 1;
+            }
+            (_, Some(close)) => {
+                s.eat(&rest[..close + 2]);
+                depth -= 1;
+            }
+            _ => {
+                s.eat_to_end();
+                break;
+            }
+        }
+    }
+    depth
+}
+
+/// Consumes the rest of a `#[...]` attribute, `depth` brackets being open, skipping over the
+/// brackets in strings.
+///
+/// Returns the depth still open at the end of the line.
+fn eat_attribute(s: &mut Scanner, mut depth: u32) -> u32 {
+    while depth > 0 {
+        match s.bump() {
+            Some('[') => depth += 1,
+            Some(']') => depth -= 1,
+            Some('"') => {
+                s.eat_quoted('"', Some('\\'));
+            }
+            Some(_) => {}
+            None => break,
+        }
+    }
+    depth
+}
+
+/// Consumes a raw string's opening `r#"`, after an optional `b` or `c`, returning its closing
+/// delimiter.
+fn eat_raw_string_start(s: &mut Scanner) -> Option<String> {
+    let rest = s.rest();
+    let prefix = ["br", "cr", "r"]
+        .into_iter()
+        .find(|p| rest.starts_with(p))?;
+    let hashes = rest[prefix.len()..].len() - rest[prefix.len()..].trim_start_matches('#').len();
+    if rest[prefix.len() + hashes..].starts_with('"') {
+        s.eat(&rest[..prefix.len() + hashes + 1]);
+        Some(format!("\"{}", "#".repeat(hashes)))
+    } else {
+        None
+    }
+}
+
+impl Brush for Rust {
+    fn tokenize_line(&self, line: &str, state: &mut LineState, tokens: &mut Vec<Token>) {
+        let mut s = Scanner::new(line, tokens);
+
+        match state.mode {
+            BLOCK_COMMENT => {
+                state.depth = eat_block_comment(&mut s, state.depth);
+                s.token(TokenKind::Comment, 0);
+            }
+            STRING => {
+                if s.eat_quoted('"', Some('\\')) {
+                    state.mode = 0;
+                }
+                s.token(TokenKind::String, 0);
+            }
+            RAW_STRING => {
+                if s.eat_until(&state.delimiter) {
+                    state.mode = 0;
+                    state.delimiter.clear();
+                }
+                s.token(TokenKind::String, 0);
+            }
+            ATTRIBUTE => {
+                state.depth = eat_attribute(&mut s, state.depth);
+                s.token(TokenKind::Annotation, 0);
+            }
+            _ => {}
+        }
+        if state.depth == 0 && matches!(state.mode, BLOCK_COMMENT | ATTRIBUTE) {
+            state.mode = 0;
+        }
+        if state.mode != 0 {
+            return;
+        }
+
+        while !s.is_done() {
+            let start = s.pos();
+
+            // doc comments, `///` and `//!`, are comments all the same
+            if s.eat("//") {
+                s.eat_to_end();
+                s.token(TokenKind::Comment, start);
+            } else if s.eat("/*") {
+                let depth = eat_block_comment(&mut s, 1);
+                if depth > 0 {
+                    state.mode = BLOCK_COMMENT;
+                    state.depth = depth;
+                }
+                s.token(TokenKind::Comment, start);
+            } else if s.eat("#[") || s.eat("#![") {
+                let depth = eat_attribute(&mut s, 1);
+                if depth > 0 {
+                    state.mode = ATTRIBUTE;
+                    state.depth = depth;
+                }
+                s.token(TokenKind::Annotation, start);
+            } else if s.at_word_boundary()
+                && s.starts_with("r#")
+                && s.peek_nth(2).is_some_and(is_word_char)
+            {
+                // a raw identifier, `r#type` naming something `type`
+                s.eat("r#");
+                s.eat_word();
+            } else if let Some(delimiter) = s
+                .at_word_boundary()
+                .then(|| eat_raw_string_start(&mut s))
+                .flatten()
+            {
+                if !s.eat_until(&delimiter) {
+                    state.mode = RAW_STRING;
+                    state.delimiter = delimiter;
+                }
+                s.token(TokenKind::String, start);
+            } else if s.at_word_boundary() && (s.eat("b\"") || s.eat("c\"")) || s.eat_char('"') {
+                if !s.eat_quoted('"', Some('\\')) {
+                    state.mode = STRING;
+                }
+                s.token(TokenKind::String, start);
+            } else if s.at_word_boundary() && s.starts_with("b'") || s.peek() == Some('\'') {
+                s.eat_char('b');
+                s.bump();
+                // `'a'` and `'\n'` are characters, `'a` without the closing quote a lifetime
+                if s.peek() == Some('\\') || s.peek_nth(1) == Some('\'') {
+                    s.eat_quoted('\'', Some('\\'));
+                    s.token(TokenKind::String, start);
+                } else if s.eat_word() {
+                    s.token(TokenKind::Special, start);
+                }
+            } else if s.eat_number() {
+                s.token(TokenKind::Number, start);
+            } else if s.at_word_boundary() && s.eat_word() {
+                let word = s.slice(start);
+                // `name!` invokes a macro, `!=` compares
+                if s.peek() == Some('!') && s.peek_nth(1) != Some('=') {
+                    s.bump();
+                    if word == "macro_rules" {
+                        s.token(TokenKind::Keyword, start);
+                    } else {
+                        s.token(TokenKind::Function, start);
+                    }
+                } else if contains(KEYWORDS, word) {
+                    s.token(TokenKind::Keyword, start);
+                } else if contains(DATATYPES, word) {
+                    s.token(TokenKind::DataType, start);
+                } else if contains(BUILTINS, word) {
+                    s.token(TokenKind::Builtin, start);
+                }
+            } else {
+                s.bump();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::highlighter::testing::highlight;
+
+    #[test]
+    fn block_comments_nest() {
+        assert_eq!(
+            highlight(&Rust, "/* a /* b */ c\n*/ fn /* d */ x"),
+            [
+                (TokenKind::Comment, "/* a /* b */ c"),
+                (TokenKind::Comment, "*/"),
+                (TokenKind::Keyword, "fn"),
+                (TokenKind::Comment, "/* d */"),
+            ]
+        );
+    }
+
+    #[test]
+    fn raw_strings_and_raw_identifiers() {
+        assert_eq!(
+            highlight(
+                &Rust,
+                "let r#type = r##\"a \"# b\n\"## + br\"x\" + b\"y\" + c\"z\";"
+            ),
+            [
+                (TokenKind::Keyword, "let"),
+                (TokenKind::String, "r##\"a \"# b"),
+                (TokenKind::String, "\"##"),
+                (TokenKind::String, "br\"x\""),
+                (TokenKind::String, "b\"y\""),
+                (TokenKind::String, "c\"z\""),
+            ]
+        );
+    }
+
+    #[test]
+    fn characters_and_lifetimes() {
+        assert_eq!(
+            highlight(&Rust, "fn f<'a>(c: &'a char) { '\\n'; b'x'; 'y'; }"),
+            [
+                (TokenKind::Keyword, "fn"),
+                (TokenKind::Special, "'a"),
+                (TokenKind::Special, "'a"),
+                (TokenKind::DataType, "char"),
+                (TokenKind::String, "'\\n'"),
+                (TokenKind::String, "b'x'"),
+                (TokenKind::String, "'y'"),
+            ]
+        );
+    }
+
+    #[test]
+    fn attributes_span_lines() {
+        assert_eq!(
+            highlight(&Rust, "#[cfg(all(\n    feature = \"]\"))]\nstruct S;"),
+            [
+                (TokenKind::Annotation, "#[cfg(all("),
+                (TokenKind::Annotation, "    feature = \"]\"))]"),
+                (TokenKind::Keyword, "struct"),
+            ]
+        );
+    }
+
+    #[test]
+    fn macros_types_and_numbers() {
+        assert_eq!(
+            highlight(&Rust, "macro_rules! m {}\nvec![1u8, 0x1f] != Some(2.5e3)"),
+            [
+                (TokenKind::Keyword, "macro_rules!"),
+                (TokenKind::Function, "vec!"),
+                (TokenKind::Number, "1u8"),
+                (TokenKind::Number, "0x1f"),
+                (TokenKind::Builtin, "Some"),
+                (TokenKind::Number, "2.5e3"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,359 @@
+//! Bash, and the sh and zsh scripts written in the same syntax.
+
+use crate::highlighter::scanner::{contains, is_word_char, Scanner};
+use crate::highlighter::{Brush, LineState, Token, TokenKind};
+
+/// Commands, `depth` counting the parentheses opened since the `$(` they are substituted by.
+const SCRIPT: u32 = 0;
+const SINGLE_QUOTED: u32 = 1;
+const DOUBLE_QUOTED: u32 = 2;
+/// The lines of a heredoc up to the one holding just `delimiter`, `depth` being `1` when leading
+/// tabs are stripped from them.
+const HEREDOC: u32 = 3;
+/// A heredoc whose delimiter was quoted, leaving `$` unexpanded.
+const QUOTED_HEREDOC: u32 = 4;
+
+const KEYWORDS: &[&str] = &[
+    "case", "coproc", "do", "done", "elif", "else", "esac", "fi", "for", "function", "if", "in",
+    "select", "then", "time", "until", "while",
+];
+
+const BUILTINS: &[&str] = &[
+    "alias",
+    "bg",
+    "bind",
+    "break",
+    "builtin",
+    "cd",
+    "command",
+    "continue",
+    "declare",
+    "dirs",
+    "disown",
+    "echo",
+    "enable",
+    "eval",
+    "exec",
+    "exit",
+    "export",
+    "false",
+    "fc",
+    "fg",
+    "getopts",
+    "hash",
+    "help",
+    "history",
+    "jobs",
+    "kill",
+    "let",
+    "local",
+    "logout",
+    "mapfile",
+    "popd",
+    "printf",
+    "pushd",
+    "pwd",
+    "read",
+    "readarray",
+    "readonly",
+    "return",
+    "set",
+    "shift",
+    "shopt",
+    "source",
+    "suspend",
+    "test",
+    "times",
+    "trap",
+    "true",
+    "type",
+    "typeset",
+    "ulimit",
+    "umask",
+    "unalias",
+    "unset",
+    "wait",
+];
+
+pub(crate) struct Shell;
+
+/// A heredoc started on the current line, its lines following the line's end.
+struct Heredoc {
+    mode: u32,
+    strip_tabs: bool,
+    delimiter: String,
+}
+
+/// Whether a `$` expansion starts at the cursor, rather than a literal `$`.
+fn at_expansion(s: &Scanner) -> bool {
+    s.peek() == Some('$')
+        && s.peek_nth(1)
+            .is_some_and(|c| is_word_char(c) || "@*#?$!-({".contains(c))
+}
+
+/// Consumes the `$` expansion at the cursor: a variable, `${...}`, or the `$(` or `$((` opening a
+/// command or arithmetic substitution, which enters script until the matching `)`.
+fn eat_expansion(s: &mut Scanner, state: &mut LineState) {
+    let start = s.pos();
+    if s.eat("$((") {
+        s.token(TokenKind::Variable, start);
+        // the first `)` closes the inner parenthesis, the second the substitution
+        state.enter(SCRIPT, 1);
+    } else if s.eat("$(") {
+        s.token(TokenKind::Variable, start);
+        state.enter(SCRIPT, 0);
+    } else if s.eat("${") {
+        let mut depth = 1;
+        while depth > 0 {
+            match s.bump() {
+                Some('{') => depth += 1,
+                Some('}') => depth -= 1,
+                Some(_) => {}
+                None => break,
+            }
+        }
+        s.token(TokenKind::Variable, start);
+    } else {
+        s.bump();
+        if !s.eat_word() {
+            s.bump();
+        }
+        s.token(TokenKind::Variable, start);
+    }
+}
+
+/// Consumes the body of a double quoted string, or of a heredoc when `closing` is `None`, up to
+/// the closing quote or an expansion entering script.
+fn expanding(s: &mut Scanner, state: &mut LineState, closing: Option<char>) {
+    let mode = state.mode;
+    let mut start = s.pos();
+    while let Some(c) = s.peek() {
+        if c == '\\' {
+            s.bump();
+            s.bump();
+        } else if Some(c) == closing {
+            s.bump();
+            s.token(TokenKind::String, start);
+            state.leave();
+            return;
+        } else if at_expansion(s) {
+            s.token(TokenKind::String, start);
+            eat_expansion(s, state);
+            if state.mode != mode {
+                return;
+            }
+            start = s.pos();
+        } else {
+            s.bump();
+        }
+    }
+    s.token(TokenKind::String, start);
+}
+
+/// Consumes a heredoc's `<<` operator and delimiter, `<<<` here-strings being something else.
+fn eat_heredoc_start(s: &mut Scanner) -> Option<Heredoc> {
+    if !s.starts_with("<<") || s.starts_with("<<<") {
+        return None;
+    }
+    s.eat("<<");
+    let strip_tabs = s.eat_char('-');
+    s.eat_while(|c| c == ' ' || c == '\t');
+    let (mode, delimiter) = match s.peek() {
+        Some(quote @ ('\'' | '"')) => {
+            s.bump();
+            let start = s.pos();
+            s.eat_while(|c| c != quote);
+            let delimiter = s.slice(start).to_string();
+            s.bump();
+            (QUOTED_HEREDOC, delimiter)
+        }
+        _ => {
+            let start = s.pos();
+            // `\EOF` quotes the delimiter as well
+            let quoted = s.eat_char('\\');
+            s.eat_while(|c| is_word_char(c) || c == '-' || c == '.');
+            let delimiter = s.slice(start).trim_start_matches('\\').to_string();
+            (if quoted { QUOTED_HEREDOC } else { HEREDOC }, delimiter)
+        }
+    };
+    // `$((1 << 2))` shifts
+    let valid = delimiter
+        .chars()
+        .next()
+        .is_some_and(|c| !c.is_ascii_digit());
+    valid.then_some(Heredoc {
+        mode,
+        strip_tabs,
+        delimiter,
+    })
+}
+
+impl Shell {
+    fn script(s: &mut Scanner, state: &mut LineState, heredoc: &mut Option<Heredoc>) {
+        while state.mode == SCRIPT && !s.is_done() {
+            let start = s.pos();
+            // words start after blanks and operators
+            let word_start = s
+                .prev()
+                .is_none_or(|c| c.is_whitespace() || ";&|()".contains(c));
+
+            if word_start && s.eat_char('#') {
+                s.eat_to_end();
+                s.token(TokenKind::Comment, start);
+            } else if s.eat_char('\'') {
+                if !s.eat_until("'") {
+                    state.enter(SINGLE_QUOTED, 0);
+                }
+                s.token(TokenKind::String, start);
+            } else if s.eat("$'") {
+                s.eat_quoted('\'', Some('\\'));
+                s.token(TokenKind::String, start);
+            } else if s.eat_char('"') {
+                s.token(TokenKind::String, start);
+                state.enter(DOUBLE_QUOTED, 0);
+            } else if at_expansion(s) {
+                eat_expansion(s, state);
+            } else if s.eat_char('`') {
+                s.eat_quoted('`', Some('\\'));
+                s.token(TokenKind::Variable, start);
+            } else if let Some(started) = eat_heredoc_start(s) {
+                s.token(TokenKind::Special, start);
+                heredoc.get_or_insert(started);
+            } else if s.eat_char('\\') {
+                s.bump();
+            } else if s.eat_char('(') {
+                state.depth += 1;
+            } else if s.eat_char(')') {
+                if state.depth > 0 {
+                    state.depth -= 1;
+                } else if !state.stack.is_empty() {
+                    s.token(TokenKind::Variable, start);
+                    state.leave();
+                }
+            } else if word_start && s.eat_while(|c| is_word_char(c) || c == '-') {
+                let word = s.slice(start);
+                if s.peek() == Some('=') && !word.contains('-') {
+                    s.token(TokenKind::Variable, start);
+                } else if contains(KEYWORDS, word) {
+                    s.token(TokenKind::Keyword, start);
+                } else if contains(BUILTINS, word) {
+                    s.token(TokenKind::Builtin, start);
+                }
+            } else {
+                s.bump();
+            }
+        }
+    }
+
+    fn heredoc(s: &mut Scanner, state: &mut LineState) {
+        if s.pos() == 0 {
+            let line = s.rest();
+            let line = if state.depth == 1 {
+                line.trim_start_matches('\t')
+            } else {
+                line
+            };
+            if line == state.delimiter {
+                s.eat_to_end();
+                s.token(TokenKind::Special, 0);
+                state.delimiter.clear();
+                state.leave();
+                return;
+            }
+        }
+        if state.mode == QUOTED_HEREDOC {
+            let start = s.pos();
+            s.eat_to_end();
+            s.token(TokenKind::String, start);
+        } else {
+            expanding(s, state, None);
+        }
+    }
+}
+
+impl Brush for Shell {
+    fn tokenize_line(&self, line: &str, state: &mut LineState, tokens: &mut Vec<Token>) {
+        let mut s = Scanner::new(line, tokens);
+        let mut heredoc = None;
+
+        while !s.is_done() {
+            match state.mode {
+                SINGLE_QUOTED => {
+                    let start = s.pos();
+                    if s.eat_until("'") {
+                        state.leave();
+                    }
+                    s.token(TokenKind::String, start);
+                }
+                DOUBLE_QUOTED => expanding(&mut s, state, Some('"')),
+                HEREDOC | QUOTED_HEREDOC => Shell::heredoc(&mut s, state),
+                _ => Shell::script(&mut s, state, &mut heredoc),
+            }
+        }
+
+        if let Some(heredoc) = heredoc.filter(|_| state.mode == SCRIPT) {
+            state.enter(heredoc.mode, heredoc.strip_tabs as u32);
+            state.delimiter = heredoc.delimiter;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::highlighter::testing::highlight;
+
+    #[test]
+    fn expansions_nest_in_strings() {
+        assert_eq!(
+            highlight(&Shell, "echo \"a $b $(c \"${d}\") e\" # f"),
+            [
+                (TokenKind::Builtin, "echo"),
+                (TokenKind::String, "\""),
+                (TokenKind::String, "a "),
+                (TokenKind::Variable, "$b"),
+                (TokenKind::String, " "),
+                (TokenKind::Variable, "$("),
+                (TokenKind::String, "\""),
+                (TokenKind::Variable, "${d}"),
+                (TokenKind::String, "\""),
+                (TokenKind::Variable, ")"),
+                (TokenKind::String, " e\""),
+                (TokenKind::Comment, "# f"),
+            ]
+        );
+    }
+
+    #[test]
+    fn heredocs_end_at_their_delimiter() {
+        assert_eq!(
+            highlight(
+                &Shell,
+                "cat <<-EOF; x=1\n\t$a EOF\n\tEOF\ncat <<'END'\n$b\nEND"
+            ),
+            [
+                (TokenKind::Special, "<<-EOF"),
+                (TokenKind::Variable, "x"),
+                (TokenKind::String, "\t"),
+                (TokenKind::Variable, "$a"),
+                (TokenKind::String, " EOF"),
+                (TokenKind::Special, "\tEOF"),
+                (TokenKind::Special, "<<'END'"),
+                (TokenKind::String, "$b"),
+                (TokenKind::Special, "END"),
+            ]
+        );
+    }
+
+    #[test]
+    fn shifts_and_comments_inside_words() {
+        assert_eq!(
+            highlight(&Shell, "echo $((1 << 2)) a#b"),
+            [
+                (TokenKind::Builtin, "echo"),
+                (TokenKind::Variable, "$(("),
+                (TokenKind::Variable, ")"),
+            ]
+        );
+    }
+}
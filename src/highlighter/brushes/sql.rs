@@ -0,0 +1,291 @@
+use crate::highlighter::scanner::{contains_ignore_case, Scanner};
+use crate::highlighter::{Brush, LineState, Token, TokenKind};
+
+const BLOCK_COMMENT: u32 = 1;
+
+const FUNCTIONS: &[&str] = &[
+    "abs",
+    "avg",
+    "case",
+    "cast",
+    "coalesce",
+    "convert",
+    "count",
+    "current_timestamp",
+    "current_user",
+    "day",
+    "isnull",
+    "left",
+    "lower",
+    "month",
+    "nullif",
+    "replace",
+    "right",
+    "session_user",
+    "space",
+    "substring",
+    "sum",
+    "system_user",
+    "upper",
+    "user",
+    "year",
+];
+
+const KEYWORDS: &[&str] = &[
+    "absolute",
+    "action",
+    "add",
+    "after",
+    "alter",
+    "as",
+    "asc",
+    "at",
+    "authorization",
+    "begin",
+    "bigint",
+    "binary",
+    "bit",
+    "by",
+    "cascade",
+    "char",
+    "character",
+    "check",
+    "checkpoint",
+    "close",
+    "collate",
+    "column",
+    "commit",
+    "committed",
+    "connect",
+    "connection",
+    "constraint",
+    "contains",
+    "continue",
+    "create",
+    "cube",
+    "current",
+    "current_date",
+    "current_time",
+    "cursor",
+    "database",
+    "date",
+    "deallocate",
+    "dec",
+    "decimal",
+    "declare",
+    "default",
+    "delete",
+    "desc",
+    "distinct",
+    "double",
+    "drop",
+    "dynamic",
+    "else",
+    "end",
+    "end-exec",
+    "escape",
+    "except",
+    "exec",
+    "execute",
+    "false",
+    "fetch",
+    "first",
+    "float",
+    "for",
+    "force",
+    "foreign",
+    "forward",
+    "free",
+    "from",
+    "full",
+    "function",
+    "global",
+    "goto",
+    "grant",
+    "group",
+    "grouping",
+    "having",
+    "hour",
+    "ignore",
+    "index",
+    "inner",
+    "insensitive",
+    "insert",
+    "instead",
+    "int",
+    "integer",
+    "intersect",
+    "into",
+    "is",
+    "isolation",
+    "key",
+    "last",
+    "level",
+    "load",
+    "local",
+    "max",
+    "min",
+    "minute",
+    "modify",
+    "move",
+    "name",
+    "national",
+    "nchar",
+    "next",
+    "no",
+    "numeric",
+    "of",
+    "off",
+    "on",
+    "only",
+    "open",
+    "option",
+    "order",
+    "out",
+    "output",
+    "partial",
+    "password",
+    "precision",
+    "prepare",
+    "primary",
+    "prior",
+    "privileges",
+    "procedure",
+    "public",
+    "read",
+    "real",
+    "references",
+    "relative",
+    "repeatable",
+    "restrict",
+    "return",
+    "returns",
+    "revoke",
+    "rollback",
+    "rollup",
+    "rows",
+    "rule",
+    "schema",
+    "scroll",
+    "second",
+    "section",
+    "select",
+    "sequence",
+    "serializable",
+    "set",
+    "size",
+    "smallint",
+    "static",
+    "statistics",
+    "table",
+    "temp",
+    "temporary",
+    "then",
+    "time",
+    "timestamp",
+    "to",
+    "top",
+    "transaction",
+    "translation",
+    "trigger",
+    "true",
+    "truncate",
+    "uncommitted",
+    "union",
+    "unique",
+    "update",
+    "values",
+    "varchar",
+    "varying",
+    "view",
+    "when",
+    "where",
+    "with",
+    "work",
+];
+
+const OPERATORS: &[&str] = &[
+    "all", "and", "any", "between", "cross", "in", "join", "like", "not", "null", "or", "outer",
+    "some",
+];
+
+pub(crate) struct Sql;
+
+impl Brush for Sql {
+    fn tokenize_line(&self, line: &str, state: &mut LineState, tokens: &mut Vec<Token>) {
+        let mut s = Scanner::new(line, tokens);
+
+        if state.mode == BLOCK_COMMENT {
+            let closed = s.eat_until("*/");
+            s.token(TokenKind::Comment, 0);
+            if !closed {
+                return;
+            }
+            state.mode = 0;
+        }
+
+        while !s.is_done() {
+            let start = s.pos();
+
+            if s.eat("--") {
+                s.eat_to_end();
+                s.token(TokenKind::Comment, start);
+            } else if s.eat("/*") {
+                if !s.eat_until("*/") {
+                    state.mode = BLOCK_COMMENT;
+                }
+                s.token(TokenKind::Comment, start);
+            } else if let Some(quote) = s.peek().filter(|c| *c == '"' || *c == '\'') {
+                s.bump();
+                s.eat_quoted(quote, Some('\\'));
+                s.token(TokenKind::String, start);
+            } else if s.at_word_boundary() && s.eat_word() {
+                let word = s.slice(start);
+                if contains_ignore_case(FUNCTIONS, word) {
+                    s.token(TokenKind::Function, start);
+                } else if contains_ignore_case(OPERATORS, word) {
+                    s.token(TokenKind::Operator, start);
+                } else if contains_ignore_case(KEYWORDS, word) {
+                    s.token(TokenKind::Keyword, start);
+                }
+            } else {
+                s.bump();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::highlighter::testing::highlight;
+
+    #[test]
+    fn keywords_functions_and_operators_ignore_case() {
+        assert_eq!(
+            highlight(&Sql, "select Count(*) FROM t WHERE a like 'x''y' -- c"),
+            [
+                (TokenKind::Keyword, "select"),
+                (TokenKind::Function, "Count"),
+                (TokenKind::Keyword, "FROM"),
+                (TokenKind::Keyword, "WHERE"),
+                (TokenKind::Operator, "like"),
+                (TokenKind::String, "'x'"),
+                (TokenKind::String, "'y'"),
+                (TokenKind::Comment, "-- c"),
+            ]
+        );
+    }
+
+    #[test]
+    fn block_comments_span_lines() {
+        assert_eq!(
+            highlight(&Sql, "/* a\nb */ NULL"),
+            [
+                (TokenKind::Comment, "/* a"),
+                (TokenKind::Comment, "b */"),
+                (TokenKind::Operator, "NULL"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,226 @@
+//! TOML 1.1: tables, arrays of tables, dotted keys and the four kinds of datetimes.
+
+use crate::highlighter::scanner::{contains, Scanner};
+use crate::highlighter::{Brush, LineState, SyntaxError, Token, TokenKind};
+
+/// Key/value pairs and table headers, `depth` counting the arrays open across lines.
+const PAIRS: u32 = 0;
+/// Inside a `"""` string.
+const MULTILINE_BASIC: u32 = 1;
+/// Inside a `'''` string.
+const MULTILINE_LITERAL: u32 = 2;
+
+const VALUES: &[&str] = &["false", "true"];
+
+/// The float special values, signed or not.
+const NUMBERS: &[&str] = &["inf", "nan"];
+
+pub(crate) struct Toml;
+
+fn is_bare_key_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || c == '-'
+}
+
+/// Length of the possibly dotted key `rest` starts with, blanks between its parts included.
+fn key_len(rest: &str) -> Option<usize> {
+    let mut len = 0;
+    loop {
+        let part = &rest[len..];
+        let part_len = match part.chars().next()? {
+            quote @ ('"' | '\'') => 2 + part[1..].find(quote)?,
+            _ => part.len() - part.trim_start_matches(is_bare_key_char).len(),
+        };
+        if part_len == 0 {
+            return None;
+        }
+        len += part_len;
+        let after = rest[len..].trim_start_matches([' ', '\t']);
+        match after.strip_prefix('.') {
+            Some(next) => len = rest.len() - next.trim_start_matches([' ', '\t']).len(),
+            None => return Some(len),
+        }
+    }
+}
+
+/// Length of the date, time or date and time `rest` starts with, like `1979-05-27T07:32:00Z`.
+fn datetime_len(rest: &str) -> Option<usize> {
+    let bytes = rest.as_bytes();
+    let digits = |from: usize, count: usize| {
+        bytes.len() >= from + count && bytes[from..from + count].iter().all(u8::is_ascii_digit)
+    };
+    let date = digits(0, 4) && bytes.get(4) == Some(&b'-') && digits(5, 2);
+    let time = digits(0, 2) && bytes.get(2) == Some(&b':') && digits(3, 2);
+    if !date && !time {
+        return None;
+    }
+    let mut len = rest.len()
+        - rest
+            .trim_start_matches(|c: char| c.is_ascii_alphanumeric() || ":.-+".contains(c))
+            .len();
+    // the date and time may be separated by a space instead of a `T`
+    if date && len == 10 && rest[len..].starts_with(' ') {
+        if let Some(time) = datetime_len(&rest[len + 1..]).filter(|_| digits(len + 1, 2)) {
+            len += 1 + time;
+        }
+    }
+    Some(len)
+}
+
+fn check_toml(code: &str) -> Result<(), SyntaxError> {
+    let Err(error) = code.parse::<toml::Table>() else {
+        return Ok(());
+    };
+    let offset = error.span().map_or(0, |span| span.start);
+    Err(SyntaxError::at_offset(code, offset, error.message()))
+}
+
+impl Toml {
+    fn pairs(s: &mut Scanner, state: &mut LineState) {
+        while state.mode == PAIRS && !s.is_done() {
+            let start = s.pos();
+            // keys start lines and the entries of inline tables
+            let key_expected = state.depth == 0 && s.at_line_start()
+                || s.consumed().trim_end().ends_with(['{', ',']);
+
+            if s.eat_char('#') {
+                s.eat_to_end();
+                s.token(TokenKind::Comment, start);
+            } else if state.depth == 0 && s.at_line_start() && s.starts_with("[") {
+                let array = s.starts_with("[[");
+                s.eat_until(if array { "]]" } else { "]" });
+                s.token(TokenKind::Keyword, start);
+            } else if let Some(len) = key_expected
+                .then(|| key_len(s.rest()))
+                .flatten()
+                .filter(|len| s.rest()[*len..].trim_start().starts_with('='))
+            {
+                s.eat(&s.rest()[..len]);
+                s.token(TokenKind::Attribute, start);
+            } else if s.eat("\"\"\"") {
+                if !s.eat_until("\"\"\"") {
+                    state.mode = MULTILINE_BASIC;
+                }
+                s.token(TokenKind::String, start);
+            } else if s.eat("'''") {
+                if !s.eat_until("'''") {
+                    state.mode = MULTILINE_LITERAL;
+                }
+                s.token(TokenKind::String, start);
+            } else if s.eat_char('"') {
+                s.eat_quoted('"', Some('\\'));
+                s.token(TokenKind::String, start);
+            } else if s.eat_char('\'') {
+                s.eat_quoted('\'', None);
+                s.token(TokenKind::String, start);
+            } else if s.eat_char('[') {
+                state.depth += 1;
+            } else if s.eat_char(']') {
+                state.depth = state.depth.saturating_sub(1);
+            } else if let Some(len) = datetime_len(s.rest()).filter(|_| s.at_word_boundary()) {
+                s.eat(&s.rest()[..len]);
+                s.token(TokenKind::Special, start);
+            } else if matches!(s.peek(), Some('+' | '-'))
+                && s.peek_nth(1)
+                    .is_some_and(|c| c.is_ascii_digit() || c == 'i' || c == 'n')
+            {
+                s.bump();
+                if !s.eat_separated_number() {
+                    s.eat_word();
+                }
+                s.token(TokenKind::Number, start);
+            } else if s.eat_separated_number() {
+                s.token(TokenKind::Number, start);
+            } else if s.at_word_boundary() && s.eat_word() {
+                let word = s.slice(start);
+                if contains(VALUES, word) {
+                    s.token(TokenKind::Value, start);
+                } else if contains(NUMBERS, word) {
+                    s.token(TokenKind::Number, start);
+                }
+            } else {
+                s.bump();
+            }
+        }
+    }
+}
+
+impl Brush for Toml {
+    fn tokenize_line(&self, line: &str, state: &mut LineState, tokens: &mut Vec<Token>) {
+        let mut s = Scanner::new(line, tokens);
+
+        while !s.is_done() {
+            let start = s.pos();
+            match state.mode {
+                MULTILINE_BASIC | MULTILINE_LITERAL => {
+                    let (closing, quote) = if state.mode == MULTILINE_BASIC {
+                        ("\"\"\"", '"')
+                    } else {
+                        ("'''", '\'')
+                    };
+                    if s.eat_until(closing) {
+                        // up to two more quotes belong to the string rather than closing it
+                        s.eat_char(quote);
+                        s.eat_char(quote);
+                        state.mode = PAIRS;
+                    }
+                    s.token(TokenKind::String, start);
+                }
+                _ => Toml::pairs(&mut s, state),
+            }
+        }
+    }
+
+    fn check(&self, code: &str) -> Option<Result<(), SyntaxError>> {
+        Some(check_toml(code))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::highlighter::testing::highlight;
+
+    #[test]
+    fn keys_tables_and_datetimes() {
+        assert_eq!(
+            highlight(
+                &Toml,
+                "[[a.b]]\nc . \"d\" = 1979-05-27 07:32:00Z # e\nf = { g = -inf }"
+            ),
+            [
+                (TokenKind::Keyword, "[[a.b]]"),
+                (TokenKind::Attribute, "c . \"d\""),
+                (TokenKind::Special, "1979-05-27 07:32:00Z"),
+                (TokenKind::Comment, "# e"),
+                (TokenKind::Attribute, "f"),
+                (TokenKind::Attribute, "g"),
+                (TokenKind::Number, "-inf"),
+            ]
+        );
+    }
+
+    #[test]
+    fn arrays_and_multi_line_strings_span_lines() {
+        assert_eq!(
+            highlight(&Toml, "a = [\n  [1],\n]\nb = '''x\ny'''''\nc = true"),
+            [
+                (TokenKind::Attribute, "a"),
+                (TokenKind::Number, "1"),
+                (TokenKind::Attribute, "b"),
+                (TokenKind::String, "'''x"),
+                (TokenKind::String, "y'''''"),
+                (TokenKind::Attribute, "c"),
+                (TokenKind::Value, "true"),
+            ]
+        );
+    }
+
+    #[test]
+    fn check_reports_the_error_position() {
+        assert_eq!(Toml.check("a = 1\n[b]\nc = [1, 2]\n"), Some(Ok(())));
+        let error = Toml.check("a = 1\nb = \"é\" x\n").unwrap().unwrap_err();
+        assert_eq!((error.line, error.column), (2, 9));
+        let error = Toml.check("a = 1\na = 2\n").unwrap().unwrap_err();
+        assert_eq!(error.line, 2);
+    }
+}
@@ -0,0 +1,209 @@
+use crate::highlighter::scanner::{contains_ignore_case, Scanner};
+use crate::highlighter::{Brush, LineState, Token, TokenKind};
+
+const KEYWORDS: &[&str] = &[
+    "addhandler",
+    "addressof",
+    "alias",
+    "and",
+    "andalso",
+    "ansi",
+    "as",
+    "assembly",
+    "auto",
+    "boolean",
+    "byref",
+    "byte",
+    "byval",
+    "call",
+    "case",
+    "catch",
+    "cbool",
+    "cbyte",
+    "cchar",
+    "cdate",
+    "cdbl",
+    "cdec",
+    "char",
+    "cint",
+    "class",
+    "clng",
+    "cobj",
+    "const",
+    "cshort",
+    "csng",
+    "cstr",
+    "ctype",
+    "date",
+    "decimal",
+    "declare",
+    "default",
+    "delegate",
+    "dim",
+    "directcast",
+    "do",
+    "double",
+    "each",
+    "else",
+    "elseif",
+    "end",
+    "enum",
+    "erase",
+    "error",
+    "event",
+    "exit",
+    "false",
+    "finally",
+    "for",
+    "friend",
+    "function",
+    "get",
+    "gettype",
+    "gosub",
+    "goto",
+    "handles",
+    "if",
+    "implements",
+    "imports",
+    "in",
+    "inherits",
+    "integer",
+    "interface",
+    "is",
+    "let",
+    "lib",
+    "like",
+    "long",
+    "loop",
+    "me",
+    "mod",
+    "module",
+    "mustinherit",
+    "mustoverride",
+    "mybase",
+    "myclass",
+    "namespace",
+    "new",
+    "next",
+    "not",
+    "nothing",
+    "notinheritable",
+    "notoverridable",
+    "object",
+    "on",
+    "option",
+    "optional",
+    "or",
+    "orelse",
+    "overloads",
+    "overridable",
+    "overrides",
+    "paramarray",
+    "preserve",
+    "private",
+    "property",
+    "protected",
+    "public",
+    "raiseevent",
+    "readonly",
+    "redim",
+    "rem",
+    "removehandler",
+    "resume",
+    "return",
+    "select",
+    "set",
+    "shadows",
+    "shared",
+    "short",
+    "single",
+    "static",
+    "step",
+    "stop",
+    "string",
+    "structure",
+    "sub",
+    "synclock",
+    "then",
+    "throw",
+    "to",
+    "true",
+    "try",
+    "typeof",
+    "unicode",
+    "until",
+    "variant",
+    "when",
+    "while",
+    "with",
+    "withevents",
+    "writeonly",
+    "xor",
+];
+
+pub(crate) struct Vb;
+
+impl Brush for Vb {
+    fn tokenize_line(&self, line: &str, _state: &mut LineState, tokens: &mut Vec<Token>) {
+        let mut s = Scanner::new(line, tokens);
+
+        while !s.is_done() {
+            let start = s.pos();
+
+            if s.at_line_start() && s.peek() == Some('#') {
+                s.eat_to_end();
+                s.token(TokenKind::Preprocessor, start);
+            } else if s.eat_char('\'') {
+                s.eat_to_end();
+                s.token(TokenKind::Comment, start);
+            } else if s.eat_char('"') {
+                // quotes are escaped by doubling them, which scans as two adjacent strings
+                s.eat_quoted('"', None);
+                s.token(TokenKind::String, start);
+            } else if s.at_word_boundary() && s.eat_word() {
+                let word = s.slice(start);
+                if word.eq_ignore_ascii_case("rem") {
+                    s.eat_to_end();
+                    s.token(TokenKind::Comment, start);
+                } else if contains_ignore_case(KEYWORDS, word) {
+                    s.token(TokenKind::Keyword, start);
+                }
+            } else {
+                s.bump();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::highlighter::testing::highlight;
+
+    #[test]
+    fn comments_strings_and_keywords() {
+        assert_eq!(
+            highlight(&Vb, "Dim s As String = \"say \"\"hi\"\"\" ' greet"),
+            [
+                (TokenKind::Keyword, "Dim"),
+                (TokenKind::Keyword, "As"),
+                (TokenKind::Keyword, "String"),
+                (TokenKind::String, "\"say \""),
+                (TokenKind::String, "\"hi\""),
+                (TokenKind::String, "\"\""),
+                (TokenKind::Comment, "' greet"),
+            ]
+        );
+    }
+
+    #[test]
+    fn rem_comments_and_directives() {
+        assert_eq!(
+            highlight(&Vb, "#If DEBUG Then\nREM note\nRemove(x)"),
+            [
+                (TokenKind::Preprocessor, "#If DEBUG Then"),
+                (TokenKind::Comment, "REM note"),
+            ]
+        );
+    }
+}
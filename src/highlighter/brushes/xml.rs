@@ -0,0 +1,144 @@
+use crate::highlighter::scanner::{is_word_char, Scanner};
+use crate::highlighter::{Brush, LineState, Token, TokenKind};
+
+const COMMENT: u32 = 1;
+const CDATA: u32 = 2;
+const TAG: u32 = 3;
+
+fn is_name_char(c: char) -> bool {
+    is_word_char(c) || matches!(c, ':' | '-' | '.')
+}
+
+pub(crate) struct Xml;
+
+impl Xml {
+    /// Scans attributes up to the end of the tag, returning whether the tag was closed.
+    fn tokenize_tag(s: &mut Scanner) -> bool {
+        while !s.is_done() {
+            let start = s.pos();
+
+            if s.eat("/>") || s.eat("?>") || s.eat_char('>') {
+                s.token(TokenKind::Tag, start);
+                return true;
+            } else if s.eat_while(is_name_char) {
+                s.token(TokenKind::Attribute, start);
+                s.eat_while(char::is_whitespace);
+                if s.eat_char('=') {
+                    s.eat_while(char::is_whitespace);
+                    let value = s.pos();
+                    if let Some(quote) = s.peek().filter(|c| *c == '"' || *c == '\'') {
+                        s.bump();
+                        s.eat_quoted(quote, None);
+                    } else {
+                        s.eat_while(is_word_char);
+                    }
+                    s.token(TokenKind::AttributeValue, value);
+                }
+            } else {
+                s.bump();
+            }
+        }
+        false
+    }
+}
+
+impl Brush for Xml {
+    fn tokenize_line(&self, line: &str, state: &mut LineState, tokens: &mut Vec<Token>) {
+        let mut s = Scanner::new(line, tokens);
+
+        match state.mode {
+            COMMENT | CDATA => {
+                let (delimiter, kind) = if state.mode == COMMENT {
+                    ("-->", TokenKind::Comment)
+                } else {
+                    ("]]>", TokenKind::CData)
+                };
+                let closed = s.eat_until(delimiter);
+                s.token(kind, 0);
+                if !closed {
+                    return;
+                }
+                state.mode = 0;
+            }
+            TAG => {
+                if !Xml::tokenize_tag(&mut s) {
+                    return;
+                }
+                state.mode = 0;
+            }
+            _ => {}
+        }
+
+        while !s.is_done() {
+            let start = s.pos();
+
+            if s.eat("<!--") {
+                if !s.eat_until("-->") {
+                    state.mode = COMMENT;
+                }
+                s.token(TokenKind::Comment, start);
+            } else if s.eat("<![") {
+                if !s.eat_until("]]>") {
+                    state.mode = CDATA;
+                }
+                s.token(TokenKind::CData, start);
+            } else if s.eat_char('<') {
+                s.eat_while(|c| c == '/' || c == '?');
+                s.token(TokenKind::Tag, start);
+
+                let name = s.pos();
+                if s.eat_while(is_name_char) {
+                    s.token(TokenKind::TagName, name);
+                }
+                if !Xml::tokenize_tag(&mut s) {
+                    state.mode = TAG;
+                }
+            } else {
+                s.bump();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::highlighter::testing::highlight;
+
+    #[test]
+    fn tags_and_attributes() {
+        assert_eq!(
+            highlight(&Xml, "<a href=\"x\" id=one>text</a>"),
+            [
+                (TokenKind::Tag, "<"),
+                (TokenKind::TagName, "a"),
+                (TokenKind::Attribute, "href"),
+                (TokenKind::AttributeValue, "\"x\""),
+                (TokenKind::Attribute, "id"),
+                (TokenKind::AttributeValue, "one"),
+                (TokenKind::Tag, ">"),
+                (TokenKind::Tag, "</"),
+                (TokenKind::TagName, "a"),
+                (TokenKind::Tag, ">"),
+            ]
+        );
+    }
+
+    #[test]
+    fn tags_comments_and_cdata_span_lines() {
+        assert_eq!(
+            highlight(&Xml, "<img\n  src='a.png'/><!-- a\nb --><![CDATA[ x\ny ]]>"),
+            [
+                (TokenKind::Tag, "<"),
+                (TokenKind::TagName, "img"),
+                (TokenKind::Attribute, "src"),
+                (TokenKind::AttributeValue, "'a.png'"),
+                (TokenKind::Tag, "/>"),
+                (TokenKind::Comment, "<!-- a"),
+                (TokenKind::Comment, "b -->"),
+                (TokenKind::CData, "<![CDATA[ x"),
+                (TokenKind::CData, "y ]]>"),
+            ]
+        );
+    }
+}
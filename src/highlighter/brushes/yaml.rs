@@ -0,0 +1,324 @@
+//! YAML 1.2, block and flow style.
+//!
+//! Plain scalars are left as they are unless they are keys, literals or numbers, so prose in
+//! values doesn't light up.
+
+use crate::highlighter::scanner::{contains, Scanner};
+use crate::highlighter::{Brush, LineState, SyntaxError, Token, TokenKind};
+
+/// Block and flow nodes, `depth` counting the flow collections open.
+const NODES: u32 = 0;
+/// The lines of a `|` or `>` block scalar, those indented deeper than `depth`.
+const BLOCK_SCALAR: u32 = 1;
+const DOUBLE_QUOTED: u32 = 2;
+const SINGLE_QUOTED: u32 = 3;
+
+/// The core schema's booleans and nulls, in the spellings it accepts.
+const VALUES: &[&str] = &[
+    "FALSE", "False", "NULL", "Null", "TRUE", "True", "false", "null", "true", "~",
+];
+
+pub(crate) struct Yaml;
+
+/// Whether `c` can't start or continue a plain scalar inside a flow collection.
+fn is_flow_indicator(c: char) -> bool {
+    matches!(c, ',' | '[' | ']' | '{' | '}')
+}
+
+/// Whether a `:` at the start of `rest` separates a key from its value.
+fn is_value_indicator(rest: &str, flow: bool) -> bool {
+    rest.strip_prefix(':').is_some_and(|after| {
+        after.is_empty()
+            || after.starts_with([' ', '\t'])
+            || flow && after.starts_with(is_flow_indicator)
+    })
+}
+
+/// Length of the plain scalar `rest` starts with, trailing blanks excluded.
+fn plain_len(rest: &str, flow: bool) -> usize {
+    let mut end = 0;
+    let mut chars = rest.char_indices().peekable();
+    while let Some((index, c)) = chars.next() {
+        if c == ':' && is_value_indicator(&rest[index..], flow)
+            || flow && is_flow_indicator(c)
+            || c.is_whitespace() && chars.peek().is_some_and(|(_, c)| *c == '#')
+        {
+            break;
+        }
+        if !c.is_whitespace() {
+            end = index + c.len_utf8();
+        }
+    }
+    end
+}
+
+/// Whether the plain scalar `word` resolves to an integer or float in the core schema.
+fn is_number(word: &str) -> bool {
+    let unsigned = word.trim_start_matches(['-', '+']);
+    if let Some(digits) = word.strip_prefix("0x") {
+        return !digits.is_empty() && digits.chars().all(|c| c.is_ascii_hexdigit());
+    }
+    if let Some(digits) = word.strip_prefix("0o") {
+        return !digits.is_empty() && digits.chars().all(|c| ('0'..='7').contains(&c));
+    }
+    if matches!(unsigned, ".inf" | ".Inf" | ".INF") || matches!(word, ".nan" | ".NaN" | ".NAN") {
+        return true;
+    }
+    let digit_first = unsigned.starts_with(|c: char| c.is_ascii_digit())
+        || unsigned.starts_with('.') && unsigned[1..].starts_with(|c: char| c.is_ascii_digit());
+    digit_first && unsigned.parse::<f64>().is_ok()
+}
+
+/// Length of the block scalar header `rest` starts with, `|` or `>` with its chomping and
+/// indentation indicators, `None` when more than a comment follows it.
+fn block_scalar_header_len(rest: &str) -> Option<usize> {
+    let indicators = rest.strip_prefix(['|', '>'])?;
+    let len = 1 + indicators.len()
+        - indicators
+            .trim_start_matches(|c: char| c == '-' || c == '+' || c.is_ascii_digit())
+            .len();
+    let after = rest[len..].trim_start();
+    (after.is_empty() || after.starts_with('#') && rest[len..].starts_with([' ', '\t']))
+        .then_some(len)
+}
+
+/// Consumes a single quoted scalar's body, where `''` is a quote, returning whether it closed.
+fn eat_single_quoted(s: &mut Scanner) -> bool {
+    while s.eat_until("'") {
+        if !s.eat_char('\'') {
+            return true;
+        }
+    }
+    false
+}
+
+fn check_yaml(code: &str) -> Result<(), SyntaxError> {
+    match yaml_rust2::YamlLoader::load_from_str(code) {
+        Ok(_) => Ok(()),
+        Err(error) => Err(SyntaxError::new(
+            error.marker().line().max(1),
+            error.marker().col() + 1,
+            error.info(),
+        )),
+    }
+}
+
+impl Yaml {
+    fn nodes(s: &mut Scanner, state: &mut LineState) {
+        let indent = s.rest().len() - s.rest().trim_start_matches(' ').len();
+        // the indentation a block scalar's lines must exceed, that of the key or entry owning it
+        let mut owner = indent;
+
+        if s.pos() == 0 && s.starts_with("%") {
+            s.eat_to_end();
+            s.token(TokenKind::Directive, 0);
+            return;
+        }
+        if s.pos() == 0 && (s.starts_with("---") || s.starts_with("...")) {
+            let after = &s.rest()[3..];
+            if after.is_empty() || after.starts_with([' ', '\t']) {
+                s.eat(&s.rest()[..3]);
+                s.token(TokenKind::Preprocessor, 0);
+                state.depth = 0;
+            }
+        }
+
+        while state.mode == NODES && !s.is_done() {
+            let start = s.pos();
+            let flow = state.depth > 0;
+            let blank_before = s.prev().is_none_or(char::is_whitespace);
+
+            if blank_before && s.eat_char('#') {
+                s.eat_to_end();
+                s.token(TokenKind::Comment, start);
+            } else if s.peek().is_some_and(|c| c.is_whitespace() || c == ',')
+                || is_value_indicator(s.rest(), flow)
+            {
+                s.bump();
+            } else if !flow
+                && matches!(s.peek(), Some('-' | '?'))
+                && s.peek_nth(1).is_none_or(char::is_whitespace)
+            {
+                // a sequence entry or complex key, their nodes nested deeper
+                owner = start;
+                s.bump();
+            } else if matches!(s.peek(), Some('&' | '*')) {
+                s.bump();
+                s.eat_while(|c| !c.is_whitespace() && !is_flow_indicator(c));
+                s.token(TokenKind::Variable, start);
+            } else if s.eat_char('!') {
+                if s.eat_char('<') {
+                    s.eat_until(">");
+                } else {
+                    s.eat_while(|c| !c.is_whitespace() && !is_flow_indicator(c));
+                }
+                s.token(TokenKind::DataType, start);
+            } else if let Some(len) = block_scalar_header_len(s.rest()).filter(|_| !flow) {
+                s.eat(&s.rest()[..len]);
+                s.token(TokenKind::Special, start);
+                // only a comment may follow the header, the scalar's lines start on the next line
+                s.eat_while(char::is_whitespace);
+                let comment = s.pos();
+                s.eat_to_end();
+                s.token(TokenKind::Comment, comment);
+                state.mode = BLOCK_SCALAR;
+                state.depth = owner as u32;
+            } else if let Some(quote) = s.peek().filter(|c| *c == '"' || *c == '\'') {
+                s.bump();
+                let closed = if quote == '"' {
+                    s.eat_quoted('"', Some('\\'))
+                } else {
+                    eat_single_quoted(s)
+                };
+                if !closed {
+                    s.token(TokenKind::String, start);
+                    state.enter(
+                        if quote == '"' {
+                            DOUBLE_QUOTED
+                        } else {
+                            SINGLE_QUOTED
+                        },
+                        0,
+                    );
+                } else if is_value_indicator(s.rest().trim_start(), flow) {
+                    s.token(TokenKind::Attribute, start);
+                    owner = start;
+                } else {
+                    s.token(TokenKind::String, start);
+                }
+            } else if s.eat_char('[') || s.eat_char('{') {
+                state.depth += 1;
+            } else if s.eat_char(']') || s.eat_char('}') {
+                state.depth = state.depth.saturating_sub(1);
+            } else {
+                let len = plain_len(s.rest(), flow).max(1);
+                s.eat(&s.rest()[..len]);
+                let word = s.slice(start);
+                if is_value_indicator(s.rest().trim_start(), flow) {
+                    s.token(TokenKind::Attribute, start);
+                    owner = start;
+                } else if contains(VALUES, word) {
+                    s.token(TokenKind::Value, start);
+                } else if is_number(word) {
+                    s.token(TokenKind::Number, start);
+                }
+            }
+        }
+    }
+
+    fn block_scalar(s: &mut Scanner, state: &mut LineState) {
+        let start = s.pos();
+        let line = s.rest();
+        let indent = line.len() - line.trim_start_matches(' ').len();
+        if line.trim().is_empty() || indent > state.depth as usize {
+            s.eat_to_end();
+            s.token(TokenKind::String, start);
+        } else {
+            state.mode = NODES;
+            state.depth = 0;
+        }
+    }
+}
+
+impl Brush for Yaml {
+    fn tokenize_line(&self, line: &str, state: &mut LineState, tokens: &mut Vec<Token>) {
+        let mut s = Scanner::new(line, tokens);
+
+        // a document marker ends a block scalar however it is indented
+        if state.mode == BLOCK_SCALAR && (line.starts_with("---") || line.starts_with("...")) {
+            state.mode = NODES;
+            state.depth = 0;
+        }
+
+        while !s.is_done() {
+            let start = s.pos();
+            match state.mode {
+                BLOCK_SCALAR => Yaml::block_scalar(&mut s, state),
+                DOUBLE_QUOTED => {
+                    if s.eat_quoted('"', Some('\\')) {
+                        state.leave();
+                    }
+                    s.token(TokenKind::String, start);
+                }
+                SINGLE_QUOTED => {
+                    if eat_single_quoted(&mut s) {
+                        state.leave();
+                    }
+                    s.token(TokenKind::String, start);
+                }
+                _ => Yaml::nodes(&mut s, state),
+            }
+        }
+    }
+
+    fn check(&self, code: &str) -> Option<Result<(), SyntaxError>> {
+        Some(check_yaml(code))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::highlighter::testing::{assert_token, highlight};
+
+    #[test]
+    fn block_scalar_after_a_header_with_trailing_blanks() {
+        assert_eq!(
+            highlight(&Yaml, "key: |  \n  text"),
+            [
+                (TokenKind::Attribute, "key"),
+                (TokenKind::Special, "|"),
+                (TokenKind::String, "  text"),
+            ]
+        );
+    }
+
+    #[test]
+    fn block_scalar_header_comment() {
+        assert_eq!(
+            highlight(&Yaml, "key: > # folded\n  text\nnext: 1"),
+            [
+                (TokenKind::Attribute, "key"),
+                (TokenKind::Special, ">"),
+                (TokenKind::Comment, "# folded"),
+                (TokenKind::String, "  text"),
+                (TokenKind::Attribute, "next"),
+                (TokenKind::Number, "1"),
+            ]
+        );
+    }
+
+    #[test]
+    fn block_scalar_ends_at_the_owner_indentation() {
+        let code = "list:\n  - run: |-\n      echo hi\n    name: x\n";
+        assert_token(&Yaml, code, "      echo hi", TokenKind::String);
+        assert_token(&Yaml, code, "name", TokenKind::Attribute);
+    }
+
+    #[test]
+    fn anchors_tags_and_markers() {
+        let code = "%YAML 1.2\n---\nbase: &base\n  tags: !!set { a, b: c }\nuse: *base\n...";
+        assert_token(&Yaml, code, "%YAML 1.2", TokenKind::Directive);
+        assert_token(&Yaml, code, "---", TokenKind::Preprocessor);
+        assert_token(&Yaml, code, "&base", TokenKind::Variable);
+        assert_token(&Yaml, code, "*base", TokenKind::Variable);
+        assert_token(&Yaml, code, "!!set", TokenKind::DataType);
+        assert_token(&Yaml, code, "b", TokenKind::Attribute);
+        assert_token(&Yaml, code, "...", TokenKind::Preprocessor);
+    }
+
+    #[test]
+    fn multi_line_quoted_scalar() {
+        let code = "a: \"one\n  two\"\nb: ~";
+        assert_token(&Yaml, code, "\"one", TokenKind::String);
+        assert_token(&Yaml, code, "  two\"", TokenKind::String);
+        assert_token(&Yaml, code, "~", TokenKind::Value);
+    }
+
+    #[test]
+    fn check_reports_the_error_position() {
+        assert_eq!(Yaml.check("a: 1\nb: [1, 2]\n"), Some(Ok(())));
+        let error = Yaml.check("a: 1\n b: 2\n").unwrap().unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+    }
+}
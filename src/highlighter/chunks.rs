@@ -0,0 +1,149 @@
+use std::ops::Range;
+use std::rc::Rc;
+
+use super::Token;
+
+/// Tokens of a document as the worker streams them, in chunks shared between clones.
+///
+/// Keeping a snapshot per chunk received copies the list of chunks rather than the tokens, so
+/// showing every chunk as it arrives takes linear time overall.
+#[derive(Clone, Debug, Default)]
+pub struct TokenChunks {
+    /// Chunks in order, none of them empty.
+    chunks: Vec<Rc<[Token]>>,
+}
+
+impl TokenChunks {
+    pub fn new() -> Self {
+        TokenChunks::default()
+    }
+
+    /// Replaces the tokens starting at `offset` or later with `tokens`, as a
+    /// [`HighlightResponse::Tokens`](crate::worker::HighlightResponse::Tokens) chunk does.
+    pub fn replace_from(&mut self, offset: usize, tokens: Vec<Token>) {
+        let kept = self
+            .chunks
+            .partition_point(|chunk| chunk[0].range.start < offset);
+        self.chunks.truncate(kept);
+        if let Some(last) = self.chunks.last_mut() {
+            let end = last.partition_point(|token| token.range.start < offset);
+            if end < last.len() {
+                *last = last[..end].into();
+            }
+        }
+        if !tokens.is_empty() {
+            self.chunks.push(tokens.into());
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.chunks.iter().map(|chunk| chunk.len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.chunks.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Token> {
+        self.chunks.iter().flat_map(|chunk| chunk.iter())
+    }
+
+    /// Tokens starting within `range`, found without going through those before it.
+    pub fn range(&self, range: Range<usize>) -> impl Iterator<Item = &Token> {
+        let first = self.chunks.partition_point(|chunk| {
+            chunk
+                .last()
+                .is_some_and(|token| token.range.start < range.start)
+        });
+        self.chunks[first..]
+            .iter()
+            .flat_map(|chunk| chunk.iter())
+            .skip_while(move |token| token.range.start < range.start)
+            .take_while(move |token| token.range.start < range.end)
+    }
+
+    pub fn to_vec(&self) -> Vec<Token> {
+        self.iter().cloned().collect()
+    }
+}
+
+impl From<Vec<Token>> for TokenChunks {
+    fn from(tokens: Vec<Token>) -> Self {
+        let mut chunks = TokenChunks::new();
+        chunks.replace_from(0, tokens);
+        chunks
+    }
+}
+
+/// Snapshots are equal when they share their chunks, without comparing the tokens.
+impl PartialEq for TokenChunks {
+    fn eq(&self, other: &Self) -> bool {
+        self.chunks.len() == other.chunks.len()
+            && self
+                .chunks
+                .iter()
+                .zip(&other.chunks)
+                .all(|(a, b)| Rc::ptr_eq(a, b))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::TokenKind;
+    use super::*;
+
+    fn tokens(starts: &[usize]) -> Vec<Token> {
+        starts
+            .iter()
+            .map(|start| Token::new(TokenKind::Keyword, *start..start + 1))
+            .collect()
+    }
+
+    fn starts(chunks: &TokenChunks) -> Vec<usize> {
+        chunks.iter().map(|token| token.range.start).collect()
+    }
+
+    #[test]
+    fn chunks_replace_the_tokens_from_their_offset() {
+        let mut chunks = TokenChunks::new();
+        chunks.replace_from(0, tokens(&[0, 2, 4]));
+        chunks.replace_from(6, tokens(&[6, 8]));
+        assert_eq!(starts(&chunks), [0, 2, 4, 6, 8]);
+        assert_eq!(chunks.len(), 5);
+
+        // cutting into the first chunk drops the second
+        chunks.replace_from(3, tokens(&[3, 5]));
+        assert_eq!(starts(&chunks), [0, 2, 3, 5]);
+        chunks.replace_from(0, Vec::new());
+        assert!(chunks.is_empty());
+        assert_eq!(chunks.to_vec(), []);
+    }
+
+    #[test]
+    fn snapshots_share_their_chunks() {
+        let mut chunks = TokenChunks::from(tokens(&[0, 2]));
+        let snapshot = chunks.clone();
+        assert_eq!(snapshot, chunks);
+
+        chunks.replace_from(4, tokens(&[4]));
+        assert_ne!(snapshot, chunks);
+        assert_eq!(starts(&snapshot), [0, 2]);
+        // equal tokens in chunks of their own still differ
+        assert_ne!(
+            TokenChunks::from(tokens(&[0])),
+            TokenChunks::from(tokens(&[0]))
+        );
+    }
+
+    #[test]
+    fn ranges_span_chunks() {
+        let mut chunks = TokenChunks::from(tokens(&[0, 2, 4]));
+        chunks.replace_from(5, tokens(&[6, 8]));
+        chunks.replace_from(9, tokens(&[10]));
+        let range = |range: Range<usize>| chunks.range(range).map(|token| token.range.start);
+        assert_eq!(range(2..7).collect::<Vec<_>>(), [2, 4, 6]);
+        assert_eq!(range(3..4).count(), 0);
+        assert_eq!(range(7..20).collect::<Vec<_>>(), [8, 10]);
+        assert_eq!(range(11..20).count(), 0);
+    }
+}
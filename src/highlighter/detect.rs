@@ -0,0 +1,245 @@
+use super::{Language, Registry, TokenKind};
+
+/// Only the start of large inputs is looked at, that's plenty to tell languages apart.
+const SAMPLE_LENGTH: usize = 16 * 1024;
+/// Occurrences of a hint beyond this count add nothing more to the score.
+const MAX_HINT_COUNT: usize = 5;
+/// Weight of the share of words the brush highlights as keywords and alike.
+const KEYWORD_DENSITY_WEIGHT: f32 = 4.0;
+/// Lines at the start and end of the code searched for editor modelines.
+const MODELINE_LINES: usize = 5;
+
+/// A language the code might be written in.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Guess<'a> {
+    pub language: &'a Language,
+    /// Share of the overall score, from `0.0` to `1.0`.
+    pub confidence: f32,
+}
+
+/// Scores `code` against every language in `registry`, most likely language first.
+///
+/// Shebangs and modelines are decisive, otherwise the languages' hints and the density of
+/// keywords their brushes find are weighed against each other. Languages without any evidence
+/// are left out.
+pub fn detect<'a>(registry: &'a Registry, code: &str) -> Vec<Guess<'a>> {
+    let sample = sample(code);
+
+    if sample.trim().is_empty() {
+        return Vec::new();
+    }
+
+    if let Some(language) = shebang(registry, sample).or_else(|| modeline(registry, sample)) {
+        return vec![Guess {
+            language,
+            confidence: 1.0,
+        }];
+    }
+
+    let scores = registry
+        .languages()
+        .iter()
+        .map(|language| (language, score(language, sample)))
+        .filter(|(_, score)| *score > 0.0)
+        .collect::<Vec<_>>();
+    let total = scores.iter().map(|(_, score)| score).sum::<f32>();
+
+    let mut guesses = scores
+        .into_iter()
+        .map(|(language, score)| Guess {
+            language,
+            confidence: score / total,
+        })
+        .collect::<Vec<_>>();
+    guesses.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
+    guesses
+}
+
+fn sample(code: &str) -> &str {
+    if code.len() <= SAMPLE_LENGTH {
+        return code;
+    }
+    let mut end = SAMPLE_LENGTH;
+    while !code.is_char_boundary(end) {
+        end -= 1;
+    }
+    &code[..end]
+}
+
+fn score(language: &Language, code: &str) -> f32 {
+    let hints = language
+        .hints
+        .iter()
+        .map(|(hint, weight)| {
+            weight * code.matches(hint.as_str()).take(MAX_HINT_COUNT).count() as f32
+        })
+        .sum::<f32>();
+
+    let words = code
+        .split(|c: char| !c.is_alphanumeric() && c != '_')
+        .filter(|word| !word.is_empty())
+        .count();
+    // keys look the same in assignments of any language, the data formats find them everywhere
+    let keywords = language
+        .tokenize(code)
+        .iter()
+        .filter(|token| {
+            !matches!(
+                token.kind,
+                TokenKind::Comment
+                    | TokenKind::String
+                    | TokenKind::Number
+                    | TokenKind::Value
+                    | TokenKind::Attribute
+            )
+        })
+        .count();
+    let density = if words == 0 {
+        0.0
+    } else {
+        (keywords as f32 / words as f32).min(1.0)
+    };
+
+    hints + KEYWORD_DENSITY_WEIGHT * density
+}
+
+/// Matches a `#!/usr/bin/env python3` like first line against the languages' interpreters.
+fn shebang<'a>(registry: &'a Registry, code: &str) -> Option<&'a Language> {
+    let line = code.lines().next()?.strip_prefix("#!")?;
+    let mut words = line.split_whitespace();
+    let mut program = words.next()?.rsplit('/').next()?;
+    if program == "env" {
+        program = words.find(|word| !word.starts_with('-'))?;
+    }
+    let interpreter = program.trim_end_matches(|c: char| c.is_ascii_digit() || c == '.');
+
+    registry.languages().iter().find(|language| {
+        language
+            .interpreters
+            .iter()
+            .any(|i| i.eq_ignore_ascii_case(interpreter))
+    })
+}
+
+/// Looks for vim (`vim: ft=ruby`) and emacs (`-*- mode: ruby -*-`) modelines.
+fn modeline<'a>(registry: &'a Registry, code: &str) -> Option<&'a Language> {
+    let lines = code.lines().collect::<Vec<_>>();
+    let head = lines.iter().take(MODELINE_LINES);
+    let tail = lines.iter().rev().take(MODELINE_LINES);
+
+    head.chain(tail).find_map(|line| {
+        let name = vim_modeline(line).or_else(|| emacs_modeline(line))?;
+        registry.find(name)
+    })
+}
+
+fn vim_modeline(line: &str) -> Option<&str> {
+    let (_, options) = line.split_once("vim:").or_else(|| line.split_once("vi:"))?;
+    options
+        .split(|c: char| c.is_whitespace() || c == ':')
+        .find_map(|option| {
+            let (key, value) = option.split_once('=')?;
+            matches!(key, "ft" | "filetype" | "syntax").then_some(value)
+        })
+}
+
+fn emacs_modeline(line: &str) -> Option<&str> {
+    let (_, rest) = line.split_once("-*-")?;
+    let (variables, _) = rest.split_once("-*-")?;
+    if !variables.contains(':') {
+        return Some(variables.trim());
+    }
+    variables.split(';').find_map(|variable| {
+        let (key, value) = variable.split_once(':')?;
+        key.trim()
+            .eq_ignore_ascii_case("mode")
+            .then_some(value.trim())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn best(code: &str) -> Option<String> {
+        let registry = Registry::builtin();
+        let guesses = detect(&registry, code);
+        guesses.first().map(|guess| guess.language.id.clone())
+    }
+
+    #[test]
+    fn shebangs_are_decisive() {
+        for (code, id) in [
+            ("#!/usr/bin/python3.12\nx = 1", "python"),
+            ("#!/usr/bin/env -S python3 -u\nx = 1", "python"),
+            ("#!/bin/bash\nSELECT 1;", "bash"),
+            ("#!/usr/bin/env node\nputs 'x'", "js"),
+            ("#!/usr/local/bin/RUBY\n", "ruby"),
+        ] {
+            let registry = Registry::builtin();
+            let guesses = detect(&registry, code);
+            assert_eq!(guesses.len(), 1, "{code}");
+            assert_eq!(guesses[0].language.id, id, "{code}");
+            assert_eq!(guesses[0].confidence, 1.0);
+        }
+        // unknown interpreters leave it to the code
+        assert_ne!(
+            best("#!/usr/bin/awk -f\n{ print $1 }").as_deref(),
+            Some("bash")
+        );
+    }
+
+    #[test]
+    fn modelines_at_either_end() {
+        assert_eq!(
+            best("# vim: set ft=ruby ts=2:\nx = 1").as_deref(),
+            Some("ruby")
+        );
+        assert_eq!(
+            best("x = 1\n\n// vim:syntax=javascript\n").as_deref(),
+            Some("js")
+        );
+        assert_eq!(
+            best("; -*- mode: Delphi; tab-width: 4 -*-\nx").as_deref(),
+            Some("delphi")
+        );
+        assert_eq!(best("/* -*- sql -*- */\nx").as_deref(), Some("sql"));
+        // beyond the first and last lines they don't count
+        let code = format!("{}# vim: ft=ruby\n{}", "x\n".repeat(10), "x\n".repeat(10));
+        assert_ne!(best(&code).as_deref(), Some("ruby"));
+    }
+
+    #[test]
+    fn hints_and_keywords() {
+        for (code, id) in [
+            ("#include <iostream>\nint main() { std::cout << 1; }", "cpp"),
+            ("def main():\n    print(self.x)\n", "python"),
+            ("SELECT name FROM users WHERE id = 1;", "sql"),
+            ("<?php echo $name; ?>", "php"),
+        ] {
+            assert_eq!(best(code).as_deref(), Some(id), "{code}");
+        }
+    }
+
+    #[test]
+    fn guesses_share_the_confidence() {
+        let registry = Registry::builtin();
+        let guesses = detect(&registry, "public static void main(String[] args) {}");
+        assert!(guesses.len() > 1);
+        assert!(guesses
+            .windows(2)
+            .all(|pair| pair[0].confidence >= pair[1].confidence));
+        let total = guesses.iter().map(|guess| guess.confidence).sum::<f32>();
+        assert!((total - 1.0).abs() < 1e-4);
+
+        assert!(detect(&registry, " \n\t").is_empty());
+    }
+
+    #[test]
+    fn long_code_is_cut_on_a_character_boundary() {
+        let code = "é".repeat(SAMPLE_LENGTH);
+        assert_eq!(sample(&code).len(), SAMPLE_LENGTH);
+        let code = format!("x{code}");
+        assert_eq!(sample(&code).len(), SAMPLE_LENGTH - 1);
+    }
+}
@@ -0,0 +1,256 @@
+use serde_derive::{Deserialize, Serialize};
+
+use super::{Language, LineState, Token};
+
+/// A tokenized line along with the lexer states around it.
+#[derive(Clone, Debug, PartialEq)]
+struct CachedLine {
+    /// The line without its `\n`, but with the `\r` of a windows line break.
+    text: String,
+    /// State the line starts in, the previous line's `end`.
+    start: LineState,
+    end: LineState,
+    /// Tokens with ranges relative to the start of the line.
+    tokens: Vec<Token>,
+}
+
+/// What an [`Incremental::update`] had to do.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UpdateStats {
+    /// Lines tokenized so far, all of them once `done`.
+    pub lines: usize,
+    /// Lines the brush tokenized anew, the rest came from the cache.
+    pub tokenized: usize,
+    /// Index of the first line tokenized anew, the ones before kept their tokens.
+    pub first_changed: usize,
+    /// Whether every line is tokenized, rather than the update stopping at its limit.
+    pub done: bool,
+}
+
+/// Keeps the tokens of a document line by line, so re-highlighting it after an edit only
+/// tokenizes the changed lines and those following until the lexer state is the same as before.
+#[derive(Clone, Debug, Default)]
+pub struct Incremental {
+    /// Id of the language the cached lines were tokenized with.
+    language: Option<String>,
+    lines: Vec<CachedLine>,
+}
+
+impl Incremental {
+    pub fn new() -> Self {
+        Incremental::default()
+    }
+
+    /// Forgets every line, e.g. once the code is gone.
+    pub fn clear(&mut self) {
+        self.language = None;
+        self.lines.clear();
+    }
+
+    /// Brings the cache up to date with `code`, highlighted as `language`.
+    pub fn update(&mut self, code: &str, language: &Language) -> UpdateStats {
+        self.update_limited(code, language, usize::MAX)
+    }
+
+    /// Like [`Incremental::update`], but stops after tokenizing `limit` lines. The lines up to
+    /// there are cached, so calling it again with the same code continues where it stopped.
+    pub fn update_limited(&mut self, code: &str, language: &Language, limit: usize) -> UpdateStats {
+        if self.language.as_deref() != Some(language.id.as_str()) {
+            self.clear();
+            self.language = Some(language.id.clone());
+        }
+
+        let texts = code.split('\n').collect::<Vec<_>>();
+        let mut lines = std::mem::take(&mut self.lines);
+
+        let prefix = lines
+            .iter()
+            .zip(&texts)
+            .take_while(|(cached, text)| cached.text == **text)
+            .count();
+        let suffix = lines[prefix..]
+            .iter()
+            .rev()
+            .zip(texts[prefix..].iter().rev())
+            .take_while(|(cached, text)| cached.text == **text)
+            .count();
+        // cached lines matching the unchanged end of the code, starting at `suffix_start` in it
+        let mut tail = lines.split_off(lines.len() - suffix);
+        let suffix_start = texts.len() - suffix;
+        lines.truncate(prefix);
+
+        let mut state = lines
+            .last()
+            .map(|line| line.end.clone())
+            .unwrap_or_default();
+        let mut tokenized = 0;
+        let mut done = true;
+
+        for (index, text) in texts.iter().enumerate().skip(prefix) {
+            // once a line starts in the state it did before, so does every following one
+            if index >= suffix_start && tail[index - suffix_start].start == state {
+                lines.extend(tail.drain(index - suffix_start..));
+                break;
+            }
+
+            if tokenized == limit {
+                done = false;
+                break;
+            }
+
+            let start = state.clone();
+            let mut tokens = Vec::new();
+            let content = text.strip_suffix('\r').unwrap_or(text);
+            language
+                .brush
+                .tokenize_line(content, &mut state, &mut tokens);
+            tokenized += 1;
+            lines.push(CachedLine {
+                text: text.to_string(),
+                start,
+                end: state.clone(),
+                tokens,
+            });
+        }
+
+        self.lines = lines;
+        UpdateStats {
+            lines: self.lines.len(),
+            tokenized,
+            first_changed: prefix,
+            done,
+        }
+    }
+
+    /// Tokens of the whole document, with ranges being byte offsets into the code of the last
+    /// [`Incremental::update`].
+    pub fn tokens(&self) -> Vec<Token> {
+        self.tokens_from(0).1
+    }
+
+    /// Tokens of the lines from `line` on, along with the byte offset that line starts at.
+    pub fn tokens_from(&self, line: usize) -> (usize, Vec<Token>) {
+        let start = self
+            .lines
+            .iter()
+            .take(line)
+            .map(|line| line.text.len() + 1)
+            .sum::<usize>();
+        let mut tokens = Vec::new();
+        let mut offset = start;
+        for line in self.lines.iter().skip(line) {
+            tokens.extend(line.tokens.iter().map(|token| {
+                Token::new(
+                    token.kind,
+                    token.range.start + offset..token.range.end + offset,
+                )
+            }));
+            offset += line.text.len() + 1;
+        }
+        (start, tokens)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{lines, tokenize, Registry};
+    use super::*;
+
+    fn language(id: &str) -> Language {
+        Registry::builtin().get(id).unwrap().clone()
+    }
+
+    fn assert_matches_tokenize(cache: &Incremental, code: &str, language: &Language) {
+        let expected = tokenize(code, language.brush.as_ref());
+        assert_eq!(cache.tokens(), expected, "{code:?}");
+        assert_eq!(lines(code, &cache.tokens()), lines(code, &expected));
+    }
+
+    #[test]
+    fn edits_match_a_full_tokenize() {
+        let cpp = language("cpp");
+        let mut cache = Incremental::new();
+        let edits = [
+            "int a = 1;\nint b = 2;\r\nint c = 3;\n",
+            "int a = 1;\nint b = 2;\r\nint c = 3;\nint d = 4;",
+            "int a = 1;\n/* int b = 2;\r\nint c = 3;\nint d = 4;",
+            "int a = 1;\n/* int b = 2;\r\nint c = 3; */\nint d = 4;",
+            "int a = 1;\nint d = 4;",
+            "",
+            "\"one\nint two = 2;",
+        ];
+        for code in edits {
+            let stats = cache.update(code, &cpp);
+            assert!(stats.done);
+            assert_eq!(stats.lines, code.split('\n').count());
+            assert_matches_tokenize(&cache, code, &cpp);
+        }
+    }
+
+    #[test]
+    fn unchanged_lines_come_from_the_cache() {
+        let cpp = language("cpp");
+        let mut cache = Incremental::new();
+        let code = "int a;\nint b;\nint c;\nint d;";
+        assert_eq!(cache.update(code, &cpp).tokenized, 4);
+        assert_eq!(cache.update(code, &cpp).tokenized, 0);
+
+        let stats = cache.update("int a;\nlong b;\nint c;\nint d;", &cpp);
+        assert_eq!((stats.first_changed, stats.tokenized), (1, 1));
+
+        // opening a comment changes the state of every following line
+        let stats = cache.update("int a;\n/* long b;\nint c;\nint d;", &cpp);
+        assert_eq!((stats.first_changed, stats.tokenized), (1, 3));
+    }
+
+    #[test]
+    fn tokens_from_a_line_are_offset_into_the_code() {
+        let cpp = language("cpp");
+        let mut cache = Incremental::new();
+        let code = "int a;\r\nint b;\nint c;";
+        cache.update(code, &cpp);
+        let (start, tokens) = cache.tokens_from(1);
+        assert_eq!(start, 8);
+        let expected = tokenize(code, cpp.brush.as_ref())
+            .into_iter()
+            .filter(|token| token.range.start >= start)
+            .collect::<Vec<_>>();
+        assert_eq!(tokens, expected);
+    }
+
+    #[test]
+    fn limited_updates_continue_where_they_stopped() {
+        let cpp = language("cpp");
+        let mut cache = Incremental::new();
+        let code = (0..10)
+            .map(|n| format!("int a{n} = {n}; // {n}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let mut rounds = 0;
+        loop {
+            let stats = cache.update_limited(&code, &cpp, 3);
+            assert!(stats.tokenized <= 3);
+            rounds += 1;
+            if stats.done {
+                break;
+            }
+            assert_eq!(stats.lines, 3 * rounds);
+        }
+        assert_eq!(rounds, 4);
+        assert_matches_tokenize(&cache, &code, &cpp);
+    }
+
+    #[test]
+    fn switching_languages_starts_over() {
+        let mut cache = Incremental::new();
+        let code = "# comment\nx = 1";
+        cache.update(code, &language("cpp"));
+        let python = language("python");
+        let stats = cache.update(code, &python);
+        assert_eq!((stats.first_changed, stats.tokenized), (0, 2));
+        assert_matches_tokenize(&cache, code, &python);
+
+        cache.clear();
+        assert!(cache.tokens().is_empty());
+    }
+}
@@ -0,0 +1,215 @@
+//! Pure Rust highlighting engine, a port of the `dp.SyntaxHighlighter` brushes.
+//!
+//! Brushes work line by line and hand a [`LineState`] from one line to the next, so multi line
+//! constructs such as block comments survive the line break.
+
+mod brushes;
+mod chunks;
+mod detect;
+mod incremental;
+mod registry;
+pub mod scanner;
+#[cfg(test)]
+pub(crate) mod testing;
+mod token;
+
+pub use chunks::TokenChunks;
+pub use detect::{detect, Guess};
+pub use incremental::{Incremental, UpdateStats};
+pub use registry::{Icon, Language, Registry};
+pub use token::{Token, TokenKind};
+
+use serde_derive::{Deserialize, Serialize};
+
+/// Lexer state carried from the end of one line to the start of the next.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct LineState {
+    /// Brush specific construct the line starts inside of, `0` meaning none.
+    pub mode: u32,
+    /// Nesting depth of `mode`, for constructs which nest.
+    pub depth: u32,
+    /// Text closing the open construct, e.g. the quotes of a python docstring.
+    pub delimiter: String,
+    /// Modes and depths to return to once the open construct closes, innermost last, for
+    /// constructs embedded in each other like script in a template literal in JSX.
+    pub stack: Vec<(u32, u32)>,
+}
+
+impl LineState {
+    /// Enters `mode` at `depth`, embedded in the current construct, which [`LineState::leave`]
+    /// returns to.
+    pub fn enter(&mut self, mode: u32, depth: u32) {
+        self.stack.push((self.mode, self.depth));
+        self.mode = mode;
+        self.depth = depth;
+    }
+
+    /// Returns to the construct the current one is embedded in, mode `0` when there is none.
+    pub fn leave(&mut self) {
+        (self.mode, self.depth) = self.stack.pop().unwrap_or_default();
+    }
+}
+
+/// Turns source code into tokens, implemented once per language and registered with a
+/// [`Registry`] through a [`Language`].
+pub trait Brush: Send + Sync {
+    /// Tokenizes a single line without its line terminator, continuing from `state`.
+    ///
+    /// Token ranges are relative to the start of `line`, `state` is left as the next line starts.
+    fn tokenize_line(&self, line: &str, state: &mut LineState, tokens: &mut Vec<Token>);
+
+    /// Parses the whole of `code`, for data formats with a parser to check them against.
+    ///
+    /// `None` when the language isn't checked, otherwise whether `code` parses.
+    fn check(&self, _code: &str) -> Option<Result<(), SyntaxError>> {
+        None
+    }
+}
+
+/// Why and where [`Brush::check`] failed to parse the code.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyntaxError {
+    /// One based line number.
+    pub line: usize,
+    /// One based column, in characters.
+    pub column: usize,
+    pub message: String,
+}
+
+impl SyntaxError {
+    pub fn new(line: usize, column: usize, message: impl Into<String>) -> Self {
+        SyntaxError {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+
+    /// The error at byte `offset` into `code`.
+    pub(crate) fn at_offset(code: &str, offset: usize, message: impl Into<String>) -> Self {
+        let mut offset = offset.min(code.len());
+        while !code.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        let before = &code[..offset];
+        let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+        SyntaxError::new(
+            before.matches('\n').count() + 1,
+            before[line_start..].chars().count() + 1,
+            message,
+        )
+    }
+}
+
+impl std::fmt::Display for SyntaxError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+/// Tokenizes the whole of `code`, token ranges being byte offsets into it.
+pub fn tokenize(code: &str, brush: &dyn Brush) -> Vec<Token> {
+    let mut state = LineState::default();
+    let mut tokens = Vec::new();
+    let mut line_tokens = Vec::new();
+    let mut offset = 0;
+
+    for line in code.split('\n') {
+        let content = line.strip_suffix('\r').unwrap_or(line);
+
+        brush.tokenize_line(content, &mut state, &mut line_tokens);
+        tokens.extend(line_tokens.drain(..).map(|token| {
+            Token::new(
+                token.kind,
+                token.range.start + offset..token.range.end + offset,
+            )
+        }));
+
+        offset += line.len() + 1;
+    }
+
+    tokens
+}
+
+/// Splits `code` into its lines, each as the consecutive parts the `tokens` highlight or leave
+/// plain. Line terminators are dropped.
+pub fn lines<'a>(code: &'a str, tokens: &[Token]) -> Vec<Vec<(Option<TokenKind>, &'a str)>> {
+    let mut tokens = tokens.iter().peekable();
+    let mut lines = Vec::new();
+    let mut offset = 0;
+
+    for line in code.split('\n') {
+        let end = offset + line.strip_suffix('\r').unwrap_or(line).len();
+        let mut parts = Vec::new();
+        let mut pos = offset;
+
+        while let Some(token) = tokens.next_if(|token| token.range.start < end) {
+            if token.range.start > pos {
+                parts.push((None, &code[pos..token.range.start]));
+            }
+            parts.push((Some(token.kind), &code[token.range.start..token.range.end]));
+            pos = token.range.end;
+        }
+        if pos < end {
+            parts.push((None, &code[pos..end]));
+        }
+
+        lines.push(parts);
+        offset += line.len() + 1;
+    }
+
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::scanner::Scanner;
+    use super::*;
+
+    /// Highlights every word as a keyword.
+    struct Words;
+
+    impl Brush for Words {
+        fn tokenize_line(&self, line: &str, _state: &mut LineState, tokens: &mut Vec<Token>) {
+            let mut s = Scanner::new(line, tokens);
+            while !s.is_done() {
+                let start = s.pos();
+                if s.eat_word() {
+                    s.token(TokenKind::Keyword, start);
+                } else {
+                    s.bump();
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn tokenize_offsets_tokens_into_the_code() {
+        let code = "ab cd\r\n\nef";
+        let ranges = tokenize(code, &Words)
+            .into_iter()
+            .map(|token| token.range)
+            .collect::<Vec<_>>();
+        assert_eq!(ranges, [0..2, 3..5, 8..10]);
+    }
+
+    #[test]
+    fn lines_split_the_code_into_parts() {
+        let code = "ab, cd\r\n\n  éf";
+        let tokens = tokenize(code, &Words);
+        let keyword = Some(TokenKind::Keyword);
+        assert_eq!(
+            lines(code, &tokens),
+            [
+                vec![(keyword, "ab"), (None, ", "), (keyword, "cd")],
+                vec![],
+                vec![(None, "  "), (keyword, "éf")],
+            ]
+        );
+        assert_eq!(lines("", &[]), [Vec::new()]);
+    }
+}
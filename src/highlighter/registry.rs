@@ -0,0 +1,278 @@
+use std::sync::Arc;
+
+use super::{tokenize, Brush, SyntaxError, Token};
+
+/// Image shown next to a language, `path` being relative to the served `dist/` directory and
+/// lacking its extension so the app can swap in a webp variant.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Icon {
+    pub path: String,
+    pub extension: String,
+}
+
+impl Icon {
+    pub fn new(path: &str, extension: &str) -> Self {
+        Icon {
+            path: path.to_string(),
+            extension: extension.to_string(),
+        }
+    }
+}
+
+/// A language known to a [`Registry`], its brush together with the metadata around it.
+#[derive(Clone)]
+pub struct Language {
+    /// Unique identifier, also used as the alias shCore knows the language by.
+    pub id: String,
+    /// Human readable name shown in the language picker.
+    pub name: String,
+    pub aliases: Vec<String>,
+    /// File extensions without the leading dot.
+    pub extensions: Vec<String>,
+    pub mime_types: Vec<String>,
+    pub icon: Option<Icon>,
+    pub tab_width: usize,
+    /// Programs named in shebang lines, without version suffixes.
+    pub interpreters: Vec<String>,
+    /// Snippets typical for the language, with the weight each occurrence adds when detecting it.
+    pub hints: Vec<(String, f32)>,
+    pub brush: Arc<dyn Brush>,
+}
+
+impl Language {
+    pub fn new(id: &str, name: &str, brush: Arc<dyn Brush>) -> Self {
+        Language {
+            id: id.to_string(),
+            name: name.to_string(),
+            aliases: Vec::new(),
+            extensions: Vec::new(),
+            mime_types: Vec::new(),
+            icon: None,
+            tab_width: 4,
+            interpreters: Vec::new(),
+            hints: Vec::new(),
+            brush,
+        }
+    }
+
+    /// Whether `alias` names this language, either by its id or one of its aliases.
+    pub fn is_called(&self, alias: &str) -> bool {
+        self.id.eq_ignore_ascii_case(alias)
+            || self.aliases.iter().any(|a| a.eq_ignore_ascii_case(alias))
+    }
+
+    pub fn tokenize(&self, code: &str) -> Vec<Token> {
+        tokenize(code, self.brush.as_ref())
+    }
+
+    /// Whether `code` parses, `None` when the language isn't checked.
+    pub fn check(&self, code: &str) -> Option<Result<(), SyntaxError>> {
+        self.brush.check(code)
+    }
+}
+
+impl std::fmt::Debug for Language {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Language")
+            .field("id", &self.id)
+            .field("name", &self.name)
+            .finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for Language {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+/// Languages available for highlighting, in the order they are offered to the user.
+#[derive(Clone, Debug, Default)]
+pub struct Registry {
+    languages: Vec<Language>,
+}
+
+impl Registry {
+    /// A registry without any languages.
+    pub fn empty() -> Self {
+        Registry::default()
+    }
+
+    /// A registry holding the languages shipped with the app.
+    pub fn builtin() -> Self {
+        let mut registry = Registry::empty();
+        for language in super::brushes::builtin() {
+            registry.register(language);
+        }
+        registry
+    }
+
+    /// Adds `language`, replacing a previously registered language with the same id.
+    pub fn register(&mut self, language: Language) {
+        match self.languages.iter_mut().find(|l| l.id == language.id) {
+            Some(existing) => *existing = language,
+            None => self.languages.push(language),
+        }
+    }
+
+    pub fn languages(&self) -> &[Language] {
+        &self.languages
+    }
+
+    pub fn get(&self, id: &str) -> Option<&Language> {
+        self.languages.iter().find(|l| l.id == id)
+    }
+
+    /// Looks up a language by id or alias, ignoring case.
+    pub fn find(&self, alias: &str) -> Option<&Language> {
+        self.languages.iter().find(|l| l.is_called(alias))
+    }
+
+    /// Looks up a language by file extension, with or without the leading dot.
+    pub fn find_by_extension(&self, extension: &str) -> Option<&Language> {
+        let extension = extension.trim_start_matches('.');
+        self.languages.iter().find(|l| {
+            l.extensions
+                .iter()
+                .any(|e| e.eq_ignore_ascii_case(extension))
+        })
+    }
+
+    pub fn find_by_mime_type(&self, mime_type: &str) -> Option<&Language> {
+        let mime_type = mime_type.split(';').next().unwrap_or_default().trim();
+        self.languages.iter().find(|l| {
+            l.mime_types
+                .iter()
+                .any(|m| m.eq_ignore_ascii_case(mime_type))
+        })
+    }
+
+    /// Highlights `code` with the language registered under `alias`.
+    pub fn highlight(&self, code: &str, alias: &str) -> Option<Vec<Token>> {
+        self.find(alias).map(|language| language.tokenize(code))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::scanner::{contains, Scanner};
+    use super::super::{detect, LineState, TokenKind};
+    use super::*;
+
+    /// A DSL of `--` comments and `<< >>` blocks spanning lines, as another crate would write it.
+    struct Dsl;
+
+    const BLOCK: u32 = 1;
+
+    impl Brush for Dsl {
+        fn tokenize_line(&self, line: &str, state: &mut LineState, tokens: &mut Vec<Token>) {
+            let mut s = Scanner::new(line, tokens);
+            while !s.is_done() {
+                let start = s.pos();
+                if state.mode == BLOCK {
+                    if s.eat_until(">>") {
+                        state.leave();
+                    }
+                    s.token(TokenKind::String, start);
+                } else if s.eat("--") {
+                    s.eat_to_end();
+                    s.token(TokenKind::Comment, start);
+                } else if s.eat("<<") {
+                    state.enter(BLOCK, 0);
+                    s.token(TokenKind::String, start);
+                } else if s.eat_word() {
+                    if contains(&["rule", "when"], s.slice(start)) {
+                        s.token(TokenKind::Keyword, start);
+                    }
+                } else {
+                    s.bump();
+                }
+            }
+        }
+    }
+
+    fn dsl() -> Language {
+        Language {
+            aliases: vec!["rules".to_string()],
+            extensions: vec!["dsl".to_string()],
+            mime_types: vec!["text/x-dsl".to_string()],
+            hints: vec![("rule ".to_string(), 5.0)],
+            ..Language::new("dsl", "My DSL", Arc::new(Dsl))
+        }
+    }
+
+    #[test]
+    fn languages_are_found_by_alias_extension_and_mime_type() {
+        let registry = Registry::builtin();
+        let id = |language: Option<&Language>| language.map(|language| language.id.clone());
+        assert_eq!(id(registry.find("RS")), Some("rust".to_string()));
+        assert_eq!(id(registry.find("golang")), Some("go".to_string()));
+        assert_eq!(id(registry.find("cobol")), None);
+        assert_eq!(id(registry.get("RUST")), None);
+        assert_eq!(
+            id(registry.find_by_extension(".PY")),
+            Some("python".to_string())
+        );
+        assert_eq!(
+            id(registry.find_by_extension("rs")),
+            Some("rust".to_string())
+        );
+        assert_eq!(id(registry.find_by_extension("exe")), None);
+        assert_eq!(
+            id(registry.find_by_mime_type("text/x-rust; charset=utf-8")),
+            Some("rust".to_string())
+        );
+        assert_eq!(id(registry.find_by_mime_type("image/png")), None);
+    }
+
+    #[test]
+    fn registering_an_id_again_replaces_the_language_in_place() {
+        let mut registry = Registry::builtin();
+        let count = registry.languages().len();
+        let position = |registry: &Registry| {
+            registry
+                .languages()
+                .iter()
+                .position(|language| language.id == "python")
+        };
+        let before = position(&registry);
+
+        registry.register(Language::new("python", "Snake", Arc::new(Dsl)));
+        assert_eq!(registry.languages().len(), count);
+        assert_eq!(position(&registry), before);
+        assert_eq!(registry.get("python").unwrap().name, "Snake");
+        // the aliases went with the replaced language
+        assert!(registry.find("py").is_none());
+    }
+
+    #[test]
+    fn custom_brushes_highlight_end_to_end() {
+        let mut registry = Registry::empty();
+        registry.register(dsl());
+        assert_eq!(registry.find_by_extension("dsl").unwrap().id, "dsl");
+
+        let code = "rule x -- first\nwhen <<a\nb>> rule";
+        let tokens = registry.highlight(code, "rules").unwrap();
+        let texts = tokens
+            .iter()
+            .map(|token| (token.kind, &code[token.range.clone()]))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            texts,
+            [
+                (TokenKind::Keyword, "rule"),
+                (TokenKind::Comment, "-- first"),
+                (TokenKind::Keyword, "when"),
+                (TokenKind::String, "<<"),
+                (TokenKind::String, "a"),
+                (TokenKind::String, "b>>"),
+                (TokenKind::Keyword, "rule"),
+            ]
+        );
+
+        let mut registry = Registry::builtin();
+        registry.register(dsl());
+        let guesses = detect(&registry, "rule a\nrule b\nrule c");
+        assert_eq!(guesses[0].language.id, "dsl");
+    }
+}
@@ -0,0 +1,203 @@
+//! What brushes are built from, for those of other crates as much as the built-in ones.
+
+use super::token::{Token, TokenKind};
+
+/// Cursor over a single line, collecting the tokens brushes emit for it.
+pub struct Scanner<'a> {
+    line: &'a str,
+    pos: usize,
+    tokens: &'a mut Vec<Token>,
+}
+
+impl<'a> Scanner<'a> {
+    pub fn new(line: &'a str, tokens: &'a mut Vec<Token>) -> Self {
+        Scanner {
+            line,
+            pos: 0,
+            tokens,
+        }
+    }
+
+    pub fn pos(&self) -> usize {
+        self.pos
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.pos >= self.line.len()
+    }
+
+    pub fn rest(&self) -> &'a str {
+        &self.line[self.pos..]
+    }
+
+    pub fn consumed(&self) -> &'a str {
+        &self.line[..self.pos]
+    }
+
+    pub fn slice(&self, start: usize) -> &'a str {
+        &self.line[start..self.pos]
+    }
+
+    pub fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    pub fn peek_nth(&self, n: usize) -> Option<char> {
+        self.rest().chars().nth(n)
+    }
+
+    pub fn prev(&self) -> Option<char> {
+        self.consumed().chars().next_back()
+    }
+
+    pub fn starts_with(&self, prefix: &str) -> bool {
+        self.rest().starts_with(prefix)
+    }
+
+    /// Whether only whitespace precedes the cursor on this line.
+    pub fn at_line_start(&self) -> bool {
+        self.consumed().trim().is_empty()
+    }
+
+    /// Whether the cursor is not in the middle of a word.
+    pub fn at_word_boundary(&self) -> bool {
+        !self.prev().is_some_and(is_word_char)
+    }
+
+    pub fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    pub fn eat(&mut self, expected: &str) -> bool {
+        if self.starts_with(expected) {
+            self.pos += expected.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn eat_char(&mut self, expected: char) -> bool {
+        if self.peek() == Some(expected) {
+            self.pos += expected.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn eat_while(&mut self, predicate: impl Fn(char) -> bool) -> bool {
+        let start = self.pos;
+        while let Some(c) = self.peek() {
+            if !predicate(c) {
+                break;
+            }
+            self.pos += c.len_utf8();
+        }
+        self.pos > start
+    }
+
+    pub fn eat_to_end(&mut self) {
+        self.pos = self.line.len();
+    }
+
+    /// Consumes everything up to and including `delimiter`, or the rest of the line when it is missing.
+    pub fn eat_until(&mut self, delimiter: &str) -> bool {
+        match self.rest().find(delimiter) {
+            Some(index) => {
+                self.pos += index + delimiter.len();
+                true
+            }
+            None => {
+                self.eat_to_end();
+                false
+            }
+        }
+    }
+
+    /// Consumes the body and closing `quote` of a string whose opening quote was already consumed.
+    ///
+    /// Returns `false` when the line ends before the string is closed.
+    pub fn eat_quoted(&mut self, quote: char, escape: Option<char>) -> bool {
+        while let Some(c) = self.bump() {
+            if Some(c) == escape {
+                self.bump();
+            } else if c == quote {
+                return true;
+            }
+        }
+        false
+    }
+
+    pub fn eat_word(&mut self) -> bool {
+        self.eat_while(is_word_char)
+    }
+
+    /// Consumes a decimal or `0x` prefixed number if one starts at the cursor.
+    pub fn eat_number(&mut self) -> bool {
+        if !self.at_word_boundary() || !self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            return false;
+        }
+        if self.eat("0x") || self.eat("0X") {
+            self.eat_while(|c| c.is_ascii_hexdigit());
+        } else {
+            self.eat_while(|c| c.is_ascii_digit());
+            if self.peek() == Some('.') && self.peek_nth(1).is_some_and(|c| c.is_ascii_digit()) {
+                self.bump();
+                self.eat_while(|c| c.is_ascii_digit());
+            }
+        }
+        self.eat_while(is_word_char);
+        true
+    }
+
+    /// Consumes a number the way newer languages write them: decimal, `0x`, `0b` or `0o`
+    /// prefixed, with `_` separators, fractions, exponents and suffixes like Go's imaginary `i`.
+    pub fn eat_separated_number(&mut self) -> bool {
+        let starts = self.peek().is_some_and(|c| c.is_ascii_digit())
+            || self.peek() == Some('.') && self.peek_nth(1).is_some_and(|c| c.is_ascii_digit());
+        if !self.at_word_boundary() || !starts {
+            return false;
+        }
+        let hex = self.starts_with("0x") || self.starts_with("0X");
+        // hex floats take a `p` exponent, `e` being a digit in them
+        let exponent = if hex { ['p', 'P'] } else { ['e', 'E'] };
+        while let Some(c) = self.peek() {
+            if !is_word_char(c) && c != '.' {
+                break;
+            }
+            self.bump();
+            if exponent.contains(&c) && matches!(self.peek(), Some('+' | '-')) {
+                self.bump();
+            }
+        }
+        true
+    }
+
+    /// Emits a token from `start` up to the cursor.
+    pub fn token(&mut self, kind: TokenKind, start: usize) {
+        if start < self.pos {
+            self.tokens.push(Token::new(kind, start..self.pos));
+        }
+    }
+}
+
+pub fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Looks up `word` in a sorted keyword list.
+pub fn contains(words: &[&str], word: &str) -> bool {
+    words.binary_search(&word).is_ok()
+}
+
+/// Looks up `word` in a sorted, lower case keyword list.
+pub fn contains_ignore_case(words: &[&str], word: &str) -> bool {
+    if word.bytes().any(|b| b.is_ascii_uppercase()) {
+        contains(words, &word.to_ascii_lowercase())
+    } else {
+        contains(words, word)
+    }
+}
@@ -0,0 +1,50 @@
+//! Helpers for the brushes' tests.
+
+use super::{tokenize, Brush, Token, TokenKind};
+
+/// Asserts that `tokens` are in order, don't overlap, aren't empty and stay within their line of
+/// `code`, as [`super::lines`] and the exports rely on.
+pub(crate) fn assert_well_formed(code: &str, tokens: &[Token]) {
+    let mut end = 0;
+    for token in tokens {
+        let range = token.range.clone();
+        assert!(range.start < range.end, "empty token {token:?}");
+        assert!(
+            range.start >= end,
+            "token {token:?} starts before the previous one ends at {end}"
+        );
+        assert!(
+            range.end <= code.len(),
+            "token {token:?} ends past the code"
+        );
+        assert!(
+            code.is_char_boundary(range.start) && code.is_char_boundary(range.end),
+            "token {token:?} splits a character"
+        );
+        assert!(
+            !code[range].contains('\n'),
+            "token {token:?} spans a line break"
+        );
+        end = token.range.end;
+    }
+}
+
+/// Tokenizes `code` with `brush`, checking the tokens are well formed, and returns each token's
+/// kind and text.
+pub(crate) fn highlight<'a>(brush: &dyn Brush, code: &'a str) -> Vec<(TokenKind, &'a str)> {
+    let tokens = tokenize(code, brush);
+    assert_well_formed(code, &tokens);
+    tokens
+        .iter()
+        .map(|token| (token.kind, &code[token.range.clone()]))
+        .collect()
+}
+
+/// Asserts that `brush` highlights `text` in `code` as `kind`, as a token of its own.
+pub(crate) fn assert_token(brush: &dyn Brush, code: &str, text: &str, kind: TokenKind) {
+    let tokens = highlight(brush, code);
+    assert!(
+        tokens.contains(&(kind, text)),
+        "expected {text:?} as {kind:?} in {tokens:?}"
+    );
+}
@@ -0,0 +1,98 @@
+use std::ops::Range;
+
+use serde_derive::{Deserialize, Serialize};
+
+/// Kind of a highlighted token, one per CSS class known to `SyntaxHighlighter.css` and the brushes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TokenKind {
+    Comment,
+    String,
+    Keyword,
+    Preprocessor,
+    Number,
+    DataType,
+    Value,
+    Important,
+    Directive,
+    Annotation,
+    Decorator,
+    Variable,
+    Function,
+    Operator,
+    Symbol,
+    Builtin,
+    Special,
+    Tag,
+    TagName,
+    Attribute,
+    AttributeValue,
+    CData,
+}
+
+impl TokenKind {
+    pub const ALL: [TokenKind; 22] = [
+        TokenKind::Comment,
+        TokenKind::String,
+        TokenKind::Keyword,
+        TokenKind::Preprocessor,
+        TokenKind::Number,
+        TokenKind::DataType,
+        TokenKind::Value,
+        TokenKind::Important,
+        TokenKind::Directive,
+        TokenKind::Annotation,
+        TokenKind::Decorator,
+        TokenKind::Variable,
+        TokenKind::Function,
+        TokenKind::Operator,
+        TokenKind::Symbol,
+        TokenKind::Builtin,
+        TokenKind::Special,
+        TokenKind::Tag,
+        TokenKind::TagName,
+        TokenKind::Attribute,
+        TokenKind::AttributeValue,
+        TokenKind::CData,
+    ];
+
+    /// CSS class shCore used for this kind of match.
+    pub fn css_class(self) -> &'static str {
+        match self {
+            TokenKind::Comment => "comment",
+            TokenKind::String => "string",
+            TokenKind::Keyword => "keyword",
+            TokenKind::Preprocessor => "preprocessor",
+            TokenKind::Number => "number",
+            TokenKind::DataType => "datatypes",
+            TokenKind::Value => "value",
+            TokenKind::Important => "important",
+            TokenKind::Directive => "directive",
+            TokenKind::Annotation => "annotation",
+            TokenKind::Decorator => "decorator",
+            TokenKind::Variable => "vars",
+            TokenKind::Function => "func",
+            TokenKind::Operator => "op",
+            TokenKind::Symbol => "symbol",
+            TokenKind::Builtin => "builtin",
+            TokenKind::Special => "special",
+            TokenKind::Tag => "tag",
+            TokenKind::TagName => "tag-name",
+            TokenKind::Attribute => "attribute",
+            TokenKind::AttributeValue => "attribute-value",
+            TokenKind::CData => "cdata",
+        }
+    }
+}
+
+/// A highlighted part of the code, `range` being byte offsets into the tokenized text.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Token {
+    pub kind: TokenKind,
+    pub range: Range<usize>,
+}
+
+impl Token {
+    pub fn new(kind: TokenKind, range: Range<usize>) -> Self {
+        Token { kind, range }
+    }
+}
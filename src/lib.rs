@@ -0,0 +1,6 @@
+#![deny(clippy::all)]
+
+pub mod export;
+pub mod highlighter;
+pub mod theme;
+pub mod worker;
@@ -1,6 +1,10 @@
 #![deny(clippy::all)]
 
 mod app;
+mod preview;
+mod share;
+mod snippets;
+mod state;
 
 pub mod utils;
 
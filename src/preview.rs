@@ -0,0 +1,283 @@
+//! The highlighted code, rendered by Yew from the tokens.
+//!
+//! Only the lines in view and a buffer around them are rendered, spacers standing in for the
+//! rest, so even files with a hundred thousand lines scroll smoothly. The tokens are looked up
+//! for those lines alone, so each chunk the worker streams shows without going over the others.
+//!
+//! For the languages with a parser, the toolbar shows whether the code parses, the line of a
+//! parse error being marked and a click away.
+
+use web_sys::{Element, HtmlInputElement};
+use yew::prelude::*;
+
+use planetb_syntax_highligher::highlighter::{lines, SyntaxError, Token, TokenChunks, TokenKind};
+
+/// Height of a line, as the stylesheet sets it.
+const LINE_HEIGHT_PX: usize = 14;
+/// Lines rendered above and below those in view, so scrolling doesn't reveal blank space.
+const BUFFER_LINES: usize = 50;
+/// Height of the preview, beyond which it scrolls.
+const MAX_HEIGHT: &str = "70vh";
+
+#[derive(Properties, PartialEq)]
+pub struct PreviewProps {
+    pub code: AttrValue,
+    /// Tokens of `code`, as the selected language's brush found them. Lines past the last chunk
+    /// received show plain.
+    pub tokens: TokenChunks,
+    pub line_numbers: bool,
+    pub tab_width: usize,
+    /// Whether `code` parses, `None` when its language isn't checked.
+    pub validation: Option<Result<(), SyntaxError>>,
+}
+
+/// The parts of a line, as [`lines`] splits it.
+type Line<'a> = [(Option<TokenKind>, &'a str)];
+
+fn view_line(number: usize, line: &Line, target: bool, error: Option<&SyntaxError>) -> Html {
+    let parts = if line.is_empty() {
+        html! { "\u{a0}" }
+    } else {
+        line.iter()
+            .map(|(kind, text)| match kind {
+                Some(kind) => html! { <span class={kind.css_class()}>{*text}</span> },
+                None => html! { {*text} },
+            })
+            .collect::<Html>()
+    };
+    // like the export, starting with an `alt` line
+    html! {
+        <li
+            key={number}
+            class={classes!(
+                (number % 2 == 1).then_some("alt"),
+                target.then_some("target"),
+                error.is_some().then_some("error"),
+            )}
+            title={error.map(|error| AttrValue::from(error.message.clone()))}
+        >
+            <span>{parts}</span>
+        </li>
+    }
+}
+
+/// The `.dp-highlighter` markup the HTML export writes, so the export's stylesheet styles both.
+#[function_component]
+pub fn Preview(props: &PreviewProps) -> Html {
+    // byte offsets the lines start at
+    let starts = use_memo(props.code.clone(), |code| {
+        std::iter::once(0)
+            .chain(code.match_indices('\n').map(|(index, _)| index + 1))
+            .collect::<Vec<_>>()
+    });
+    let container = use_node_ref();
+    let scroll_top = use_state(|| 0);
+    let height = use_state(|| {
+        web_sys::window()
+            .and_then(|window| window.inner_height().ok())
+            .and_then(|height| height.as_f64())
+            .map_or(1000, |height| height as usize)
+    });
+    // line number jumped to, highlighted until the next jump
+    let target = use_state(|| None::<usize>);
+
+    let onscroll = {
+        let scroll_top = scroll_top.setter();
+        let height = height.setter();
+        Callback::from(move |event: Event| {
+            let element = event.target_unchecked_into::<Element>();
+            scroll_top.set(element.scroll_top().max(0) as usize);
+            height.set(element.client_height().max(0) as usize);
+        })
+    };
+
+    let count = starts.len();
+    let jump = {
+        let container = container.clone();
+        let target = target.setter();
+        let height = *height;
+        Callback::from(move |number: usize| {
+            let number = number.clamp(1, count.max(1));
+            if let Some(element) = container.cast::<Element>() {
+                // centers the line in the view
+                let top = ((number - 1) * LINE_HEIGHT_PX).saturating_sub(height / 2);
+                element.set_scroll_top(top as i32);
+            }
+            target.set(Some(number));
+        })
+    };
+    let onkeydown = {
+        let jump = jump.clone();
+        Callback::from(move |event: KeyboardEvent| {
+            if event.key() != "Enter" {
+                return;
+            }
+            let input = event.target_unchecked_into::<HtmlInputElement>();
+            if let Ok(number) = input.value().trim().parse::<usize>() {
+                jump.emit(number);
+            }
+        })
+    };
+
+    let error = props
+        .validation
+        .as_ref()
+        .and_then(|result| result.as_ref().err());
+    let validation = match &props.validation {
+        Some(Err(error)) => {
+            let line = error.line;
+            html! {
+                <button
+                    type="button"
+                    class="btn btn-link btn-sm text-danger text-start mb-0 p-0"
+                    title="Go to the error"
+                    onclick={jump.reform(move |_: MouseEvent| line)}
+                >
+                    {format!("Parse error on {error}")}
+                </button>
+            }
+        }
+        Some(Ok(())) => html! { <span class="badge bg-success">{"Valid"}</span> },
+        None => html! {},
+    };
+
+    let first = (*scroll_top / LINE_HEIGHT_PX)
+        .saturating_sub(BUFFER_LINES)
+        .min(count);
+    let last = ((*scroll_top + *height) / LINE_HEIGHT_PX + 1 + BUFFER_LINES).min(count);
+    let code = &props.code;
+    let start = starts.get(first).copied().unwrap_or(code.len());
+    // without the line break ending the last visible line
+    let end = starts.get(last).map_or(code.len(), |next| next - 1);
+    let tokens = props
+        .tokens
+        .range(start..end)
+        .map(|token| {
+            Token::new(
+                token.kind,
+                token.range.start - start..token.range.end - start,
+            )
+        })
+        .collect::<Vec<_>>();
+    let visible = lines(&code[start..end], &tokens)
+        .iter()
+        .take(last - first)
+        .enumerate()
+        .map(|(index, line)| {
+            let number = first + index + 1;
+            let error = error.filter(|error| error.line == number);
+            view_line(number, line, *target == Some(number), error)
+        })
+        .collect::<Html>();
+
+    html! {
+        <>
+            <div class="d-flex align-items-center">
+                {validation}
+                <input
+                    type="number"
+                    class="form-control form-control-sm w-auto ms-auto"
+                    min="1"
+                    max={count.to_string()}
+                    placeholder="Go to line"
+                    aria-label="Go to line"
+                    {onkeydown}
+                />
+            </div>
+            <div
+                ref={container}
+                class={classes!("dp-highlighter", (!props.line_numbers).then_some("nogutter"))}
+                style={format!("tab-size:{};max-height:{MAX_HEIGHT};", props.tab_width)}
+                {onscroll}
+            >
+                <div style={format!("height:{}px", first * LINE_HEIGHT_PX)}></div>
+                <ol start={(first + 1).to_string()}>{visible}</ol>
+                <div style={format!("height:{}px", (count - last) * LINE_HEIGHT_PX)}></div>
+            </div>
+        </>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
+
+    use super::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    /// Renders the preview into a fresh element of the page, once Yew got to it.
+    async fn render(props: PreviewProps) -> Element {
+        let document = web_sys::window().unwrap().document().unwrap();
+        let root = document.create_element("div").unwrap();
+        document.body().unwrap().append_child(&root).unwrap();
+        yew::Renderer::<Preview>::with_root_and_props(root.clone(), props).render();
+        yew::platform::time::sleep(Duration::ZERO).await;
+        root
+    }
+
+    fn props(code: &str, tokens: Vec<Token>) -> PreviewProps {
+        PreviewProps {
+            code: AttrValue::from(code.to_string()),
+            tokens: TokenChunks::from(tokens),
+            line_numbers: true,
+            tab_width: 4,
+            validation: None,
+        }
+    }
+
+    #[wasm_bindgen_test]
+    async fn renders_tokens_as_spans_and_code_as_text() {
+        let code = "<script>alert(1)</script>\nplain";
+        let tokens = vec![
+            Token::new(TokenKind::Tag, 0..1),
+            Token::new(TokenKind::TagName, 1..7),
+        ];
+        let root = render(props(code, tokens)).await;
+
+        assert!(root.query_selector("script").unwrap().is_none());
+        let lines = root.query_selector_all("ol > li").unwrap();
+        assert_eq!(lines.length(), 2);
+        let first = root.query_selector("ol > li").unwrap().unwrap();
+        assert_eq!(first.text_content().unwrap(), "<script>alert(1)</script>");
+        let name = first.query_selector("span.tag-name").unwrap().unwrap();
+        assert_eq!(name.text_content().unwrap(), "script");
+        assert!(root.query_selector("li.alt").unwrap().is_some());
+    }
+
+    #[wasm_bindgen_test]
+    async fn marks_the_line_of_a_parse_error() {
+        let root = render(PreviewProps {
+            validation: Some(Err(SyntaxError::new(2, 3, "expected a value"))),
+            ..props("{\n  ,\n}", Vec::new())
+        })
+        .await;
+
+        let error = root.query_selector("li.error").unwrap().unwrap();
+        assert_eq!(error.text_content().unwrap(), "  ,");
+        assert_eq!(
+            error.get_attribute("title").as_deref(),
+            Some("expected a value")
+        );
+        let button = root.query_selector("button").unwrap().unwrap();
+        assert_eq!(
+            button.text_content().unwrap(),
+            "Parse error on line 2, column 3: expected a value"
+        );
+    }
+
+    #[wasm_bindgen_test]
+    async fn shows_valid_code() {
+        let root = render(PreviewProps {
+            validation: Some(Ok(())),
+            ..props("{}", Vec::new())
+        })
+        .await;
+
+        let badge = root.query_selector(".badge").unwrap().unwrap();
+        assert_eq!(badge.text_content().unwrap(), "Valid");
+        assert!(root.query_selector("li.error").unwrap().is_none());
+    }
+}
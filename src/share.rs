@@ -0,0 +1,200 @@
+//! Links carrying a snippet in their `#fragment`, so sharing code never involves a server.
+//!
+//! A fragment reads `v1.` followed by the deflated JSON of a [`Shared`], base64url encoded.
+
+use std::fmt;
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use serde_derive::{Deserialize, Serialize};
+
+use crate::state::State;
+
+const FORMAT_PREFIX: &str = "v1.";
+/// Longest fragment created or accepted, links much longer than this get cut off by chat and
+/// mail clients.
+pub const MAX_FRAGMENT_LENGTH: usize = 32 * 1024;
+/// Most bytes a fragment may inflate to, so a crafted link can't exhaust the memory.
+const MAX_PAYLOAD_LENGTH: usize = 1024 * 1024;
+/// Level `miniz_oxide` compresses with, its best.
+const COMPRESSION_LEVEL: u8 = 10;
+
+/// The part of the [`State`] a link shares.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Shared {
+    pub code: String,
+    pub programming_language: Option<String>,
+    pub theme: String,
+    pub line_numbers: bool,
+}
+
+impl From<&State> for Shared {
+    fn from(state: &State) -> Self {
+        Shared {
+            code: state.code.clone(),
+            programming_language: state.programming_language.clone(),
+            theme: state.theme.clone(),
+            line_numbers: state.line_numbers,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ShareError {
+    /// The link was made by a newer version of the app.
+    UnknownVersion,
+    /// The code doesn't fit into a link, or the link is longer than any this app creates.
+    TooLarge,
+    /// The link got cut off or altered.
+    Corrupted,
+}
+
+impl fmt::Display for ShareError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ShareError::UnknownVersion => write!(f, "the link was made by a newer version"),
+            ShareError::TooLarge => write!(f, "the code is too large to share as a link"),
+            ShareError::Corrupted => write!(f, "the link is incomplete or damaged"),
+        }
+    }
+}
+
+impl std::error::Error for ShareError {}
+
+pub fn encode(shared: &Shared) -> Result<String, ShareError> {
+    let json = serde_json::to_vec(shared).map_err(|_| ShareError::Corrupted)?;
+    if json.len() > MAX_PAYLOAD_LENGTH {
+        return Err(ShareError::TooLarge);
+    }
+    let compressed = miniz_oxide::deflate::compress_to_vec(&json, COMPRESSION_LEVEL);
+    let fragment = format!("{FORMAT_PREFIX}{}", URL_SAFE_NO_PAD.encode(compressed));
+
+    if fragment.len() > MAX_FRAGMENT_LENGTH {
+        return Err(ShareError::TooLarge);
+    }
+    Ok(fragment)
+}
+
+/// Decodes a fragment made by [`encode`], with or without its leading `#`.
+pub fn decode(fragment: &str) -> Result<Shared, ShareError> {
+    let fragment = fragment.trim_start_matches('#');
+    if fragment.len() > MAX_FRAGMENT_LENGTH {
+        return Err(ShareError::TooLarge);
+    }
+    let Some(payload) = fragment.strip_prefix(FORMAT_PREFIX) else {
+        let versioned = fragment.split_once('.').is_some_and(|(version, _)| {
+            version
+                .strip_prefix('v')
+                .is_some_and(|n| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()))
+        });
+        return Err(match versioned {
+            true => ShareError::UnknownVersion,
+            false => ShareError::Corrupted,
+        });
+    };
+
+    let compressed = URL_SAFE_NO_PAD
+        .decode(payload)
+        .map_err(|_| ShareError::Corrupted)?;
+    let json = miniz_oxide::inflate::decompress_to_vec_with_limit(&compressed, MAX_PAYLOAD_LENGTH)
+        .map_err(|error| match error.status {
+            miniz_oxide::inflate::TINFLStatus::HasMoreOutput => ShareError::TooLarge,
+            _ => ShareError::Corrupted,
+        })?;
+    serde_json::from_slice(&json).map_err(|_| ShareError::Corrupted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shared(code: String) -> Shared {
+        Shared {
+            code,
+            programming_language: Some("rust".to_string()),
+            theme: "Default".to_string(),
+            line_numbers: true,
+        }
+    }
+
+    /// Code that hardly compresses, from a small linear congruential generator.
+    fn noise(len: usize) -> String {
+        let mut seed = 0x2545_f491_u32;
+        (0..len)
+            .map(|_| {
+                seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+                char::from(b'!' + (seed >> 16) as u8 % 90)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn links_round_trip() {
+        let original = shared("fn main() {\n    println!(\"ünïcode → #&?\");\n}\n".to_string());
+        let fragment = encode(&original).unwrap();
+        assert!(fragment.starts_with(FORMAT_PREFIX));
+        assert!(fragment
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b"-_.".contains(&b)));
+        assert_eq!(decode(&fragment).unwrap(), original);
+        assert_eq!(decode(&format!("#{fragment}")).unwrap(), original);
+    }
+
+    #[test]
+    fn unknown_prefixes_are_rejected() {
+        let fragment = encode(&shared("x".to_string())).unwrap();
+        let payload = fragment.strip_prefix(FORMAT_PREFIX).unwrap();
+        assert_eq!(
+            decode(&format!("v2.{payload}")),
+            Err(ShareError::UnknownVersion)
+        );
+        for fragment in [
+            "",
+            "v1",
+            "vx.abc",
+            "v.abc",
+            &format!("V1.{payload}"),
+            payload,
+        ] {
+            assert_eq!(decode(fragment), Err(ShareError::Corrupted), "{fragment:?}");
+        }
+    }
+
+    #[test]
+    fn damaged_links_are_rejected() {
+        let fragment = encode(&shared("let x = 1;".to_string())).unwrap();
+        assert_eq!(
+            decode(&fragment[..fragment.len() - 4]),
+            Err(ShareError::Corrupted)
+        );
+        assert_eq!(decode("v1.not*base64"), Err(ShareError::Corrupted));
+        let not_json = miniz_oxide::deflate::compress_to_vec(b"{\"code\":", COMPRESSION_LEVEL);
+        assert_eq!(
+            decode(&format!("v1.{}", URL_SAFE_NO_PAD.encode(not_json))),
+            Err(ShareError::Corrupted)
+        );
+    }
+
+    #[test]
+    fn oversized_code_and_links_are_rejected() {
+        assert_eq!(
+            encode(&shared(noise(MAX_FRAGMENT_LENGTH))),
+            Err(ShareError::TooLarge)
+        );
+        assert_eq!(
+            encode(&shared(" ".repeat(MAX_PAYLOAD_LENGTH))),
+            Err(ShareError::TooLarge)
+        );
+        let long = format!("v1.{}", "A".repeat(MAX_FRAGMENT_LENGTH));
+        assert_eq!(decode(&long), Err(ShareError::TooLarge));
+    }
+
+    #[test]
+    fn payloads_inflating_past_the_limit_are_rejected() {
+        let bomb = vec![b' '; MAX_PAYLOAD_LENGTH + 1];
+        let compressed = miniz_oxide::deflate::compress_to_vec(&bomb, COMPRESSION_LEVEL);
+        let fragment = format!("v1.{}", URL_SAFE_NO_PAD.encode(compressed));
+        assert!(fragment.len() <= MAX_FRAGMENT_LENGTH);
+        assert_eq!(decode(&fragment), Err(ShareError::TooLarge));
+    }
+}
@@ -0,0 +1,141 @@
+//! The snippet library, code samples kept in IndexedDB for reuse.
+
+use serde_derive::{Deserialize, Serialize};
+use wasm_bindgen::JsValue;
+use web_sys::{IdbDatabase, IdbObjectStoreParameters};
+
+use crate::utils::indexed_db::Database;
+
+const DATABASE_NAME: &str = "codestyle";
+const DATABASE_VERSION: u32 = 1;
+const SNIPPETS_STORE: &str = "snippets";
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Snippet {
+    pub id: String,
+    pub title: String,
+    pub code: String,
+    /// Id of the language in the registry.
+    pub language: Option<String>,
+    pub tags: Vec<String>,
+    /// Milliseconds since the unix epoch.
+    pub created: f64,
+    /// Milliseconds since the unix epoch.
+    pub updated: f64,
+    pub line_numbers: bool,
+    /// Id of the theme.
+    pub theme: String,
+}
+
+impl Snippet {
+    /// A snippet with a fresh id, created now.
+    pub fn new(title: String, code: String) -> Self {
+        let now = js_sys::Date::now();
+        Snippet {
+            id: format!(
+                "{:x}-{:08x}",
+                now as u64,
+                (js_sys::Math::random() * u32::MAX as f64) as u32
+            ),
+            title,
+            code,
+            language: None,
+            tags: Vec::new(),
+            created: now,
+            updated: now,
+            line_numbers: false,
+            theme: String::new(),
+        }
+    }
+
+    /// A copy stored under a fresh id, its title marking it as such.
+    pub fn duplicate(&self) -> Self {
+        Snippet {
+            language: self.language.clone(),
+            tags: self.tags.clone(),
+            line_numbers: self.line_numbers,
+            theme: self.theme.clone(),
+            ..Snippet::new(format!("{} (copy)", self.title), self.code.clone())
+        }
+    }
+
+    /// Whether the title, a tag, the language or the code contain `query`, ignoring case.
+    pub fn matches(&self, query: &str) -> bool {
+        let query = query.trim().to_lowercase();
+        query.is_empty()
+            || self.title.to_lowercase().contains(&query)
+            || self
+                .tags
+                .iter()
+                .any(|tag| tag.to_lowercase().contains(&query))
+            || self
+                .language
+                .as_ref()
+                .is_some_and(|language| language.contains(&query))
+            || self.code.to_lowercase().contains(&query)
+    }
+}
+
+/// Splits comma separated tags, dropping empty ones.
+pub fn parse_tags(tags: &str) -> Vec<String> {
+    tags.split(',')
+        .map(str::trim)
+        .filter(|tag| !tag.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn upgrade(database: &IdbDatabase) -> Result<(), JsValue> {
+    if !database.object_store_names().contains(SNIPPETS_STORE) {
+        let parameters = IdbObjectStoreParameters::new();
+        parameters.set_key_path(&JsValue::from_str("id"));
+        database.create_object_store_with_optional_parameters(SNIPPETS_STORE, &parameters)?;
+    }
+    Ok(())
+}
+
+/// The snippets stored in the browser.
+#[derive(Clone, Debug)]
+pub struct Library(Database);
+
+impl Library {
+    pub async fn open() -> Result<Self, JsValue> {
+        Database::open(DATABASE_NAME, DATABASE_VERSION, upgrade)
+            .await
+            .map(Library)
+    }
+
+    /// Every stored snippet, the most recently updated first.
+    pub async fn snippets(&self) -> Result<Vec<Snippet>, JsValue> {
+        let mut snippets = self
+            .0
+            .get_all(SNIPPETS_STORE)
+            .await?
+            .into_iter()
+            .filter_map(|record| {
+                let json = js_sys::JSON::stringify(&record).ok()?.as_string()?;
+                match serde_json::from_str::<Snippet>(&json) {
+                    Ok(snippet) => Some(snippet),
+                    Err(error) => {
+                        log::warn!("Skipping unreadable snippet: {}", error);
+                        None
+                    }
+                }
+            })
+            .collect::<Vec<_>>();
+        snippets.sort_by(|a, b| b.updated.total_cmp(&a.updated));
+        Ok(snippets)
+    }
+
+    /// Stores `snippet`, replacing the one with the same id.
+    pub async fn save(&self, snippet: &Snippet) -> Result<(), JsValue> {
+        let json = serde_json::to_string(snippet).map_err(|e| JsValue::from_str(&e.to_string()))?;
+        self.0
+            .put(SNIPPETS_STORE, &js_sys::JSON::parse(&json)?)
+            .await
+    }
+
+    pub async fn delete(&self, id: &str) -> Result<(), JsValue> {
+        self.0.delete(SNIPPETS_STORE, &JsValue::from_str(id)).await
+    }
+}
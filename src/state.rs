@@ -0,0 +1,157 @@
+//! The working state of the app, kept in local storage so it survives reloads.
+
+use log::{error, warn};
+use serde_derive::{Deserialize, Serialize};
+use serde_json::Value;
+
+use gloo_storage::LocalStorage;
+use gloo_storage::Storage;
+
+use planetb_syntax_highligher::export::image;
+use planetb_syntax_highligher::theme::Theme;
+
+const STATE_KEY: &str = "codestyle.state";
+/// Version of the schema [`State`] is stored with, bumped on every incompatible change.
+const STATE_VERSION: u64 = 1;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct State {
+    /// Whether the welcome message shows on startup.
+    pub show_info: bool,
+    pub code: String,
+    /// Id of the selected language in the registry.
+    pub programming_language: Option<String>,
+    /// Whether the user picked the language, which detection then leaves alone.
+    pub language_chosen: bool,
+    pub line_numbers: bool,
+    /// Id of the selected theme.
+    pub theme: String,
+    /// How the last image was exported.
+    #[serde(default)]
+    pub image: image::Options,
+}
+
+impl Default for State {
+    fn default() -> Self {
+        State {
+            show_info: true,
+            code: "".to_string(),
+            programming_language: None,
+            language_chosen: false,
+            line_numbers: false,
+            theme: Theme::default().id,
+            image: image::Options::default(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct StoredState<S> {
+    version: u64,
+    #[serde(flatten)]
+    state: S,
+}
+
+/// What was stored before the schema got a version, only whether to show the welcome message.
+#[derive(Deserialize)]
+struct StoredStateV0 {
+    show_info: Option<bool>,
+}
+
+impl From<StoredStateV0> for State {
+    fn from(stored: StoredStateV0) -> Self {
+        State {
+            show_info: stored.show_info.unwrap_or(true),
+            ..State::default()
+        }
+    }
+}
+
+impl State {
+    /// Restores the stored state, upgrading older schemas, or starts afresh.
+    pub fn load() -> Self {
+        match LocalStorage::get::<Value>(STATE_KEY) {
+            Ok(stored) => State::from_stored(stored),
+            Err(_) => State::default(),
+        }
+    }
+
+    /// Reads the state from what [`State::store`] wrote with any version of the schema.
+    fn from_stored(stored: Value) -> Self {
+        let version = stored.get("version").and_then(Value::as_u64).unwrap_or(0);
+
+        let state = match version {
+            0 => serde_json::from_value::<StoredStateV0>(stored).map(State::from),
+            STATE_VERSION => serde_json::from_value::<StoredState<State>>(stored).map(|s| s.state),
+            _ => {
+                warn!("Ignoring state stored with the unknown version {}", version);
+                return State::default();
+            }
+        };
+        state.unwrap_or_else(|error| {
+            warn!("Ignoring unreadable state: {}", error);
+            State::default()
+        })
+    }
+
+    pub fn store(&self) {
+        let stored = StoredState {
+            version: STATE_VERSION,
+            state: self,
+        };
+        if let Err(error) = LocalStorage::set(STATE_KEY, stored) {
+            error!("Storing the state failed: {:?}", error);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn unversioned_state_keeps_only_the_welcome_message() {
+        let state = State::from_stored(json!({"show_info": false, "code": "ignored"}));
+        assert_eq!(
+            state,
+            State {
+                show_info: false,
+                ..State::default()
+            }
+        );
+        assert_eq!(State::from_stored(json!({})), State::default());
+    }
+
+    #[test]
+    fn stored_state_round_trips() {
+        let state = State {
+            show_info: false,
+            code: "fn main() {}".to_string(),
+            programming_language: Some("rust".to_string()),
+            language_chosen: true,
+            line_numbers: true,
+            ..State::default()
+        };
+        let stored = serde_json::to_value(StoredState {
+            version: STATE_VERSION,
+            state: &state,
+        })
+        .unwrap();
+        assert_eq!(stored["version"], json!(STATE_VERSION));
+        assert_eq!(State::from_stored(stored), state);
+    }
+
+    #[test]
+    fn unknown_or_unreadable_state_starts_afresh() {
+        let newer = json!({"version": STATE_VERSION + 1, "show_info": false});
+        assert_eq!(State::from_stored(newer), State::default());
+        let broken = json!({"version": STATE_VERSION, "code": 1});
+        assert_eq!(State::from_stored(broken), State::default());
+        assert_eq!(
+            State::from_stored(json!({"show_info": "no"})),
+            State::default()
+        );
+    }
+}
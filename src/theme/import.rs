@@ -0,0 +1,656 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use serde_derive::Deserialize;
+
+use super::{Color, Style, Theme};
+use crate::highlighter::TokenKind;
+
+/// How deep `var()` references of sublime color schemes are followed.
+const MAX_VARIABLE_DEPTH: usize = 8;
+
+/// Why a color theme file couldn't be imported.
+#[derive(Debug)]
+pub enum ImportError {
+    Json(serde_json::Error),
+    Plist(plist::Error),
+    /// VS Code themes can keep their token colors in a separate file, which isn't at hand.
+    ExternalTokenColors(String),
+    /// The file parsed, but doesn't color a single scope.
+    NoTokenColors,
+}
+
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImportError::Json(error) => write!(f, "not a valid JSON theme: {error}"),
+            ImportError::Plist(error) => write!(f, "not a valid tmTheme: {error}"),
+            ImportError::ExternalTokenColors(path) => {
+                write!(
+                    f,
+                    "the token colors are kept in `{path}`, import that file instead"
+                )
+            }
+            ImportError::NoTokenColors => write!(f, "the theme doesn't define any token colors"),
+        }
+    }
+}
+
+impl std::error::Error for ImportError {}
+
+/// Imports a VS Code `.json` theme, a TextMate `.tmTheme` or a `.sublime-color-scheme`.
+///
+/// The scopes the theme colors are mapped onto the token kinds, missing layout colors are taken
+/// from the built-in theme closest to it. `file_name` names the theme if the file doesn't.
+pub fn import(source: &str, file_name: &str) -> Result<Theme, ImportError> {
+    let name = file_name
+        .rsplit_once('.')
+        .map_or(file_name, |(stem, _)| stem)
+        .to_string();
+
+    if source.trim_start().starts_with('<') {
+        let theme =
+            plist::from_bytes::<TextMateTheme>(source.as_bytes()).map_err(ImportError::Plist)?;
+        return theme.into_theme(name);
+    }
+
+    let json = serde_json::from_str::<serde_json::Value>(&strip_comments(source))
+        .map_err(ImportError::Json)?;
+    if json.get("rules").is_some() {
+        serde_json::from_value::<SublimeScheme>(json)
+            .map_err(ImportError::Json)?
+            .into_theme(name)
+    } else {
+        serde_json::from_value::<VsCodeTheme>(json)
+            .map_err(ImportError::Json)?
+            .into_theme(name)
+    }
+}
+
+/// `.tmTheme` plists, the first scopeless entry of `settings` holding the editor colors.
+#[derive(Deserialize)]
+struct TextMateTheme {
+    name: Option<String>,
+    settings: Vec<TextMateRule>,
+}
+
+#[derive(Deserialize)]
+struct TextMateRule {
+    scope: Option<TextMateScope>,
+    #[serde(default)]
+    settings: TextMateSettings,
+}
+
+/// Scope selectors, comma separated or, in VS Code themes, also as a list.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum TextMateScope {
+    One(String),
+    Many(Vec<String>),
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct TextMateSettings {
+    foreground: Option<String>,
+    background: Option<String>,
+    font_style: Option<String>,
+    gutter: Option<String>,
+    gutter_foreground: Option<String>,
+    line_highlight: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct VsCodeTheme {
+    name: Option<String>,
+    /// `light`, `dark` or `hc` for high contrast.
+    #[serde(rename = "type")]
+    kind: Option<String>,
+    #[serde(default)]
+    colors: HashMap<String, String>,
+    #[serde(rename = "tokenColors")]
+    token_colors: Option<TokenColors>,
+    /// Token colors of themes written before `tokenColors` existed.
+    settings: Option<Vec<TextMateRule>>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum TokenColors {
+    Rules(Vec<TextMateRule>),
+    Path(String),
+}
+
+#[derive(Deserialize)]
+struct SublimeScheme {
+    name: Option<String>,
+    #[serde(default)]
+    variables: HashMap<String, String>,
+    #[serde(default)]
+    globals: HashMap<String, String>,
+    rules: Vec<SublimeRule>,
+}
+
+#[derive(Deserialize)]
+struct SublimeRule {
+    scope: Option<String>,
+    foreground: Option<String>,
+    background: Option<String>,
+    font_style: Option<String>,
+}
+
+/// Colors around the code, as the theme file spells them.
+#[derive(Default)]
+struct Editor {
+    /// Whether the theme is meant to be dark, guessed from the background if it doesn't say.
+    kind: Option<String>,
+    foreground: Option<String>,
+    background: Option<String>,
+    line_highlight: Option<String>,
+    gutter: Option<String>,
+    gutter_background: Option<String>,
+    border: Option<String>,
+}
+
+/// A scope rule, its selectors split up.
+struct Rule {
+    selectors: Vec<String>,
+    foreground: Option<String>,
+    background: Option<String>,
+    font_style: Option<String>,
+}
+
+impl TextMateRule {
+    fn into_rule(self) -> Option<Rule> {
+        let selectors = match self.scope? {
+            TextMateScope::One(scope) => split_selectors(&scope),
+            TextMateScope::Many(scopes) => scopes.iter().flat_map(|s| split_selectors(s)).collect(),
+        };
+        Some(Rule {
+            selectors,
+            foreground: self.settings.foreground,
+            background: self.settings.background,
+            font_style: self.settings.font_style,
+        })
+    }
+}
+
+impl TextMateTheme {
+    fn into_theme(self, file_name: String) -> Result<Theme, ImportError> {
+        let mut editor = Editor::default();
+        let mut rules = Vec::new();
+
+        for rule in self.settings {
+            if rule.scope.is_none() {
+                let settings = rule.settings;
+                editor = Editor {
+                    foreground: settings.foreground,
+                    background: settings.background,
+                    line_highlight: settings.line_highlight,
+                    gutter: settings.gutter_foreground,
+                    gutter_background: settings.gutter,
+                    ..Editor::default()
+                };
+            } else {
+                rules.extend(rule.into_rule());
+            }
+        }
+
+        build(self.name.unwrap_or(file_name), editor, rules)
+    }
+}
+
+impl VsCodeTheme {
+    fn into_theme(mut self, file_name: String) -> Result<Theme, ImportError> {
+        let rules = match self.token_colors {
+            Some(TokenColors::Rules(rules)) => rules,
+            Some(TokenColors::Path(path)) => return Err(ImportError::ExternalTokenColors(path)),
+            None => self.settings.unwrap_or_default(),
+        };
+
+        // old style themes keep the editor colors in their scopeless first rule
+        let (global, rules) = rules
+            .into_iter()
+            .partition::<Vec<_>, _>(|rule| rule.scope.is_none());
+        let global = global.into_iter().next().map(|rule| rule.settings);
+        let mut color = |key: &str| self.colors.remove(key);
+
+        let editor = Editor {
+            kind: self.kind,
+            foreground: color("editor.foreground")
+                .or_else(|| global.as_ref().and_then(|g| g.foreground.clone())),
+            background: color("editor.background")
+                .or_else(|| global.as_ref().and_then(|g| g.background.clone())),
+            line_highlight: color("editor.lineHighlightBackground"),
+            gutter: color("editorLineNumber.foreground"),
+            gutter_background: color("editorGutter.background"),
+            border: color("editorLineNumber.activeForeground"),
+        };
+        let rules = rules
+            .into_iter()
+            .filter_map(TextMateRule::into_rule)
+            .collect();
+
+        build(self.name.unwrap_or(file_name), editor, rules)
+    }
+}
+
+impl SublimeScheme {
+    fn into_theme(mut self, file_name: String) -> Result<Theme, ImportError> {
+        let variables = self.variables;
+        let resolve = |value: Option<String>| value.map(|v| resolve_variables(&v, &variables, 0));
+        let mut global = |key: &str| resolve(self.globals.remove(key));
+
+        let editor = Editor {
+            foreground: global("foreground"),
+            background: global("background"),
+            line_highlight: global("line_highlight"),
+            gutter: global("gutter_foreground"),
+            gutter_background: global("gutter"),
+            ..Editor::default()
+        };
+        let rules = self
+            .rules
+            .into_iter()
+            .filter_map(|rule| {
+                Some(Rule {
+                    selectors: split_selectors(&rule.scope?),
+                    foreground: resolve(rule.foreground),
+                    background: resolve(rule.background),
+                    font_style: rule.font_style,
+                })
+            })
+            .collect();
+
+        build(self.name.unwrap_or(file_name), editor, rules)
+    }
+}
+
+/// Replaces sublime's `var(name)` references with the variables' values.
+fn resolve_variables(value: &str, variables: &HashMap<String, String>, depth: usize) -> String {
+    let Some(name) = value
+        .trim()
+        .strip_prefix("var(")
+        .and_then(|rest| rest.strip_suffix(')'))
+    else {
+        return value.to_string();
+    };
+    match variables.get(name.trim()) {
+        Some(value) if depth < MAX_VARIABLE_DEPTH => resolve_variables(value, variables, depth + 1),
+        _ => value.to_string(),
+    }
+}
+
+/// Splits a comma separated scope selector, leaving out descendant and exclusion selectors,
+/// which never match as tokens don't know the scopes they're nested in.
+fn split_selectors(scope: &str) -> Vec<String> {
+    scope
+        .split(',')
+        .map(str::trim)
+        .filter(|selector| !selector.is_empty() && !selector.contains(char::is_whitespace))
+        .map(str::to_string)
+        .collect()
+}
+
+/// TextMate scopes a token kind stands for, most fitting first.
+fn scopes(kind: TokenKind) -> &'static [&'static str] {
+    match kind {
+        TokenKind::Comment => &["comment"],
+        TokenKind::String => &["string.quoted", "string"],
+        TokenKind::Keyword => &["keyword.control", "storage.modifier", "keyword"],
+        TokenKind::Preprocessor => &["meta.preprocessor", "keyword.control.directive"],
+        TokenKind::Number => &["constant.numeric"],
+        TokenKind::DataType => &["storage.type", "support.type", "entity.name.type"],
+        TokenKind::Value => &["support.constant.property-value", "constant.language"],
+        TokenKind::Important => &["keyword.other.important", "keyword"],
+        TokenKind::Directive => &["keyword.control.directive", "meta.preprocessor"],
+        TokenKind::Annotation => &["storage.type.annotation", "meta.annotation"],
+        TokenKind::Decorator => &["entity.name.function.decorator", "meta.decorator"],
+        TokenKind::Variable => &["variable.other", "variable"],
+        TokenKind::Function => &["entity.name.function", "support.function"],
+        TokenKind::Operator => &["keyword.operator"],
+        TokenKind::Symbol => &["constant.other.symbol"],
+        TokenKind::Builtin => &["support.function.builtin", "support.function"],
+        TokenKind::Special => &["variable.language"],
+        TokenKind::Tag => &["punctuation.definition.tag"],
+        TokenKind::TagName => &["entity.name.tag"],
+        TokenKind::Attribute => &["entity.other.attribute-name"],
+        TokenKind::AttributeValue => &["string.quoted", "string"],
+        TokenKind::CData => &["string.unquoted.cdata", "meta.tag.metadata.cdata"],
+    }
+}
+
+/// Whether `selector` matches `scope`, being it or one of its parents.
+fn matches(selector: &str, scope: &str) -> bool {
+    scope
+        .strip_prefix(selector)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+}
+
+/// The settings for `scope` the way TextMate combines them: every setting comes from the most
+/// specific selector setting it, later rules winning among equally specific ones.
+fn resolve<'a>(
+    rules: &'a [Rule],
+    scope: &str,
+) -> Option<(Option<&'a str>, Option<&'a str>, Option<&'a str>)> {
+    let mut matching = rules
+        .iter()
+        .enumerate()
+        .filter_map(|(index, rule)| {
+            let specificity = rule
+                .selectors
+                .iter()
+                .filter(|selector| matches(selector, scope))
+                .map(|selector| selector.split('.').count())
+                .max()?;
+            Some((specificity, index, rule))
+        })
+        .collect::<Vec<_>>();
+    if matching.is_empty() {
+        return None;
+    }
+    matching.sort_by_key(|(specificity, index, _)| (*specificity, *index));
+
+    let (mut foreground, mut background, mut font_style) = (None, None, None);
+    for (_, _, rule) in matching {
+        foreground = rule.foreground.as_deref().or(foreground);
+        background = rule.background.as_deref().or(background);
+        font_style = rule.font_style.as_deref().or(font_style);
+    }
+    Some((foreground, background, font_style))
+}
+
+/// Parses `#rgb`, `#rgba`, `#rrggbb` and `#rrggbbaa`, translucent colors laid over `under`.
+fn parse_color(value: &str, under: Color) -> Option<Color> {
+    let hex = value.trim().trim_start_matches('#');
+    // byte lengths, `get` turns away values whose non-ASCII characters they would split
+    let (rgb, alpha) = match hex.len() {
+        4 => (
+            hex.get(..3)?,
+            u8::from_str_radix(hex.get(3..)?, 16).ok()? * 17,
+        ),
+        8 => (hex.get(..6)?, u8::from_str_radix(hex.get(6..)?, 16).ok()?),
+        _ => (hex, 255),
+    };
+    Some(Color::from_hex(rgb)?.blend(under, alpha))
+}
+
+fn is_dark(color: Color) -> bool {
+    let luma = 0.299 * color.r as f32 + 0.587 * color.g as f32 + 0.114 * color.b as f32;
+    luma < 128.0
+}
+
+fn slug(name: &str) -> String {
+    let slug = name
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+    format!("custom-{slug}")
+}
+
+fn build(name: String, editor: Editor, rules: Vec<Rule>) -> Result<Theme, ImportError> {
+    if rules.is_empty() {
+        return Err(ImportError::NoTokenColors);
+    }
+
+    let background = editor
+        .background
+        .as_deref()
+        .and_then(|value| parse_color(value, Color::rgb(0xff, 0xff, 0xff)));
+    let base = match editor.kind.as_deref() {
+        Some("hc") | Some("hcDark") => Theme::high_contrast(),
+        Some("dark") => Theme::dark(),
+        Some(_) => Theme::light(),
+        None if background.is_some_and(is_dark) => Theme::dark(),
+        None => Theme::light(),
+    };
+    let background = background.unwrap_or(base.background);
+    let color = |value: &Option<String>, fallback: Color| {
+        value
+            .as_deref()
+            .and_then(|value| parse_color(value, background))
+            .unwrap_or(fallback)
+    };
+
+    let text = color(&editor.foreground, base.text);
+    let gutter = color(&editor.gutter, base.gutter);
+    let styles = TokenKind::ALL
+        .into_iter()
+        .filter_map(|kind| {
+            let (foreground, token_background, font_style) = scopes(kind)
+                .iter()
+                .find_map(|scope| resolve(&rules, scope))?;
+            let font_style = font_style.unwrap_or_default();
+            let style = Style {
+                color: foreground.and_then(|value| parse_color(value, background)),
+                background: token_background.and_then(|value| parse_color(value, background)),
+                bold: font_style.contains("bold"),
+                italic: font_style.contains("italic"),
+            };
+            Some((kind, style))
+        })
+        .collect();
+
+    Ok(Theme {
+        id: slug(&name),
+        name,
+        text,
+        background,
+        alt_background: color(&editor.line_highlight, background),
+        gutter,
+        gutter_background: color(&editor.gutter_background, background),
+        border: color(&editor.border, gutter),
+        styles,
+    })
+}
+
+/// Drops the comments and trailing commas VS Code allows in its JSON files.
+fn strip_comments(source: &str) -> String {
+    let mut json = String::with_capacity(source.len());
+    let mut chars = source.chars().peekable();
+    // a comma is held back until it's clear it doesn't trail
+    let mut comma = None;
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                json.extend(comma.take());
+                json.push(c);
+                while let Some(c) = chars.next() {
+                    json.push(c);
+                    match c {
+                        '\\' => json.extend(chars.next()),
+                        '"' => break,
+                        _ => {}
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'/') => while chars.next_if(|&c| c != '\n').is_some() {},
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut previous = ' ';
+                for c in chars.by_ref() {
+                    if previous == '*' && c == '/' {
+                        break;
+                    }
+                    previous = c;
+                }
+            }
+            ',' => {
+                json.extend(comma.replace(c));
+            }
+            '}' | ']' => {
+                comma = None;
+                json.push(c);
+            }
+            c if c.is_whitespace() => json.push(c),
+            c => {
+                json.extend(comma.take());
+                json.push(c);
+            }
+        }
+    }
+
+    json.extend(comma);
+    json
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WHITE: Color = Color::rgb(0xff, 0xff, 0xff);
+
+    #[test]
+    fn parses_colors_with_and_without_alpha() {
+        assert_eq!(parse_color("#fff", WHITE), Some(WHITE));
+        assert_eq!(
+            parse_color(" #102030 ", WHITE),
+            Some(Color::rgb(0x10, 0x20, 0x30))
+        );
+        assert_eq!(parse_color("#0000", WHITE), Some(WHITE));
+        assert_eq!(
+            parse_color("#00000080", WHITE),
+            Some(Color::rgb(0x7f, 0x7f, 0x7f))
+        );
+        assert_eq!(parse_color("#12345", WHITE), None);
+        assert_eq!(parse_color("red", WHITE), None);
+    }
+
+    #[test]
+    fn non_ascii_colors_are_rejected() {
+        for value in ["#a€", "#€", "#abcd€", "#a€b€"] {
+            assert_eq!(parse_color(value, WHITE), None, "{value}");
+        }
+
+        let source = r##"{
+            "colors": { "editor.background": "#a€" },
+            "tokenColors": [{ "scope": "comment", "settings": { "foreground": "#ab€" } }]
+        }"##;
+        let theme = import(source, "broken.json").unwrap();
+        assert_eq!(theme.background, Theme::light().background);
+        assert_eq!(theme.style(TokenKind::Comment).color, None);
+    }
+
+    #[test]
+    fn imports_vs_code_themes() {
+        let source = r##"{
+            // comments and trailing commas are fine
+            "name": "Night Owl",
+            "type": "dark",
+            "colors": {
+                "editor.background": "#011627",
+                "editor.foreground": "#d6deeb",
+            },
+            "tokenColors": [
+                { "scope": "comment", "settings": { "foreground": "#637777", "fontStyle": "italic" } },
+                { "scope": ["keyword", "storage.type"], "settings": { "foreground": "#c792ea" } },
+                { "scope": "keyword.control", "settings": { "fontStyle": "bold" } },
+            ],
+        }"##;
+        let theme = import(source, "night-owl.json").unwrap();
+
+        assert_eq!(theme.id, "custom-night-owl");
+        assert_eq!(theme.name, "Night Owl");
+        assert_eq!(theme.background, Color::rgb(0x01, 0x16, 0x27));
+        assert_eq!(theme.text, Color::rgb(0xd6, 0xde, 0xeb));
+        assert_eq!(theme.gutter, Theme::dark().gutter);
+
+        let comment = theme.style(TokenKind::Comment);
+        assert_eq!(comment.color, Some(Color::rgb(0x63, 0x77, 0x77)));
+        assert!(comment.italic && !comment.bold);
+        // the more specific selector only sets the font style, the color comes from `keyword`
+        let keyword = theme.style(TokenKind::Keyword);
+        assert_eq!(keyword.color, Some(Color::rgb(0xc7, 0x92, 0xea)));
+        assert!(keyword.bold);
+        assert_eq!(
+            theme.style(TokenKind::DataType).color,
+            Some(Color::rgb(0xc7, 0x92, 0xea))
+        );
+        assert_eq!(theme.style(TokenKind::Number), Style::default());
+    }
+
+    #[test]
+    fn imports_text_mate_themes() {
+        let source = r#"<?xml version="1.0" encoding="UTF-8"?>
+<plist version="1.0">
+<dict>
+    <key>settings</key>
+    <array>
+        <dict>
+            <key>settings</key>
+            <dict>
+                <key>background</key><string>#FFFFFF</string>
+                <key>foreground</key><string>#000000</string>
+                <key>lineHighlight</key><string>#00000010</string>
+            </dict>
+        </dict>
+        <dict>
+            <key>scope</key><string>string, constant.numeric</string>
+            <key>settings</key>
+            <dict><key>foreground</key><string>#A31515</string></dict>
+        </dict>
+    </array>
+</dict>
+</plist>"#;
+        let theme = import(source, "Paper.tmTheme").unwrap();
+
+        assert_eq!(theme.name, "Paper");
+        assert_eq!(theme.alt_background, Color::rgb(0xef, 0xef, 0xef));
+        let red = Some(Color::rgb(0xa3, 0x15, 0x15));
+        assert_eq!(theme.style(TokenKind::String).color, red);
+        assert_eq!(theme.style(TokenKind::Number).color, red);
+    }
+
+    #[test]
+    fn imports_sublime_color_schemes() {
+        let source = r##"{
+            "variables": { "black": "#222", "accent": "var(black)" },
+            "globals": { "background": "var(black)" },
+            "rules": [
+                { "scope": "comment", "foreground": "var(accent)", "font_style": "italic" },
+                { "scope": "source.rust comment", "foreground": "#fff" },
+            ]
+        }"##;
+        let theme = import(source, "Dim.sublime-color-scheme").unwrap();
+
+        assert_eq!(theme.background, Color::rgb(0x22, 0x22, 0x22));
+        // dark, judging by the background
+        assert_eq!(theme.gutter, Theme::dark().gutter);
+        let comment = theme.style(TokenKind::Comment);
+        assert_eq!(comment.color, Some(Color::rgb(0x22, 0x22, 0x22)));
+        assert!(comment.italic);
+    }
+
+    #[test]
+    fn rejects_themes_without_token_colors() {
+        assert!(matches!(
+            import(r#"{ "tokenColors": "./themes/dark.json" }"#, "x.json"),
+            Err(ImportError::ExternalTokenColors(path)) if path == "./themes/dark.json"
+        ));
+        assert!(matches!(
+            import(
+                r##"{ "colors": { "editor.background": "#000" } }"##,
+                "x.json"
+            ),
+            Err(ImportError::NoTokenColors)
+        ));
+        assert!(matches!(
+            import("{ not json", "x.json"),
+            Err(ImportError::Json(_))
+        ));
+        assert!(matches!(
+            import("<plist>", "x.tmTheme"),
+            Err(ImportError::Plist(_))
+        ));
+    }
+
+    #[test]
+    fn strips_comments_and_trailing_commas_outside_strings() {
+        let json = strip_comments(r#"{ "a": "//x,]", /* b */ "c": [1, 2,], }"#);
+        let value = serde_json::from_str::<serde_json::Value>(&json).unwrap();
+        assert_eq!(value, serde_json::json!({ "a": "//x,]", "c": [1, 2] }));
+    }
+}
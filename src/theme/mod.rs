@@ -0,0 +1,438 @@
+//! Colors and font styles tokens are rendered with, shared by the preview and every export.
+
+use std::collections::HashMap;
+
+use serde_derive::{Deserialize, Serialize};
+
+use crate::highlighter::TokenKind;
+
+mod import;
+
+pub use import::{import, ImportError};
+
+/// Font stack of `.dp-highlighter` in `SyntaxHighlighter.css`.
+pub const FONT_FAMILY: &str = "Consolas, 'Courier New', Courier, monospace";
+/// Font size of `.dp-highlighter` in points, matching its 12px.
+pub const FONT_SIZE_PT: f32 = 9.0;
+
+/// An opaque color, stored as its `#rrggbb` notation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(into = "String", try_from = "String")]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Color {
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Color { r, g, b }
+    }
+
+    /// Parses `#rgb` and `#rrggbb` notations, the `#` being optional.
+    pub fn from_hex(hex: &str) -> Option<Self> {
+        let hex = hex.trim().trim_start_matches('#');
+        let channel = |i: usize, len: usize| u8::from_str_radix(hex.get(i..i + len)?, 16).ok();
+
+        match hex.len() {
+            3 => Some(Color::rgb(
+                channel(0, 1)? * 17,
+                channel(1, 1)? * 17,
+                channel(2, 1)? * 17,
+            )),
+            6 => Some(Color::rgb(channel(0, 2)?, channel(2, 2)?, channel(4, 2)?)),
+            _ => None,
+        }
+    }
+
+    pub fn to_hex(self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
+
+    /// This color with `alpha` opacity laid over `background`.
+    pub fn blend(self, background: Color, alpha: u8) -> Self {
+        let mix = |fg: u8, bg: u8| {
+            ((fg as u32 * alpha as u32 + bg as u32 * (255 - alpha as u32) + 127) / 255) as u8
+        };
+        Color::rgb(
+            mix(self.r, background.r),
+            mix(self.g, background.g),
+            mix(self.b, background.b),
+        )
+    }
+}
+
+impl From<Color> for String {
+    fn from(color: Color) -> Self {
+        color.to_hex()
+    }
+}
+
+impl TryFrom<String> for Color {
+    type Error = String;
+
+    fn try_from(hex: String) -> Result<Self, Self::Error> {
+        Color::from_hex(&hex).ok_or_else(|| format!("invalid color `{hex}`"))
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Style {
+    pub color: Option<Color>,
+    pub background: Option<Color>,
+    pub bold: bool,
+    pub italic: bool,
+}
+
+impl Style {
+    pub const fn color(color: Color) -> Self {
+        Style {
+            color: Some(color),
+            background: None,
+            bold: false,
+            italic: false,
+        }
+    }
+
+    pub const fn bold(self) -> Self {
+        Style { bold: true, ..self }
+    }
+
+    pub const fn italic(self) -> Self {
+        Style {
+            italic: true,
+            ..self
+        }
+    }
+
+    /// The style as CSS declarations, e.g. `color:#008200;font-weight:bold;`.
+    pub fn to_css(self) -> String {
+        let mut css = String::new();
+        if let Some(color) = self.color {
+            css.push_str(&format!("color:{};", color.to_hex()));
+        }
+        if let Some(background) = self.background {
+            css.push_str(&format!("background-color:{};", background.to_hex()));
+        }
+        if self.bold {
+            css.push_str("font-weight:bold;");
+        }
+        if self.italic {
+            css.push_str("font-style:italic;");
+        }
+        css
+    }
+}
+
+/// A color scheme, the styles of every token kind plus the colors of the surrounding layout.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Theme {
+    pub id: String,
+    pub name: String,
+    /// Code which isn't part of any token.
+    pub text: Color,
+    /// Background of the code lines.
+    pub background: Color,
+    /// Background of every other line, starting with the first.
+    pub alt_background: Color,
+    /// Line numbers.
+    pub gutter: Color,
+    /// Background behind the line numbers.
+    pub gutter_background: Color,
+    /// Line separating the line numbers from the code.
+    pub border: Color,
+    pub styles: HashMap<TokenKind, Style>,
+}
+
+impl Theme {
+    /// Style of `kind`, tokens without one are rendered like plain text.
+    pub fn style(&self, kind: TokenKind) -> Style {
+        self.styles.get(&kind).copied().unwrap_or_default()
+    }
+
+    /// The themes shipped with the app, the default first.
+    pub fn builtin() -> Vec<Theme> {
+        vec![
+            Theme::light(),
+            Theme::dark(),
+            Theme::solarized(),
+            Theme::high_contrast(),
+            Theme::print(),
+        ]
+    }
+
+    /// The built-in theme `id`.
+    pub fn find(id: &str) -> Option<Theme> {
+        Theme::builtin().into_iter().find(|theme| theme.id == id)
+    }
+
+    /// The colors of `SyntaxHighlighter.css` and the brushes' own styles.
+    pub fn light() -> Self {
+        let comment = Style::color(Color::rgb(0x00, 0x82, 0x00));
+        let string = Style::color(Color::rgb(0x00, 0x00, 0xff));
+        let keyword = Style::color(Color::rgb(0x00, 0x66, 0x99)).bold();
+        let gray = Style::color(Color::rgb(0x80, 0x80, 0x80));
+        let red = Style::color(Color::rgb(0xff, 0x00, 0x00));
+        let annotation = Style::color(Color::rgb(0x64, 0x64, 0x64));
+        let pink = Style::color(Color::rgb(0xff, 0x14, 0x93));
+
+        Theme {
+            id: "light".to_string(),
+            name: "Light".to_string(),
+            text: Color::rgb(0x00, 0x00, 0x00),
+            background: Color::rgb(0xf8, 0xf8, 0xf8),
+            alt_background: Color::rgb(0xff, 0xff, 0xff),
+            gutter: Color::rgb(0x5c, 0x5c, 0x5c),
+            gutter_background: Color::rgb(0xe7, 0xe5, 0xdc),
+            border: Color::rgb(0x17, 0x81, 0xe3),
+            styles: HashMap::from([
+                (TokenKind::Comment, comment),
+                (TokenKind::String, string),
+                (TokenKind::Keyword, keyword),
+                (TokenKind::Preprocessor, gray),
+                (
+                    TokenKind::Number,
+                    Style::color(Color::rgb(0xc0, 0x00, 0x00)),
+                ),
+                (
+                    TokenKind::DataType,
+                    Style::color(Color::rgb(0x2e, 0x8b, 0x57)).bold(),
+                ),
+                (TokenKind::Value, Style::color(Color::rgb(0x00, 0x00, 0x00))),
+                (TokenKind::Important, red),
+                (
+                    TokenKind::Directive,
+                    Style::color(Color::rgb(0x00, 0x82, 0x84)),
+                ),
+                (TokenKind::Annotation, annotation),
+                (TokenKind::Decorator, annotation),
+                (
+                    TokenKind::Variable,
+                    Style::color(Color::rgb(0xdd, 0x00, 0x00)),
+                ),
+                (TokenKind::Function, pink),
+                (TokenKind::Operator, gray),
+                (
+                    TokenKind::Symbol,
+                    Style::color(Color::rgb(0xaa, 0x77, 0x00)),
+                ),
+                (TokenKind::Builtin, pink),
+                (
+                    TokenKind::Special,
+                    Style::color(Color::rgb(0xa5, 0x2a, 0x2a)),
+                ),
+                (TokenKind::Tag, keyword),
+                (TokenKind::TagName, keyword),
+                (TokenKind::Attribute, red),
+                (TokenKind::AttributeValue, string),
+                (TokenKind::CData, pink),
+            ]),
+        }
+    }
+
+    pub fn dark() -> Self {
+        let string = Style::color(Color::rgb(0xce, 0x91, 0x78));
+        let keyword = Style::color(Color::rgb(0x56, 0x9c, 0xd6));
+        let purple = Style::color(Color::rgb(0xc5, 0x86, 0xc0));
+        let teal = Style::color(Color::rgb(0x4e, 0xc9, 0xb0));
+        let yellow = Style::color(Color::rgb(0xdc, 0xdc, 0xaa));
+        let light_blue = Style::color(Color::rgb(0x9c, 0xdc, 0xfe));
+
+        Theme {
+            id: "dark".to_string(),
+            name: "Dark".to_string(),
+            text: Color::rgb(0xd4, 0xd4, 0xd4),
+            background: Color::rgb(0x1e, 0x1e, 0x1e),
+            alt_background: Color::rgb(0x25, 0x25, 0x26),
+            gutter: Color::rgb(0x85, 0x85, 0x85),
+            gutter_background: Color::rgb(0x1e, 0x1e, 0x1e),
+            border: Color::rgb(0x56, 0x9c, 0xd6),
+            styles: HashMap::from([
+                (
+                    TokenKind::Comment,
+                    Style::color(Color::rgb(0x6a, 0x99, 0x55)).italic(),
+                ),
+                (TokenKind::String, string),
+                (TokenKind::Keyword, keyword.bold()),
+                (TokenKind::Preprocessor, purple),
+                (
+                    TokenKind::Number,
+                    Style::color(Color::rgb(0xb5, 0xce, 0xa8)),
+                ),
+                (TokenKind::DataType, teal),
+                (TokenKind::Value, string),
+                (
+                    TokenKind::Important,
+                    Style::color(Color::rgb(0xf4, 0x47, 0x47)).bold(),
+                ),
+                (TokenKind::Directive, purple),
+                (TokenKind::Annotation, yellow),
+                (TokenKind::Decorator, yellow),
+                (TokenKind::Variable, light_blue),
+                (TokenKind::Function, yellow),
+                (
+                    TokenKind::Operator,
+                    Style::color(Color::rgb(0xd4, 0xd4, 0xd4)),
+                ),
+                (
+                    TokenKind::Symbol,
+                    Style::color(Color::rgb(0x4f, 0xc1, 0xff)),
+                ),
+                (TokenKind::Builtin, teal),
+                (TokenKind::Special, keyword),
+                (TokenKind::Tag, Style::color(Color::rgb(0x80, 0x80, 0x80))),
+                (TokenKind::TagName, keyword),
+                (TokenKind::Attribute, light_blue),
+                (TokenKind::AttributeValue, string),
+                (TokenKind::CData, purple),
+            ]),
+        }
+    }
+
+    /// Ethan Schoonover's solarized, in its light variant.
+    pub fn solarized() -> Self {
+        let yellow = Style::color(Color::rgb(0xb5, 0x89, 0x00));
+        let orange = Style::color(Color::rgb(0xcb, 0x4b, 0x16));
+        let red = Style::color(Color::rgb(0xdc, 0x32, 0x2f));
+        let magenta = Style::color(Color::rgb(0xd3, 0x36, 0x82));
+        let violet = Style::color(Color::rgb(0x6c, 0x71, 0xc4));
+        let blue = Style::color(Color::rgb(0x26, 0x8b, 0xd2));
+        let cyan = Style::color(Color::rgb(0x2a, 0xa1, 0x98));
+        let green = Style::color(Color::rgb(0x85, 0x99, 0x00));
+
+        Theme {
+            id: "solarized".to_string(),
+            name: "Solarized".to_string(),
+            text: Color::rgb(0x65, 0x7b, 0x83),
+            background: Color::rgb(0xfd, 0xf6, 0xe3),
+            alt_background: Color::rgb(0xee, 0xe8, 0xd5),
+            gutter: Color::rgb(0x93, 0xa1, 0xa1),
+            gutter_background: Color::rgb(0xee, 0xe8, 0xd5),
+            border: Color::rgb(0x93, 0xa1, 0xa1),
+            styles: HashMap::from([
+                (
+                    TokenKind::Comment,
+                    Style::color(Color::rgb(0x93, 0xa1, 0xa1)).italic(),
+                ),
+                (TokenKind::String, cyan),
+                (TokenKind::Keyword, green),
+                (TokenKind::Preprocessor, orange),
+                (TokenKind::Number, magenta),
+                (TokenKind::DataType, yellow),
+                (TokenKind::Value, cyan),
+                (TokenKind::Important, red.bold()),
+                (TokenKind::Directive, orange),
+                (TokenKind::Annotation, violet),
+                (TokenKind::Decorator, violet),
+                (TokenKind::Variable, blue),
+                (TokenKind::Function, blue),
+                (
+                    TokenKind::Operator,
+                    Style::color(Color::rgb(0x58, 0x6e, 0x75)),
+                ),
+                (TokenKind::Symbol, magenta),
+                (TokenKind::Builtin, yellow),
+                (TokenKind::Special, orange),
+                (TokenKind::Tag, blue),
+                (TokenKind::TagName, blue),
+                (TokenKind::Attribute, yellow),
+                (TokenKind::AttributeValue, cyan),
+                (TokenKind::CData, violet),
+            ]),
+        }
+    }
+
+    /// Bright colors on black, all of them well above the WCAG AAA contrast ratio.
+    pub fn high_contrast() -> Self {
+        let white = Style::color(Color::rgb(0xff, 0xff, 0xff));
+        let yellow = Style::color(Color::rgb(0xff, 0xff, 0x00));
+        let cyan = Style::color(Color::rgb(0x00, 0xff, 0xff));
+        let green = Style::color(Color::rgb(0x00, 0xff, 0x00));
+        let pink = Style::color(Color::rgb(0xff, 0x99, 0xff));
+        let orange = Style::color(Color::rgb(0xff, 0xc0, 0x66));
+
+        Theme {
+            id: "high-contrast".to_string(),
+            name: "High contrast".to_string(),
+            text: Color::rgb(0xff, 0xff, 0xff),
+            background: Color::rgb(0x00, 0x00, 0x00),
+            alt_background: Color::rgb(0x00, 0x00, 0x00),
+            gutter: Color::rgb(0xff, 0xff, 0x00),
+            gutter_background: Color::rgb(0x00, 0x00, 0x00),
+            border: Color::rgb(0xff, 0xff, 0xff),
+            styles: HashMap::from([
+                (TokenKind::Comment, green.italic()),
+                (TokenKind::String, yellow),
+                (TokenKind::Keyword, cyan.bold()),
+                (TokenKind::Preprocessor, pink),
+                (TokenKind::Number, orange),
+                (TokenKind::DataType, cyan.bold()),
+                (TokenKind::Value, yellow),
+                (TokenKind::Important, pink.bold()),
+                (TokenKind::Directive, pink),
+                (TokenKind::Annotation, pink),
+                (TokenKind::Decorator, pink),
+                (TokenKind::Variable, orange),
+                (TokenKind::Function, cyan),
+                (TokenKind::Operator, white),
+                (TokenKind::Symbol, orange),
+                (TokenKind::Builtin, cyan),
+                (TokenKind::Special, orange.bold()),
+                (TokenKind::Tag, white),
+                (TokenKind::TagName, cyan.bold()),
+                (TokenKind::Attribute, orange),
+                (TokenKind::AttributeValue, yellow),
+                (TokenKind::CData, pink),
+            ]),
+        }
+    }
+
+    /// Black on white, telling tokens apart by weight, slant and shades of gray to save ink.
+    pub fn print() -> Self {
+        let black = Style::color(Color::rgb(0x00, 0x00, 0x00));
+        let gray = Style::color(Color::rgb(0x55, 0x55, 0x55));
+        let dark_gray = Style::color(Color::rgb(0x33, 0x33, 0x33));
+
+        Theme {
+            id: "print".to_string(),
+            name: "Print friendly".to_string(),
+            text: Color::rgb(0x00, 0x00, 0x00),
+            background: Color::rgb(0xff, 0xff, 0xff),
+            alt_background: Color::rgb(0xff, 0xff, 0xff),
+            gutter: Color::rgb(0x66, 0x66, 0x66),
+            gutter_background: Color::rgb(0xff, 0xff, 0xff),
+            border: Color::rgb(0xcc, 0xcc, 0xcc),
+            styles: HashMap::from([
+                (TokenKind::Comment, gray.italic()),
+                (TokenKind::String, dark_gray),
+                (TokenKind::Keyword, black.bold()),
+                (TokenKind::Preprocessor, gray),
+                (TokenKind::Number, dark_gray),
+                (TokenKind::DataType, black.bold()),
+                (TokenKind::Value, dark_gray),
+                (TokenKind::Important, black.bold()),
+                (TokenKind::Directive, gray),
+                (TokenKind::Annotation, gray.italic()),
+                (TokenKind::Decorator, gray.italic()),
+                (TokenKind::Variable, black),
+                (TokenKind::Function, black),
+                (TokenKind::Operator, black),
+                (TokenKind::Symbol, dark_gray),
+                (TokenKind::Builtin, black.bold()),
+                (TokenKind::Special, black.italic()),
+                (TokenKind::Tag, black),
+                (TokenKind::TagName, black.bold()),
+                (TokenKind::Attribute, dark_gray.italic()),
+                (TokenKind::AttributeValue, dark_gray),
+                (TokenKind::CData, gray),
+            ]),
+        }
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::light()
+    }
+}
@@ -0,0 +1,103 @@
+use std::future::Future;
+
+use js_sys::{Array, Object, Promise, Reflect};
+use log::debug;
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{Blob, BlobPropertyBag, ClipboardEvent, ClipboardItem, HtmlDocument};
+
+/// Puts the same content in several formats on the clipboard, given as MIME type and content.
+///
+/// Prefers the async Clipboard API, but most browsers refuse `text/rtf` there, in which case the
+/// content is copied by filling in the data of a `copy` event instead. Browsers only allow that
+/// during a user gesture, so call this right in the click handler, before awaiting anything.
+pub fn write(formats: &[(&str, &str)]) -> impl Future<Output = Result<(), JsValue>> {
+    let promise = clipboard_write(formats).unwrap_or_else(|error| {
+        debug!("Clipboard API failed: {:?}", error);
+        None
+    });
+    let copied = match promise {
+        Some(_) => Ok(()),
+        None => write_on_copy_event(formats),
+    };
+
+    async move {
+        copied?;
+        if let Some(promise) = promise {
+            JsFuture::from(promise).await?;
+        }
+        Ok(())
+    }
+}
+
+/// Starts writing to the clipboard with the Clipboard API, `None` when it is missing or doesn't
+/// take every one of the formats.
+fn clipboard_write(formats: &[(&str, &str)]) -> Result<Option<Promise>, JsValue> {
+    let window = web_sys::window().expect("no global `window` exists");
+    let navigator = window.navigator();
+
+    if !Reflect::has(&navigator, &"clipboard".into())? {
+        return Ok(None);
+    }
+    let Ok(item_class) = Reflect::get(&window, &"ClipboardItem".into()) else {
+        return Ok(None);
+    };
+    if item_class.is_undefined() {
+        return Ok(None);
+    }
+    // older browsers lack `ClipboardItem.supports()` and only handle these two
+    let supports = |mime: &str| {
+        if Reflect::has(&item_class, &"supports".into()).unwrap_or(false) {
+            ClipboardItem::supports(mime)
+        } else {
+            matches!(mime, "text/plain" | "text/html")
+        }
+    };
+    if !formats.iter().all(|(mime, _)| supports(mime)) {
+        return Ok(None);
+    }
+
+    let record = Object::new();
+    for (mime, content) in formats {
+        let options = BlobPropertyBag::new();
+        options.set_type(mime);
+        let blob =
+            Blob::new_with_str_sequence_and_options(&Array::of1(&(*content).into()), &options)?;
+        Reflect::set(&record, &(*mime).into(), &blob)?;
+    }
+    let item = ClipboardItem::new_with_record_from_str_to_blob_promise(&record)?;
+
+    Ok(Some(navigator.clipboard().write(&Array::of1(&item))))
+}
+
+fn write_on_copy_event(formats: &[(&str, &str)]) -> Result<(), JsValue> {
+    let document: HtmlDocument = web_sys::window()
+        .and_then(|window| window.document())
+        .expect("should have a document on window")
+        .dyn_into()?;
+
+    let formats = formats
+        .iter()
+        .map(|(mime, content)| (mime.to_string(), content.to_string()))
+        .collect::<Vec<_>>();
+    let listener = Closure::<dyn FnMut(ClipboardEvent)>::new(move |event: ClipboardEvent| {
+        if let Some(data) = event.clipboard_data() {
+            for (mime, content) in &formats {
+                if let Err(error) = data.set_data(mime, content) {
+                    debug!("Could not set {} on the clipboard: {:?}", mime, error);
+                }
+            }
+            event.prevent_default();
+        }
+    });
+
+    document.add_event_listener_with_callback("copy", listener.as_ref().unchecked_ref())?;
+    let copied = document.exec_command("copy");
+    document.remove_event_listener_with_callback("copy", listener.as_ref().unchecked_ref())?;
+
+    match copied? {
+        true => Ok(()),
+        false => Err("the copy command was rejected".into()),
+    }
+}
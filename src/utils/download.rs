@@ -0,0 +1,42 @@
+use gloo_timers::callback::Timeout;
+use js_sys::{Array, Uint8Array};
+use log::debug;
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{Blob, BlobPropertyBag, HtmlAnchorElement, Url};
+
+/// How long the file stays available after the download starts. Browsers fetch it
+/// asynchronously, some only once the user confirms a save dialog, so revoking it right after
+/// the click can fail the download.
+const REVOKE_DELAY_MS: u32 = 40_000;
+
+/// Offers `content` to the user as a file download named `file_name`.
+pub fn download(file_name: &str, mime: &str, content: &[u8]) -> Result<(), JsValue> {
+    let document = web_sys::window()
+        .and_then(|window| window.document())
+        .expect("should have a document on window");
+
+    let options = BlobPropertyBag::new();
+    options.set_type(mime);
+    let blob = Blob::new_with_u8_array_sequence_and_options(
+        &Array::of1(&Uint8Array::from(content)),
+        &options,
+    )?;
+    let url = Url::create_object_url_with_blob(&blob)?;
+
+    // firefox only follows links which are part of the document
+    let anchor: HtmlAnchorElement = document.create_element("a")?.dyn_into()?;
+    anchor.set_href(&url);
+    anchor.set_download(file_name);
+    let body = document.body().expect("document should have a body");
+    body.append_child(&anchor)?;
+    anchor.click();
+    body.remove_child(&anchor)?;
+
+    Timeout::new(REVOKE_DELAY_MS, move || {
+        if let Err(error) = Url::revoke_object_url(&url) {
+            debug!("Revoking {} failed: {:?}", url, error);
+        }
+    })
+    .forget();
+    Ok(())
+}
@@ -0,0 +1,84 @@
+use js_sys::{Array, Function, Promise};
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{IdbDatabase, IdbObjectStore, IdbRequest, IdbTransactionMode};
+
+/// An open IndexedDB database, with promise based access to its object stores.
+#[derive(Clone, Debug)]
+pub struct Database(IdbDatabase);
+
+/// Resolves once `request` succeeds, with its result.
+async fn wait(request: &IdbRequest) -> Result<JsValue, JsValue> {
+    let promise = Promise::new(&mut |resolve: Function, reject: Function| {
+        let succeeded = request.clone();
+        let on_success = Closure::once_into_js(move || {
+            resolve.call1(&JsValue::NULL, &succeeded.result().unwrap_or_default())
+        });
+        let failed = request.clone();
+        let on_error = Closure::once_into_js(move || {
+            let error = failed.error().ok().flatten().map(JsValue::from);
+            reject.call1(&JsValue::NULL, &error.unwrap_or_default())
+        });
+        request.set_onsuccess(Some(on_success.unchecked_ref()));
+        request.set_onerror(Some(on_error.unchecked_ref()));
+    });
+    JsFuture::from(promise).await
+}
+
+impl Database {
+    /// Opens the database `name`, `upgrade` creating its object stores whenever `version` is
+    /// newer than the one the browser holds.
+    pub async fn open(
+        name: &str,
+        version: u32,
+        upgrade: fn(&IdbDatabase) -> Result<(), JsValue>,
+    ) -> Result<Self, JsValue> {
+        let factory = web_sys::window()
+            .expect("no global `window` exists")
+            .indexed_db()?
+            .ok_or_else(|| JsValue::from_str("IndexedDB isn't available"))?;
+        let request = factory.open_with_u32(name, version)?;
+
+        let upgrading = request.clone();
+        let on_upgrade = Closure::<dyn FnMut()>::new(move || {
+            let database = upgrading.result().map(IdbDatabase::unchecked_from_js);
+            if let Err(error) = database.and_then(|database| upgrade(&database)) {
+                log::error!("Upgrading the database failed: {:?}", error);
+            }
+        });
+        request.set_onupgradeneeded(Some(on_upgrade.as_ref().unchecked_ref()));
+
+        let database = wait(&request).await;
+        request.set_onupgradeneeded(None);
+        Ok(Database(database?.unchecked_into()))
+    }
+
+    fn store(&self, name: &str, mode: IdbTransactionMode) -> Result<IdbObjectStore, JsValue> {
+        self.0
+            .transaction_with_str_and_mode(name, mode)?
+            .object_store(name)
+    }
+
+    /// Every record of the object store `store`, in key order.
+    pub async fn get_all(&self, store: &str) -> Result<Vec<JsValue>, JsValue> {
+        let request = self.store(store, IdbTransactionMode::Readonly)?.get_all()?;
+        let records = wait(&request).await?;
+        Ok(records.unchecked_into::<Array>().to_vec())
+    }
+
+    /// Inserts `value`, replacing the record with the same key.
+    pub async fn put(&self, store: &str, value: &JsValue) -> Result<(), JsValue> {
+        let request = self
+            .store(store, IdbTransactionMode::Readwrite)?
+            .put(value)?;
+        wait(&request).await.map(drop)
+    }
+
+    pub async fn delete(&self, store: &str, key: &JsValue) -> Result<(), JsValue> {
+        let request = self
+            .store(store, IdbTransactionMode::Readwrite)?
+            .delete(key)?;
+        wait(&request).await.map(drop)
+    }
+}
@@ -1,2 +1,5 @@
-pub mod highlighter;
+pub mod clipboard;
+pub mod download;
 pub mod images;
+pub mod indexed_db;
+pub mod upload;
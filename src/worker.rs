@@ -0,0 +1,234 @@
+//! Highlighting off the main thread, in a Web Worker built from the `planetb-worker` binary.
+//!
+//! The app sends a [`HighlightRequest`] per edit. The worker answers with the detected languages
+//! and then streams the tokens back in chunks of lines, so large documents show up gradually
+//! instead of blocking the UI. A newer request cancels the one still in progress. Once all lines
+//! are tokenized, languages with a parser have the code checked against it.
+//!
+//! The worker highlights the languages of a [`Languages`] type, so a crate adding its own brushes
+//! builds a worker of `HighlightWorker<TheirLanguages>` rather than forking this one.
+
+use std::marker::PhantomData;
+
+use gloo_timers::callback::Timeout;
+use gloo_worker::{HandlerId, Worker, WorkerScope};
+use serde_derive::{Deserialize, Serialize};
+
+use crate::highlighter::{detect, Incremental, Registry, SyntaxError, Token, UpdateStats};
+
+/// Path the app loads the worker from, as `trunk` emits it.
+pub const WORKER_PATH: &str = "/planetb-worker.js";
+
+/// Most guesses a [`HighlightResponse::Detected`] lists.
+const MAX_GUESSES: usize = 3;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct HighlightOptions {
+    /// Whether to detect the language and answer with [`HighlightResponse::Detected`] first.
+    pub detect: bool,
+    /// Lines tokenized before sending them back and checking for newer requests.
+    pub chunk_lines: usize,
+}
+
+impl Default for HighlightOptions {
+    fn default() -> Self {
+        HighlightOptions {
+            detect: true,
+            chunk_lines: 2000,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct HighlightRequest {
+    /// Increases with every request, responses carry the id of the request they answer.
+    pub id: u64,
+    pub code: String,
+    /// Language id or alias, the most likely detected language when missing.
+    pub language: Option<String>,
+    pub options: HighlightOptions,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum HighlightResponse {
+    /// Ids of the likely languages and their confidence, most likely first.
+    Detected {
+        id: u64,
+        guesses: Vec<(String, f32)>,
+        /// Milliseconds the detection took.
+        elapsed: f64,
+    },
+    /// Tokens from `from_offset` on, replacing those the previous chunks sent from there.
+    ///
+    /// Chunks build on each other even across requests, so they are applied in order whether or
+    /// not their request is still the latest.
+    Tokens {
+        id: u64,
+        /// Id of the language the tokens are for, `None` when it is unknown and there are none.
+        language: Option<String>,
+        from_offset: usize,
+        tokens: Vec<Token>,
+        stats: UpdateStats,
+    },
+    /// Whether the code parses, following the last chunk of tokens. `None` when the language
+    /// isn't checked or unknown.
+    Checked {
+        id: u64,
+        result: Option<Result<(), SyntaxError>>,
+    },
+}
+
+/// The languages a [`HighlightWorker`] knows, named by a type as the worker is created without
+/// arguments in a context of its own.
+pub trait Languages: 'static {
+    fn registry() -> Registry;
+}
+
+/// The languages shipped with the app.
+pub struct BuiltinLanguages;
+
+impl Languages for BuiltinLanguages {
+    fn registry() -> Registry {
+        Registry::builtin()
+    }
+}
+
+/// A request in progress.
+struct Job {
+    handler: HandlerId,
+    request: HighlightRequest,
+    /// Whether the languages were detected, done before the first chunk.
+    started: bool,
+    language: Option<String>,
+}
+
+pub struct HighlightWorker<L: Languages = BuiltinLanguages> {
+    registry: Registry,
+    tokenizer: Incremental,
+    job: Option<Job>,
+    /// Whether a [`Step`] is scheduled already.
+    scheduled: bool,
+    languages: PhantomData<L>,
+}
+
+/// Tokenizes the next chunk of the current job.
+pub struct Step;
+
+impl<L: Languages> HighlightWorker<L> {
+    /// Continues after a timeout rather than right away, so requests that arrived in the meantime
+    /// are received first.
+    fn schedule(&mut self, scope: &WorkerScope<Self>) {
+        if !self.scheduled {
+            self.scheduled = true;
+            let step = scope.callback(|()| Step);
+            Timeout::new(0, move || step(())).forget();
+        }
+    }
+}
+
+impl<L: Languages> Worker for HighlightWorker<L> {
+    type Message = Step;
+    type Input = HighlightRequest;
+    type Output = HighlightResponse;
+
+    fn create(_scope: &WorkerScope<Self>) -> Self {
+        HighlightWorker {
+            registry: L::registry(),
+            tokenizer: Incremental::new(),
+            job: None,
+            scheduled: false,
+            languages: PhantomData,
+        }
+    }
+
+    fn update(&mut self, scope: &WorkerScope<Self>, Step: Step) {
+        self.scheduled = false;
+        let Some(job) = &mut self.job else {
+            return;
+        };
+        let id = job.request.id;
+
+        if !job.started {
+            job.started = true;
+            if job.request.options.detect {
+                let start = js_sys::Date::now();
+                let guesses = detect(&self.registry, &job.request.code);
+                if job.language.is_none() {
+                    job.language = guesses.first().map(|guess| guess.language.id.clone());
+                }
+                let guesses = guesses
+                    .iter()
+                    .take(MAX_GUESSES)
+                    .map(|guess| (guess.language.id.clone(), guess.confidence))
+                    .collect();
+                scope.respond(
+                    job.handler,
+                    HighlightResponse::Detected {
+                        id,
+                        guesses,
+                        elapsed: js_sys::Date::now() - start,
+                    },
+                );
+            }
+        }
+
+        let language = job
+            .language
+            .as_deref()
+            .and_then(|alias| self.registry.find(alias));
+        let Some(language) = language else {
+            self.tokenizer.clear();
+            scope.respond(
+                job.handler,
+                HighlightResponse::Tokens {
+                    id,
+                    language: None,
+                    from_offset: 0,
+                    tokens: Vec::new(),
+                    stats: UpdateStats {
+                        done: true,
+                        ..UpdateStats::default()
+                    },
+                },
+            );
+            scope.respond(job.handler, HighlightResponse::Checked { id, result: None });
+            self.job = None;
+            return;
+        };
+
+        let chunk_lines = job.request.options.chunk_lines.max(1);
+        let stats = self
+            .tokenizer
+            .update_limited(&job.request.code, language, chunk_lines);
+        let (from_offset, tokens) = self.tokenizer.tokens_from(stats.first_changed);
+        scope.respond(
+            job.handler,
+            HighlightResponse::Tokens {
+                id,
+                language: Some(language.id.clone()),
+                from_offset,
+                tokens,
+                stats,
+            },
+        );
+
+        if stats.done {
+            let result = language.check(&job.request.code);
+            scope.respond(job.handler, HighlightResponse::Checked { id, result });
+            self.job = None;
+        } else {
+            self.schedule(scope);
+        }
+    }
+
+    fn received(&mut self, scope: &WorkerScope<Self>, request: HighlightRequest, id: HandlerId) {
+        // whatever is still in progress is stale now
+        self.job = Some(Job {
+            handler: id,
+            language: request.language.clone(),
+            request,
+            started: false,
+        });
+        self.schedule(scope);
+    }
+}